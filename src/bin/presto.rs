@@ -1,20 +1,707 @@
 use clap::Parser;
-use presto_cli::{Dataset, describe, render_tui};
-use std::path::PathBuf;
+use clap::ValueEnum;
+use presto_cli::{
+    AnomalyMethod, ColumnOrder, CorrelationMethod, Dataset, DescribeOptions, Description,
+    ExpectedSchema, FilterOp, OutputFormat, Report, ShapeThresholds, TableFormat, TuiOptions,
+    TypeInference, TypeSampling, ValueTransform, compare_cohorts, describe_with_options,
+    describe_with_progress, render_tui_loading, validate_against_schema,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Presto accelerates preprocessing with precision.", long_about = None)]
 struct Args {
-    #[arg(short = 'p', long = "path", required = true)]
-    path: PathBuf,
+    #[arg(
+        short = 'p',
+        long = "path",
+        required_unless_present_any = ["compare_profiles", "sql"]
+    )]
+    path: Option<PathBuf>,
+    /// Profile the result of a Postgres query instead of a file, e.g.
+    /// `--sql "host=localhost user=me dbname=app" "SELECT * FROM orders"` (requires the `sql` feature).
+    #[arg(long = "sql", num_args = 2, value_names = ["CONN", "QUERY"])]
+    sql: Vec<String>,
+    /// Compare two saved JSON reports (from `e` or --report-json) and print the differences.
+    #[arg(long = "compare-profiles", num_args = 2, value_names = ["OLD", "NEW"])]
+    compare_profiles: Vec<PathBuf>,
+    /// Compare every column between the two groups of a binary column (A/B cohorts) and
+    /// print means, drift, effect size (Cohen's d) and Welch p-values.
+    #[arg(long = "compare-cohorts", value_name = "COL")]
+    compare_cohorts: Option<String>,
+    /// Read PATH as a fixed-width file with these column widths, e.g. `4,10,6`.
+    #[arg(long = "fixed-width", value_name = "WIDTHS", value_delimiter = ',')]
+    fixed_width: Vec<usize>,
+    /// Read column names from FILE (e.g. a UCI `.names` file) and treat PATH as having no header row.
+    #[arg(long, value_name = "FILE")]
+    names: Option<PathBuf>,
+    /// Strip leading/trailing whitespace from every value before profiling.
+    #[arg(long = "trim-whitespace")]
+    trim_whitespace: bool,
+    /// Read numbers written with comma thousands separators (`"1,234"`, `2,345.67`) as numeric.
+    #[arg(long = "thousands-separators")]
+    thousands_separators: bool,
+    /// Count whitespace-only cells (`"   "`) as values; by default they are treated as missing.
+    #[arg(long = "keep-whitespace-cells")]
+    keep_whitespace_cells: bool,
+    /// Merge category spellings that differ only in case or whitespace (`Male`, `male `, `MALE`)
+    /// into the most frequent spelling before profiling.
+    #[arg(long = "merge-case-variants")]
+    merge_case_variants: bool,
+    /// Field delimiter for CSV input (default `,`).
+    #[arg(long, value_name = "CHAR", default_value_t = ',')]
+    delimiter: char,
+    /// Number of leading raw rows shown in the Data tab.
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    head: usize,
+    /// Number of trailing raw rows shown in the Data tab.
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    tail: usize,
+    /// Longest value shown in a TUI table cell or text line; longer values end in `…`.
+    /// Enter on a row still shows them in full.
+    #[arg(long = "max-cell-width", value_name = "N", default_value_t = 40)]
+    max_cell_width: usize,
+    /// Warn that statistics are unreliable when the dataset has fewer than N rows.
+    #[arg(long = "min-rows", value_name = "N")]
+    min_rows: Option<usize>,
+    /// With --min-rows, refuse to profile (exit status 1) instead of only warning.
+    #[arg(long = "enforce-min-rows", requires = "min_rows")]
+    enforce_min_rows: bool,
+    /// Re-read and re-profile the file whenever its modification time changes, refreshing the TUI in place.
+    #[arg(long, conflicts_with = "sql")]
+    watch: bool,
+    /// Only check that the file parses into a non-empty rectangular table, then exit.
+    #[arg(long = "validate-only")]
+    validate_only: bool,
+    /// Show the evidence behind each transform suggestion and anomaly flag.
+    #[arg(long)]
+    explain: bool,
+    /// Column holding per-row frequency weights for weighted statistics.
+    #[arg(long = "weight-col", value_name = "NAME")]
+    weight_col: Option<String>,
+    /// Count rows as duplicates when these key columns match, e.g. `user_id,date`
+    /// (default: all columns).
+    #[arg(long = "dup-key", value_name = "COLS", value_delimiter = ',')]
+    dup_key: Vec<String>,
+    /// Keep these columns in the stats but leave them out of correlations, dependency
+    /// and redundancy, e.g. `id,created_at`.
+    #[arg(
+        long = "exclude-from-analysis",
+        value_name = "COLS",
+        value_delimiter = ','
+    )]
+    exclude_from_analysis: Vec<String>,
+    /// Per-column sentinel treated as missing, e.g. `age=-1` (repeatable).
+    #[arg(long = "missing-code", value_name = "COL=VALUE", value_parser = parse_missing_code)]
+    missing_codes: Vec<(String, String)>,
+    /// Compute a column's statistics on log/abs/sqrt of its values, e.g. `income=log` (repeatable).
+    #[arg(long = "transform", value_name = "COL=log|abs|sqrt", value_parser = parse_transform)]
+    transforms: Vec<(String, ValueTransform)>,
+    /// Profile a column as delimited lists, e.g. `tags:;` (repeatable): list lengths and
+    /// per-element frequencies.
+    #[arg(long = "list-col", value_name = "COL:DELIM", value_parser = parse_list_col)]
+    list_columns: Vec<(String, String)>,
+    /// Append a derived column before profiling, e.g. `ratio=a / b` or `log_income=log(income)`
+    /// (repeatable; supports + - * / ^, parentheses and log/abs/sqrt).
+    #[arg(long = "compute", value_name = "NAME=EXPR", value_parser = parse_computed_column)]
+    computed_columns: Vec<(String, String)>,
+    /// Force a column's type instead of inferring it, e.g. `zip=categorical` (repeatable).
+    #[arg(long = "column-type", value_name = "COL=TYPE", value_parser = parse_column_type)]
+    column_types: Vec<(String, TypeInference)>,
+    /// Profile only rows matching a condition, e.g. `amount > 100` or `status == active`
+    /// (repeatable; all must hold). Operators: == != < <= > >=.
+    #[arg(long = "filter", value_name = "COL OP VALUE", value_parser = parse_filter)]
+    filters: Vec<(String, FilterOp, String)>,
+    /// Estimate distinct counts with HyperLogLog instead of exact hash sets.
+    #[arg(long = "approx-cardinality")]
+    approx_cardinality: bool,
+    /// Scoring used to flag anomalies.
+    #[arg(long = "anomaly-method", value_enum, default_value_t = AnomalyMethodArg::Zscore)]
+    anomaly_method: AnomalyMethodArg,
+    /// Score above which a value is flagged (defaults: 3.0 for zscore, 3.5 for modified-zscore).
+    #[arg(long = "anomaly-threshold", value_name = "SCORE")]
+    anomaly_threshold: Option<f64>,
+    /// Score anomalies against the N neighbouring values (N/2 on each side) instead of the
+    /// whole column; for rows in time order, so local spikes in a trend stand out.
+    #[arg(long = "anomaly-window", value_name = "N")]
+    anomaly_window: Option<usize>,
+    /// Flag numeric columns that a linear combination of other numeric columns predicts
+    /// (R² >= 0.999), such as engineered features or leaked targets.
+    #[arg(long = "linear-deps")]
+    linear_deps: bool,
+    /// |skewness| above which a column is labelled highly skewed and a log transform is suggested.
+    #[arg(long = "skew-threshold", value_name = "S", default_value_t = 1.0)]
+    skew_threshold: f64,
+    /// |skewness| above which a column is labelled moderately skewed.
+    #[arg(
+        long = "moderate-skew-threshold",
+        value_name = "S",
+        default_value_t = 0.5
+    )]
+    moderate_skew_threshold: f64,
+    /// Excess kurtosis separating leptokurtic (above) from platykurtic (below) columns.
+    #[arg(long = "kurtosis-threshold", value_name = "K", default_value_t = 0.0)]
+    kurtosis_threshold: f64,
+    /// Validate against an expected schema (JSON) and exit non-zero on violations.
+    #[arg(long, value_name = "FILE")]
+    schema: Option<PathBuf>,
+    /// Extra boolean token pair, e.g. `si:no` (repeatable, case-insensitive).
+    #[arg(long = "bool-token", value_name = "TRUE:FALSE", value_parser = parse_bool_token)]
+    bool_tokens: Vec<(String, String)>,
+    /// Type columns of only 1/0 as Integer rather than Boolean.
+    #[arg(long = "binary-as-integer")]
+    binary_as_integer: bool,
+    /// Split rows into K sequential folds and report how much mean/std vary across them.
+    #[arg(long, value_name = "K")]
+    stability: Option<usize>,
+    /// Infer column types from the first N rows instead of a full scan.
+    #[arg(long = "type-sample", value_name = "N")]
+    type_sample: Option<usize>,
+    /// Draw the --type-sample rows at random rather than from the top of the file.
+    #[arg(long = "type-sample-random", requires = "type_sample")]
+    type_sample_random: bool,
+    /// Seed for every randomized step (random type sampling, noise estimates); runs are
+    /// reproducible without it, this picks a different draw.
+    #[arg(long, value_name = "N")]
+    seed: Option<u64>,
+    /// Stop analysis after this many seconds and keep the stages completed so far.
+    #[arg(long, value_name = "SECS", value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+    /// Order columns in every tab and export by a metric (descending).
+    #[arg(long = "sort-columns", value_enum, default_value_t = ColumnOrderArg::File)]
+    sort_columns: ColumnOrderArg,
+    /// Profile only the top N columns by the --sort-columns metric.
+    #[arg(long = "max-cols", value_name = "N")]
+    max_cols: Option<usize>,
+    /// Write a versioned JSON report to FILE and exit without opening the TUI.
+    #[arg(long = "report-json", value_name = "FILE")]
+    report_json: Option<PathBuf>,
+    /// Serialization for --report-json and the TUI export key.
+    #[arg(long = "output-format", value_enum, default_value_t = OutputFormatArg::Json)]
+    output_format: OutputFormatArg,
+    /// Write column pairs with |r| >= --corr-threshold to FILE as a `col_a,col_b,r` CSV edge list.
+    #[arg(long = "corr-edges", value_name = "FILE")]
+    corr_edges: Option<PathBuf>,
+    /// Write the run's provenance (Presto version, timestamp, source, shape and every
+    /// analysis option) to FILE as JSON; reports always embed the same block.
+    #[arg(long = "provenance", value_name = "FILE")]
+    provenance: Option<PathBuf>,
+    /// Leave the run timestamp out of the provenance block so repeated runs over the
+    /// same input produce identical reports.
+    #[arg(long = "no-timestamp")]
+    no_timestamp: bool,
+    /// Write a one-row-per-column data dictionary to FILE; Markdown for `.md`, CSV otherwise.
+    #[arg(long = "columns-info", value_name = "FILE")]
+    columns_info: Option<PathBuf>,
+    /// Write a tidy grouped summary to FILE as CSV: one row per (group of COL, column)
+    /// with count, mean, std_dev, min and max.
+    #[arg(long = "group-stats", num_args = 2, value_names = ["COL", "FILE"])]
+    group_stats: Vec<String>,
+    /// Correlation for the matrix and --corr-edges; `bicor` (biweight midcorrelation)
+    /// downweights outliers.
+    #[arg(long = "corr-method", value_enum, default_value_t = CorrelationMethodArg::Pearson)]
+    corr_method: CorrelationMethodArg,
+    /// Minimum absolute correlation for --corr-edges.
+    #[arg(long = "corr-threshold", value_name = "R", default_value_t = 0.5)]
+    corr_threshold: f64,
+    /// Print a one-line profile summary instead of opening the TUI.
+    #[arg(short, long)]
+    quiet: bool,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum AnomalyMethodArg {
+    Zscore,
+    ModifiedZscore,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum CorrelationMethodArg {
+    Pearson,
+    Bicor,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum OutputFormatArg {
+    Json,
+    Yaml,
+    Toml,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum ColumnOrderArg {
+    File,
+    Missing,
+    Cardinality,
+    Variance,
+}
+
+fn parse_missing_code(raw: &str) -> Result<(String, String), String> {
+    raw.split_once('=')
+        .map(|(col, code)| (col.to_string(), code.to_string()))
+        .ok_or_else(|| format!("expected COL=VALUE, got `{}`", raw))
+}
+
+fn parse_transform(raw: &str) -> Result<(String, ValueTransform), String> {
+    let (col, name) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("expected COL=log|abs|sqrt, got `{}`", raw))?;
+    let transform = match name {
+        "log" => ValueTransform::Log,
+        "abs" => ValueTransform::Abs,
+        "sqrt" => ValueTransform::Sqrt,
+        _ => {
+            return Err(format!(
+                "unknown transform `{}` (use log, abs or sqrt)",
+                name
+            ));
+        }
+    };
+    Ok((col.to_string(), transform))
+}
+
+fn parse_timeout(raw: &str) -> Result<Duration, String> {
+    match raw.parse::<f64>() {
+        Ok(secs) if secs.is_finite() && secs >= 0.0 => Ok(Duration::from_secs_f64(secs)),
+        _ => Err(format!("expected a number of seconds, got `{}`", raw)),
+    }
+}
+
+fn parse_list_col(raw: &str) -> Result<(String, String), String> {
+    match raw.split_once(':') {
+        Some((col, delimiter)) if !delimiter.is_empty() => {
+            Ok((col.to_string(), delimiter.to_string()))
+        }
+        _ => Err(format!("expected COL:DELIM, got `{}`", raw)),
+    }
+}
+
+fn parse_column_type(raw: &str) -> Result<(String, TypeInference), String> {
+    let (col, name) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("expected COL=TYPE, got `{}`", raw))?;
+    let column_type = match name.to_lowercase().as_str() {
+        "integer" => TypeInference::Integer,
+        "float" => TypeInference::Float,
+        "string" => TypeInference::String,
+        "boolean" => TypeInference::Boolean,
+        "categorical" => TypeInference::Categorical,
+        "date" => TypeInference::Date,
+        _ => return Err(format!("unknown type `{}`", name)),
+    };
+    Ok((col.to_string(), column_type))
+}
+
+fn parse_computed_column(raw: &str) -> Result<(String, String), String> {
+    raw.split_once('=')
+        .map(|(name, expr)| (name.trim().to_string(), expr.to_string()))
+        .ok_or_else(|| format!("expected NAME=EXPR, got `{}`", raw))
+}
+
+fn parse_filter(raw: &str) -> Result<(String, FilterOp, String), String> {
+    let (pos, op) = raw
+        .char_indices()
+        .find_map(|(i, _)| {
+            FilterOp::ALL
+                .iter()
+                .find(|op| raw[i..].starts_with(op.symbol()))
+                .map(|&op| (i, op))
+        })
+        .ok_or_else(|| {
+            format!(
+                "expected COL OP VALUE with OP one of == != < <= > >=, got `{}`",
+                raw
+            )
+        })?;
+    let col = raw[..pos].trim();
+    let value = raw[pos + op.symbol().len()..].trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+    if col.is_empty() {
+        return Err(format!("missing column name in `{}`", raw));
+    }
+    Ok((col.to_string(), op, value.to_string()))
+}
+
+fn parse_bool_token(raw: &str) -> Result<(String, String), String> {
+    raw.split_once(':')
+        .map(|(t, f)| (t.to_string(), f.to_string()))
+        .ok_or_else(|| format!("expected TRUE:FALSE, got `{}`", raw))
+}
+
+fn load_dataset(args: &Args, path_buf: &Path) -> Result<Dataset, presto_cli::PrestoError> {
+    let path = path_buf.to_str().ok_or_else(|| {
+        presto_cli::PrestoError::InvalidNumeric("Invalid path provided".to_string())
+    })?;
+    let names = match &args.names {
+        Some(names_path) => Some(Dataset::read_names_file(names_path.to_str().ok_or_else(
+            || presto_cli::PrestoError::InvalidNumeric("Invalid --names path".to_string()),
+        )?)?),
+        None => None,
+    };
+    if !args.fixed_width.is_empty() {
+        Dataset::from_fixed_width(path, &args.fixed_width, names)
+    } else if names.is_some() && (path_buf.is_dir() || path.contains(['*', '?'])) {
+        Err(presto_cli::PrestoError::InvalidNumeric(
+            "--names applies to a single file, not a directory or glob".to_string(),
+        ))
+    } else if path_buf.is_dir() {
+        Dataset::from_csv_dir(path)
+    } else if path.contains(['*', '?']) {
+        Dataset::from_csv_glob(path)
+    } else {
+        let delimiter = u8::try_from(args.delimiter).map_err(|_| {
+            presto_cli::PrestoError::InvalidNumeric("--delimiter must be an ASCII character".into())
+        })?;
+        match names {
+            Some(names) => Dataset::from_headerless_csv(path, delimiter, names),
+            None => Dataset::from_csv_with_delimiter(path, delimiter),
+        }
+    }
+}
+
+#[cfg(feature = "sql")]
+fn load_sql(
+    conn: &str,
+    query: &str,
+) -> Result<(Dataset, HashMap<String, TypeInference>), presto_cli::PrestoError> {
+    Dataset::from_sql(conn, query)
+}
+
+#[cfg(not(feature = "sql"))]
+fn load_sql(
+    _conn: &str,
+    _query: &str,
+) -> Result<(Dataset, HashMap<String, TypeInference>), presto_cli::PrestoError> {
+    Err(presto_cli::PrestoError::InvalidNumeric(
+        "built without the `sql` feature (cargo install presto-cli --features sql)".to_string(),
+    ))
+}
+
+fn derive_columns(args: &Args, dataset: Dataset) -> Result<Dataset, presto_cli::PrestoError> {
+    let mut dataset = if args.trim_whitespace {
+        presto_cli::trim_whitespace(&dataset).0
+    } else {
+        dataset
+    };
+    if args.thousands_separators {
+        dataset = presto_cli::strip_thousands_separators(&dataset).0;
+    }
+    if args.merge_case_variants {
+        dataset = presto_cli::merge_case_variants(&dataset).0;
+    }
+    for (name, expression) in &args.computed_columns {
+        dataset.add_computed_column(name, expression)?;
+    }
+    for (col, op, value) in &args.filters {
+        dataset = dataset.filter_with(col, *op, value, args.keep_whitespace_cells)?;
+    }
+    Ok(dataset)
 }
 
 fn main() -> Result<(), presto_cli::PrestoError> {
     let args = Args::parse();
-    let dataset = Dataset::from_csv(args.path.to_str().ok_or_else(|| {
-        presto_cli::PrestoError::InvalidNumeric("Invalid path provided".to_string())
-    })?)?;
-    let description = describe(&dataset)?;
-    render_tui(&dataset, &description)?;
+    if let [old_path, new_path] = args.compare_profiles.as_slice() {
+        let read_report = |path: &PathBuf| -> Result<Report, presto_cli::PrestoError> {
+            let json = std::fs::read_to_string(path)
+                .map_err(|e| presto_cli::PrestoError::InvalidNumeric(e.to_string()))?;
+            serde_json::from_str(&json)
+                .map_err(|e| presto_cli::PrestoError::SchemaMismatch(e.to_string()))
+        };
+        for line in read_report(old_path)?.diff(&read_report(new_path)?) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+    let path_buf = args.path.clone().unwrap_or_default();
+    let mut sql_types = HashMap::new();
+    let loaded = match args.sql.as_slice() {
+        [conn, query] => load_sql(conn, query).map(|(dataset, types)| {
+            sql_types = types;
+            dataset
+        }),
+        _ => load_dataset(&args, &path_buf),
+    };
+    if args.validate_only {
+        match loaded.and_then(|dataset| dataset.validate()) {
+            Ok((rows, cols)) => {
+                println!("OK: {} rows x {} columns", rows, cols);
+                return Ok(());
+            }
+            Err(e) => {
+                eprintln!("FAIL: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+    let dataset = loaded?;
+    if let Some(delim) = dataset.guess_delimiter() {
+        eprintln!(
+            "Warning: parsed a single column whose values all contain {:?}; \
+             the file is probably delimited by it (try --delimiter {:?})",
+            delim, delim
+        );
+    }
+    let dataset = derive_columns(&args, dataset)?;
+    if let [key_col, stats_path] = args.group_stats.as_slice() {
+        std::fs::write(
+            stats_path,
+            dataset.group_stats_csv_with(key_col, args.keep_whitespace_cells)?,
+        )
+        .map_err(|e| presto_cli::PrestoError::InvalidNumeric(e.to_string()))?;
+    }
+    if let Some(split_col) = &args.compare_cohorts {
+        for line in compare_cohorts(&dataset, split_col)?.lines() {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+    let mut violations = Vec::new();
+    if let Some(schema_path) = &args.schema {
+        let schema_json = std::fs::read_to_string(schema_path)
+            .map_err(|e| presto_cli::PrestoError::InvalidNumeric(e.to_string()))?;
+        let schema: ExpectedSchema = serde_json::from_str(&schema_json)
+            .map_err(|e| presto_cli::PrestoError::SchemaMismatch(e.to_string()))?;
+        violations = validate_against_schema(&dataset, &schema);
+        for violation in &violations {
+            eprintln!("Schema violation: {}", violation);
+        }
+        if args.report_json.is_none() {
+            if !violations.is_empty() {
+                std::process::exit(1);
+            }
+            println!("Schema OK: {} columns validated", schema.columns.len());
+            return Ok(());
+        }
+    }
+
+    let output_format = match args.output_format {
+        OutputFormatArg::Json => OutputFormat::Json,
+        OutputFormatArg::Yaml => OutputFormat::Yaml,
+        OutputFormatArg::Toml => OutputFormat::Toml,
+    };
+    let column_order = match args.sort_columns {
+        ColumnOrderArg::File => ColumnOrder::File,
+        ColumnOrderArg::Missing => ColumnOrder::Missing,
+        ColumnOrderArg::Cardinality => ColumnOrder::Cardinality,
+        ColumnOrderArg::Variance => ColumnOrder::Variance,
+    };
+    let mut options = DescribeOptions {
+        explain: args.explain,
+        weight_col: args.weight_col.clone(),
+        duplicate_key: args.dup_key.clone(),
+        analysis_exclusions: args.exclude_from_analysis.clone(),
+        approx_cardinality: args.approx_cardinality,
+        stability_folds: args.stability,
+        max_cols: args.max_cols,
+        limit_by: column_order,
+        type_sampling: match args.type_sample {
+            Some(n) if args.type_sample_random => TypeSampling::Random(n),
+            Some(n) => TypeSampling::Head(n),
+            None => TypeSampling::Full,
+        },
+        anomaly_method: match args.anomaly_method {
+            AnomalyMethodArg::Zscore => AnomalyMethod::ZScore {
+                threshold: args.anomaly_threshold.unwrap_or(3.0),
+            },
+            AnomalyMethodArg::ModifiedZscore => AnomalyMethod::ModifiedZScore {
+                threshold: args.anomaly_threshold.unwrap_or(3.5),
+            },
+        },
+        anomaly_window: args.anomaly_window,
+        seed: args.seed,
+        timeout: args.timeout,
+        keep_whitespace_cells: args.keep_whitespace_cells,
+        omit_timestamp: args.no_timestamp,
+        linear_dependencies: args.linear_deps,
+        shape_thresholds: ShapeThresholds {
+            high_skew: args.skew_threshold,
+            moderate_skew: args.moderate_skew_threshold,
+            excess_kurtosis: args.kurtosis_threshold,
+        },
+        min_rows: args.min_rows,
+        correlation_method: match args.corr_method {
+            CorrelationMethodArg::Pearson => CorrelationMethod::Pearson,
+            CorrelationMethodArg::Bicor => CorrelationMethod::Bicor,
+        },
+        ..Default::default()
+    };
+    options
+        .boolean_encoding
+        .pairs
+        .extend(args.bool_tokens.iter().cloned());
+    options.boolean_encoding.binary_as_boolean = !args.binary_as_integer;
+    options.transforms.extend(args.transforms.iter().cloned());
+    options
+        .list_columns
+        .extend(args.list_columns.iter().cloned());
+    options.type_overrides.extend(sql_types);
+    options
+        .type_overrides
+        .extend(args.column_types.iter().cloned());
+    for (col, code) in &args.missing_codes {
+        options
+            .missing_codes
+            .entry(col.clone())
+            .or_default()
+            .push(code.clone());
+    }
+    let tui_options = TuiOptions {
+        head_rows: args.head,
+        tail_rows: args.tail,
+        export_format: output_format,
+        max_cell_width: args.max_cell_width,
+    };
+    // The SQL connection string is left out: it may carry a password.
+    let source = args
+        .sql
+        .last()
+        .cloned()
+        .unwrap_or_else(|| path_buf.display().to_string());
+    if args.report_json.is_none() && !args.quiet {
+        let message = format!("Analyzing {} ({} rows)...", source, dataset.row_count());
+        let analyze = |progress: &(dyn Fn(&str) + Sync)| {
+            let mut description = describe_with_progress(&dataset, &options, progress)?;
+            description.set_source(&source);
+            if let Some(min_rows) = description.small_sample()
+                && args.enforce_min_rows
+            {
+                return Err(presto_cli::PrestoError::SchemaMismatch(format!(
+                    "only {} rows, below --min-rows {}",
+                    description.total_rows(),
+                    min_rows
+                )));
+            }
+            write_corr_edges(&args, &description)?;
+            write_columns_info(&args, &description)?;
+            write_provenance(&args, &description)?;
+            Ok(prepare_view(dataset, &description, column_order))
+        };
+        if !args.watch {
+            return render_tui_loading(&message, &tui_options, analyze, None);
+        }
+        let mut modified = modified_time(&path_buf);
+        let mut reload = || {
+            let current = modified_time(&path_buf);
+            if current == modified {
+                return Ok(None);
+            }
+            modified = current;
+            let dataset = derive_columns(&args, load_dataset(&args, &path_buf)?)?;
+            let mut description = describe_with_options(&dataset, &options)?;
+            description.set_source(&source);
+            write_provenance(&args, &description)?;
+            Ok(Some(prepare_view(dataset, &description, column_order)))
+        };
+        return render_tui_loading(&message, &tui_options, analyze, Some(&mut reload));
+    }
+
+    let mut description = describe_with_options(&dataset, &options)?;
+    description.set_source(&source);
+    if let Some(min_rows) = description.small_sample() {
+        eprintln!(
+            "Warning: only {} rows (--min-rows {}); skewness, kurtosis, correlations \
+             and other statistics are unreliable at this size",
+            description.total_rows(),
+            min_rows
+        );
+        if args.enforce_min_rows {
+            std::process::exit(1);
+        }
+    }
+    if !description.skipped_stages().is_empty() {
+        eprintln!(
+            "Warning: --timeout reached; skipped {}",
+            description.skipped_stages().join(", ")
+        );
+    }
+    if let Some(kept) = description.retained_columns() {
+        eprintln!(
+            "Profiled {} of {} columns (--max-cols)",
+            kept.len(),
+            description.source_columns()
+        );
+    }
+    write_corr_edges(&args, &description)?;
+    write_columns_info(&args, &description)?;
+    write_provenance(&args, &description)?;
+    if let Some(report_path) = &args.report_json {
+        let mut report = description.to_report();
+        report.violations = violations;
+        std::fs::write(report_path, report.render(output_format)?)
+            .map_err(|e| presto_cli::PrestoError::InvalidNumeric(e.to_string()))?;
+        if !report.violations.is_empty() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    println!("{}", description.summary_line());
     Ok(())
 }
+
+fn write_corr_edges(args: &Args, description: &Description) -> Result<(), presto_cli::PrestoError> {
+    let Some(edges_path) = &args.corr_edges else {
+        return Ok(());
+    };
+    let mut writer = csv::Writer::from_path(edges_path)
+        .map_err(|e| presto_cli::PrestoError::InvalidNumeric(e.to_string()))?;
+    writer
+        .write_record(["col_a", "col_b", "r"])
+        .map_err(|e| presto_cli::PrestoError::InvalidNumeric(e.to_string()))?;
+    for edge in description.correlation_edges(args.corr_threshold) {
+        writer
+            .write_record([edge.column_a, edge.column_b, edge.score.to_string()])
+            .map_err(|e| presto_cli::PrestoError::InvalidNumeric(e.to_string()))?;
+    }
+    writer
+        .flush()
+        .map_err(|e| presto_cli::PrestoError::InvalidNumeric(e.to_string()))
+}
+
+fn write_columns_info(
+    args: &Args,
+    description: &Description,
+) -> Result<(), presto_cli::PrestoError> {
+    let Some(info_path) = &args.columns_info else {
+        return Ok(());
+    };
+    let format = match info_path.extension().and_then(|ext| ext.to_str()) {
+        Some("md" | "markdown") => TableFormat::Markdown,
+        _ => TableFormat::Csv,
+    };
+    std::fs::write(info_path, description.to_columns_table(format))
+        .map_err(|e| presto_cli::PrestoError::InvalidNumeric(e.to_string()))
+}
+
+fn write_provenance(args: &Args, description: &Description) -> Result<(), presto_cli::PrestoError> {
+    let Some(provenance_path) = &args.provenance else {
+        return Ok(());
+    };
+    let json = serde_json::to_string_pretty(description.provenance())
+        .map_err(|e| presto_cli::PrestoError::Serialization(e.to_string()))?;
+    std::fs::write(provenance_path, json)
+        .map_err(|e| presto_cli::PrestoError::InvalidNumeric(e.to_string()))
+}
+
+fn modified_time(path: &Path) -> Option<std::time::SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn prepare_view(
+    dataset: Dataset,
+    description: &Description,
+    column_order: ColumnOrder,
+) -> (Dataset, Description) {
+    let dataset = match description.retained_columns() {
+        Some(kept) => dataset.reorder_columns(kept),
+        None => dataset,
+    };
+    let order = description.column_order(column_order);
+    (
+        dataset.reorder_columns(&order),
+        description.reorder_columns(&order),
+    )
+}
@@ -1,33 +1,58 @@
+use crate::stats::{parse_finite, quantile};
+use crate::types::{TypeInference, infer_type, parse_date};
 use crate::{Dataset, PrestoError};
 use rayon::prelude::*;
-use std::collections::HashSet;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
 
 pub fn detect_duplicates(rows: &[Vec<String>]) -> usize {
     let unique: HashSet<&Vec<String>> = rows.par_iter().collect();
     rows.len() - unique.len()
 }
 
+// Rows sharing the same values in `key_cols` count as duplicates of the first.
+pub fn detect_duplicates_by(rows: &[Vec<String>], key_cols: &[usize]) -> usize {
+    let unique: HashSet<Vec<&str>> = rows
+        .par_iter()
+        .map(|row| key_cols.iter().map(|&col| row[col].as_str()).collect())
+        .collect();
+    rows.len() - unique.len()
+}
+
+pub(crate) const OUTLIER_Z_THRESHOLD: f64 = 3.0;
+
+/// The fences `mean ± z_threshold * std_dev`; values strictly outside them are outliers.
+pub(crate) fn outlier_bounds(
+    stats: &crate::stats::ColumnStats,
+    z_threshold: f64,
+) -> Option<(f64, f64)> {
+    let (mean, std_dev) = (stats.mean?, stats.std_dev?);
+    Some((mean - z_threshold * std_dev, mean + z_threshold * std_dev))
+}
+
 pub fn detect_outliers(
     rows: &[Vec<String>],
     col_idx: usize,
     stats: &crate::stats::ColumnStats,
-) -> Vec<usize> {
+) -> Vec<(usize, f64)> {
     if stats.mean.is_none() || stats.std_dev.is_none() {
         return vec![];
     }
     let mean = stats.mean.unwrap();
     let std_dev = stats.std_dev.unwrap();
-    let z_threshold = 3.0;
+    let z_threshold = OUTLIER_Z_THRESHOLD;
 
-    rows.par_iter()
+    let mut outliers: Vec<(usize, f64)> = rows
+        .par_iter()
         .enumerate()
         .filter_map(|(idx, row)| {
             if row[col_idx].is_empty() || row[col_idx] == "NA" {
                 None
-            } else if let Ok(val) = row[col_idx].parse::<f64>() {
+            } else if let Some(val) = parse_finite(&row[col_idx]) {
                 let z_score = (val - mean).abs() / std_dev;
                 if z_score > z_threshold {
-                    Some(idx)
+                    Some((idx, z_score))
                 } else {
                     None
                 }
@@ -35,7 +60,9 @@ pub fn detect_outliers(
                 None
             }
         })
-        .collect()
+        .collect();
+    outliers.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    outliers
 }
 
 pub fn check_consistency(dataset: &Dataset) -> Result<Vec<usize>, PrestoError> {
@@ -72,12 +99,15 @@ pub fn check_consistency(dataset: &Dataset) -> Result<Vec<usize>, PrestoError> {
         .collect::<Result<Vec<_>, _>>()
 }
 
-pub fn detect_redundancy(dataset: &Dataset) -> Result<Vec<(usize, usize, f64)>, PrestoError> {
+pub fn detect_redundancy(
+    dataset: &Dataset,
+    excluded: &[usize],
+) -> Result<Vec<(usize, usize, f64)>, PrestoError> {
     let num_cols = dataset.headers.len();
     let mut pairs = Vec::new();
-    for i in 0..num_cols {
+    for i in (0..num_cols).filter(|i| !excluded.contains(i)) {
         let col_i: Vec<&str> = dataset.rows.iter().map(|row| row[i].as_str()).collect();
-        for j in (i + 1)..num_cols {
+        for j in ((i + 1)..num_cols).filter(|j| !excluded.contains(j)) {
             let col_j: Vec<&str> = dataset.rows.iter().map(|row| row[j].as_str()).collect();
             let matches = col_i
                 .iter()
@@ -100,3 +130,312 @@ pub fn detect_redundancy(dataset: &Dataset) -> Result<Vec<(usize, usize, f64)>,
     }
     Ok(pairs)
 }
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |h, &b| (h ^ b as u64).wrapping_mul(FNV_PRIME))
+}
+
+/// FNV-1a of each column's values in row order. Unlike the std hasher it is fixed
+/// across Rust releases, so hashes can be stored and compared between runs.
+pub fn compute_column_hashes(dataset: &Dataset) -> Vec<u64> {
+    (0..dataset.headers.len())
+        .into_par_iter()
+        .map(|col_idx| {
+            dataset.rows.iter().fold(FNV_OFFSET, |h, row| {
+                // 0xff never occurs in UTF-8, so it marks value boundaries unambiguously.
+                fnv1a(fnv1a(h, row[col_idx].as_bytes()), &[0xff])
+            })
+        })
+        .collect()
+}
+
+/// A fingerprint of the whole dataset: the header names and every column hash, in order.
+pub fn compute_dataset_hash(dataset: &Dataset, column_hashes: &[u64]) -> u64 {
+    dataset
+        .headers
+        .iter()
+        .zip(column_hashes)
+        .fold(FNV_OFFSET, |h, (header, column_hash)| {
+            let h = fnv1a(fnv1a(h, header.as_bytes()), &[0xff]);
+            fnv1a(h, &column_hash.to_le_bytes())
+        })
+}
+
+pub fn detect_duplicate_columns(
+    dataset: &Dataset,
+    hashes: &[u64],
+) -> Result<Vec<(usize, usize)>, PrestoError> {
+    let num_cols = dataset.headers.len();
+    let mut pairs = Vec::new();
+    for i in 0..num_cols {
+        for j in (i + 1)..num_cols {
+            if hashes[i] == hashes[j] && dataset.rows.iter().all(|row| row[i] == row[j]) {
+                pairs.push((i, j));
+            }
+        }
+    }
+    Ok(pairs)
+}
+
+pub fn count_untrimmed(dataset: &Dataset) -> Result<Vec<usize>, PrestoError> {
+    let num_cols = dataset.headers.len();
+    Ok((0..num_cols)
+        .into_par_iter()
+        .map(|col_idx| {
+            dataset
+                .rows
+                .iter()
+                .filter(|row| row[col_idx].trim() != row[col_idx])
+                .count()
+        })
+        .collect())
+}
+
+/// Columns missing in every row; they carry no co-missingness signal.
+pub fn detect_empty_columns(missing: &[usize], total_rows: usize) -> Vec<usize> {
+    missing
+        .iter()
+        .enumerate()
+        .filter(|&(_, &m)| m == total_rows)
+        .map(|(col_idx, _)| col_idx)
+        .collect()
+}
+
+pub fn compute_co_missingness(dataset: &Dataset) -> Result<Vec<Vec<f64>>, PrestoError> {
+    let num_cols = dataset.headers.len();
+    let missing_masks: Vec<Vec<bool>> = (0..num_cols)
+        .into_par_iter()
+        .map(|col_idx| {
+            dataset
+                .rows
+                .iter()
+                .map(|row| row[col_idx].is_empty() || row[col_idx] == "NA")
+                .collect()
+        })
+        .collect();
+    Ok((0..num_cols)
+        .into_par_iter()
+        .map(|i| {
+            (0..num_cols)
+                .map(|j| {
+                    let (both, either) = missing_masks[i].iter().zip(missing_masks[j].iter()).fold(
+                        (0usize, 0usize),
+                        |(both, either), (&a, &b)| {
+                            (both + (a && b) as usize, either + (a || b) as usize)
+                        },
+                    );
+                    if either > 0 {
+                        both as f64 / either as f64
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        })
+        .collect())
+}
+
+pub fn winsorize(
+    dataset: &Dataset,
+    lower: f64,
+    upper: f64,
+) -> Result<(Dataset, Vec<usize>), PrestoError> {
+    if !(0.0..=1.0).contains(&lower) || !(0.0..=1.0).contains(&upper) || lower >= upper {
+        return Err(PrestoError::InvalidNumeric(format!(
+            "Winsorize percentiles must satisfy 0 <= lower < upper <= 1, got {} and {}",
+            lower, upper
+        )));
+    }
+    let num_cols = dataset.headers.len();
+    let bounds: Vec<Option<(f64, f64)>> = (0..num_cols)
+        .into_par_iter()
+        .map(|col_idx| {
+            let mut values: Vec<f64> = dataset
+                .rows
+                .iter()
+                .filter_map(|row| parse_finite(&row[col_idx]))
+                .collect();
+            if values.is_empty() {
+                return None;
+            }
+            values.par_sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+            Some((quantile(&values, lower), quantile(&values, upper)))
+        })
+        .collect();
+
+    let mut clipped = vec![0; num_cols];
+    let mut winsorized = dataset.clone();
+    for row in winsorized.rows.iter_mut() {
+        for (col_idx, bound) in bounds.iter().enumerate() {
+            let (Some((low, high)), Some(val)) = (bound, parse_finite(&row[col_idx])) else {
+                continue;
+            };
+            if val < *low || val > *high {
+                row[col_idx] = val.clamp(*low, *high).to_string();
+                clipped[col_idx] += 1;
+            }
+        }
+    }
+    Ok((winsorized, clipped))
+}
+
+pub fn trim_whitespace(dataset: &Dataset) -> (Dataset, Vec<usize>) {
+    let mut trimmed_counts = vec![0; dataset.headers.len()];
+    let mut trimmed = dataset.clone();
+    for row in trimmed.rows.iter_mut() {
+        for (col_idx, val) in row.iter_mut().enumerate() {
+            if val.trim() != val {
+                *val = val.trim().to_string();
+                trimmed_counts[col_idx] += 1;
+            }
+        }
+    }
+    (trimmed, trimmed_counts)
+}
+
+/// Blanks cells that hold only whitespace (`"   "`) so they count as missing rather
+/// than as a distinct present value; returns how many cells changed per column.
+pub fn blank_whitespace_cells(dataset: &Dataset) -> (Dataset, Vec<usize>) {
+    let mut blanked_counts = vec![0; dataset.headers.len()];
+    let mut blanked = dataset.clone();
+    for row in blanked.rows.iter_mut() {
+        for (col_idx, val) in row.iter_mut().enumerate() {
+            if !val.is_empty() && val.trim().is_empty() {
+                val.clear();
+                blanked_counts[col_idx] += 1;
+            }
+        }
+    }
+    (blanked, blanked_counts)
+}
+
+/// Rewrites numbers written with comma thousands separators (`1,234`, `-12,345.67`)
+/// to plain `1234` / `-12345.67` so they type and parse as numeric; returns how many
+/// values changed per column. Commas in any other position are left alone.
+pub fn strip_thousands_separators(dataset: &Dataset) -> (Dataset, Vec<usize>) {
+    static GROUPED: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"^[+-]?\d{1,3}(,\d{3})+(\.\d+)?$").unwrap());
+    let mut stripped_counts = vec![0; dataset.headers.len()];
+    let mut stripped = dataset.clone();
+    for row in stripped.rows.iter_mut() {
+        for (col_idx, val) in row.iter_mut().enumerate() {
+            if GROUPED.is_match(val) {
+                *val = val.replace(',', "");
+                stripped_counts[col_idx] += 1;
+            }
+        }
+    }
+    (stripped, stripped_counts)
+}
+
+/// Spellings of one category that differ only in letter case or whitespace: the
+/// suggested canonical form and every spelling with its count, most frequent first.
+pub(crate) type CaseVariantGroup = (String, Vec<(String, usize)>);
+
+fn normalize_category(val: &str) -> String {
+    val.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+// The most frequent spelling, with its whitespace tidied, is the canonical form;
+// groups come back largest first. Missing values never join a group.
+fn case_variant_groups(rows: &[Vec<String>], col_idx: usize) -> Vec<CaseVariantGroup> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for row in rows {
+        if !row[col_idx].trim().is_empty() && row[col_idx] != "NA" {
+            *counts.entry(row[col_idx].as_str()).or_insert(0) += 1;
+        }
+    }
+    let mut by_key: HashMap<String, Vec<(String, usize)>> = HashMap::new();
+    for (val, count) in counts {
+        by_key
+            .entry(normalize_category(val))
+            .or_default()
+            .push((val.to_string(), count));
+    }
+    let mut groups: Vec<(usize, CaseVariantGroup)> = by_key
+        .into_values()
+        .filter(|variants| variants.len() > 1)
+        .map(|mut variants| {
+            variants.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            let canonical = variants[0]
+                .0
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            let total = variants.iter().map(|(_, count)| count).sum();
+            (total, (canonical, variants))
+        })
+        .collect();
+    groups.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.0.cmp(&b.1.0)));
+    groups.into_iter().map(|(_, group)| group).collect()
+}
+
+/// Per text column, the values that collapse to the same category once case and
+/// whitespace are ignored (`Male` / `male` / `MALE `). Numeric and date columns are skipped.
+pub fn detect_case_variants(
+    dataset: &Dataset,
+    types: &[TypeInference],
+) -> Result<Vec<Vec<CaseVariantGroup>>, PrestoError> {
+    Ok((0..dataset.headers.len())
+        .into_par_iter()
+        .map(|col_idx| match types[col_idx] {
+            TypeInference::Integer | TypeInference::Float | TypeInference::Date => Vec::new(),
+            _ => case_variant_groups(&dataset.rows, col_idx),
+        })
+        .collect())
+}
+
+// Numeric and date columns, as `detect_case_variants` sees them without overrides.
+fn is_numeric_or_date(rows: &[Vec<String>], col_idx: usize) -> bool {
+    match infer_type(rows, col_idx) {
+        TypeInference::Integer | TypeInference::Float => true,
+        TypeInference::String => rows
+            .iter()
+            .map(|row| row[col_idx].as_str())
+            .filter(|v| !v.is_empty() && *v != "NA")
+            .all(|v| parse_date(v).is_some()),
+        _ => false,
+    }
+}
+
+/// Rewrites every case/whitespace variant of a category to its canonical form, as
+/// reported by [`detect_case_variants`]; returns how many values changed per column.
+/// Numeric and date columns are left untouched.
+pub fn merge_case_variants(dataset: &Dataset) -> (Dataset, Vec<usize>) {
+    let canonical: Vec<HashMap<String, String>> = (0..dataset.headers.len())
+        .into_par_iter()
+        .map(|col_idx| {
+            if is_numeric_or_date(&dataset.rows, col_idx) {
+                return HashMap::new();
+            }
+            case_variant_groups(&dataset.rows, col_idx)
+                .into_iter()
+                .flat_map(|(canonical, variants)| {
+                    variants
+                        .into_iter()
+                        .map(move |(val, _)| (val, canonical.clone()))
+                        .filter(|(val, canonical)| val != canonical)
+                })
+                .collect()
+        })
+        .collect();
+    let mut merged_counts = vec![0; dataset.headers.len()];
+    let mut merged = dataset.clone();
+    for row in merged.rows.iter_mut() {
+        for (col_idx, val) in row.iter_mut().enumerate() {
+            if let Some(target) = canonical[col_idx].get(val.as_str()) {
+                *val = target.clone();
+                merged_counts[col_idx] += 1;
+            }
+        }
+    }
+    (merged, merged_counts)
+}
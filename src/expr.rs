@@ -0,0 +1,212 @@
+use crate::stats::parse_finite;
+use crate::{PrestoError, ValueTransform};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Column(usize),
+    Neg(Box<Expr>),
+    Binary(char, Box<Expr>, Box<Expr>),
+    Call(ValueTransform, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, PrestoError> {
+    let mut tokens = Vec::new();
+    let mut chars = src.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut num = String::new();
+            while let Some(&d) = chars.peek() {
+                if d.is_ascii_digit() || d == '.' {
+                    num.push(d);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let value = num.parse().map_err(|_| {
+                PrestoError::InvalidExpression(format!("bad number `{}` in `{}`", num, src))
+            })?;
+            tokens.push(Token::Number(value));
+        } else if c == '`' {
+            chars.next();
+            let name: String = chars.by_ref().take_while(|&d| d != '`').collect();
+            tokens.push(Token::Ident(name));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut name = String::new();
+            while let Some(&d) = chars.peek() {
+                if d.is_alphanumeric() || d == '_' {
+                    name.push(d);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Ident(name));
+        } else if "+-*/^()".contains(c) {
+            tokens.push(Token::Op(c));
+            chars.next();
+        } else {
+            return Err(PrestoError::InvalidExpression(format!(
+                "unexpected `{}` in `{}`",
+                c, src
+            )));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    headers: &'a [String],
+}
+
+impl Parser<'_> {
+    fn peek_op(&self) -> Option<char> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) => Some(*op),
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, op: char) -> Result<(), PrestoError> {
+        if self.peek_op() == Some(op) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(PrestoError::InvalidExpression(format!("expected `{}`", op)))
+        }
+    }
+
+    // expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Result<Expr, PrestoError> {
+        let mut lhs = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek_op() {
+            self.pos += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.term()?));
+        }
+        Ok(lhs)
+    }
+
+    // term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<Expr, PrestoError> {
+        let mut lhs = self.unary()?;
+        while let Some(op @ ('*' | '/')) = self.peek_op() {
+            self.pos += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    // unary := '-' unary | power
+    fn unary(&mut self) -> Result<Expr, PrestoError> {
+        if self.peek_op() == Some('-') {
+            self.pos += 1;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.power()
+    }
+
+    // power := atom ('^' unary)?
+    fn power(&mut self) -> Result<Expr, PrestoError> {
+        let base = self.atom()?;
+        if self.peek_op() == Some('^') {
+            self.pos += 1;
+            return Ok(Expr::Binary('^', Box::new(base), Box::new(self.unary()?)));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Expr, PrestoError> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        match token {
+            Some(Token::Number(value)) => Ok(Expr::Number(value)),
+            Some(Token::Op('(')) => {
+                let inner = self.expr()?;
+                self.expect(')')?;
+                Ok(inner)
+            }
+            Some(Token::Ident(name)) if self.peek_op() == Some('(') => {
+                let transform = match name.as_str() {
+                    "log" => ValueTransform::Log,
+                    "abs" => ValueTransform::Abs,
+                    "sqrt" => ValueTransform::Sqrt,
+                    _ => {
+                        return Err(PrestoError::InvalidExpression(format!(
+                            "unknown function `{}` (use log, abs or sqrt)",
+                            name
+                        )));
+                    }
+                };
+                self.pos += 1;
+                let arg = self.expr()?;
+                self.expect(')')?;
+                Ok(Expr::Call(transform, Box::new(arg)))
+            }
+            Some(Token::Ident(name)) => self
+                .headers
+                .iter()
+                .position(|h| *h == name)
+                .map(Expr::Column)
+                .ok_or(PrestoError::ColumnNotFound(name)),
+            Some(Token::Op(op)) => Err(PrestoError::InvalidExpression(format!(
+                "unexpected `{}`",
+                op
+            ))),
+            None => Err(PrestoError::InvalidExpression(
+                "unexpected end of expression".to_string(),
+            )),
+        }
+    }
+}
+
+impl Expr {
+    pub fn parse(src: &str, headers: &[String]) -> Result<Expr, PrestoError> {
+        let mut parser = Parser {
+            tokens: tokenize(src)?,
+            pos: 0,
+            headers,
+        };
+        let expr = parser.expr()?;
+        if parser.pos < parser.tokens.len() {
+            return Err(PrestoError::InvalidExpression(format!(
+                "trailing input in `{}`",
+                src
+            )));
+        }
+        Ok(expr)
+    }
+
+    /// Evaluates against one row; `None` when an input is missing or
+    /// non-numeric, or the result is undefined (e.g. division by zero).
+    pub fn eval(&self, row: &[String]) -> Option<f64> {
+        let value = match self {
+            Expr::Number(value) => *value,
+            Expr::Column(idx) => parse_finite(&row[*idx])?,
+            Expr::Neg(inner) => -inner.eval(row)?,
+            Expr::Call(transform, arg) => transform.apply(arg.eval(row)?)?,
+            Expr::Binary(op, lhs, rhs) => {
+                let (a, b) = (lhs.eval(row)?, rhs.eval(row)?);
+                match op {
+                    '+' => a + b,
+                    '-' => a - b,
+                    '*' => a * b,
+                    '/' => a / b,
+                    _ => a.powf(b),
+                }
+            }
+        };
+        value.is_finite().then_some(value)
+    }
+}
@@ -1,17 +1,53 @@
 mod cleaning;
+mod expr;
+mod report;
+mod schema;
+#[cfg(feature = "snapshot")]
+mod snapshot;
+#[cfg(feature = "sql")]
+mod sql;
 mod stats;
 mod tui;
 mod types;
 
-use cleaning::{check_consistency, detect_duplicates, detect_outliers, detect_redundancy};
+use cleaning::{
+    CaseVariantGroup, check_consistency, compute_co_missingness, compute_column_hashes,
+    compute_dataset_hash, count_untrimmed, detect_case_variants, detect_duplicate_columns,
+    detect_duplicates, detect_duplicates_by, detect_outliers, detect_redundancy,
+};
+pub use cleaning::{
+    blank_whitespace_cells, merge_case_variants, strip_thousands_separators, trim_whitespace,
+    winsorize,
+};
+pub use expr::Expr;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 use rayon::prelude::*;
+pub use report::{
+    Anomaly, Bounds, CaseVariants, ColumnPair, ColumnReport, FeatureImportance, FoldStability,
+    HistogramBin, LinearCombination, LinearTerm, ListColumn, Normality, Outlier, OutputFormat,
+    Provenance, REPORT_SCHEMA_VERSION, Report, Sentinel, TableFormat, TopValue, TypeMismatch,
+};
+pub use schema::{ExpectedColumn, ExpectedSchema, SchemaViolation, validate_against_schema};
+pub use stats::{
+    CohortColumn, CohortComparison, ColumnStats, Granularity, ListStats, compare_cohorts,
+};
 use stats::{
-    ColumnStats, compute_cardinality, compute_dependency_scores, compute_distribution,
-    detect_drift, detect_temporal_patterns, estimate_noise, suggest_transformations,
+    LinearDependency, compute_cardinality, compute_correlation_ratios, compute_dependency_scores,
+    compute_distribution, compute_drift_series, compute_normality, compute_stability,
+    count_non_finite, detect_categorical_drift, detect_drift, detect_granularity,
+    detect_linear_combinations, detect_sentinels, detect_sequence_columns,
+    detect_temporal_patterns, estimate_cardinality, estimate_noise, parse_finite,
+    suggest_transformations,
 };
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::time::{Duration, Instant};
 use thiserror::Error;
-pub use tui::render_tui;
-use types::TypeInference;
+pub use tui::{
+    TuiOptions, render_tui, render_tui_loading, render_tui_watch, render_tui_with_options,
+};
+pub use types::{BooleanEncoding, IntegerWidth, SemanticType, TypeInference, TypeSampling};
 
 #[derive(Debug, Error)]
 pub enum PrestoError {
@@ -19,6 +55,365 @@ pub enum PrestoError {
     EmptyDataset,
     #[error("Invalid numeric data: {0}")]
     InvalidNumeric(String),
+    #[error("Schema mismatch: {0}")]
+    SchemaMismatch(String),
+    #[error("Column not found: {0}")]
+    ColumnNotFound(String),
+    #[error("Invalid expression: {0}")]
+    InvalidExpression(String),
+    #[error("Serialization failed: {0}")]
+    Serialization(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnomalyMethod {
+    ZScore { threshold: f64 },
+    ModifiedZScore { threshold: f64 },
+}
+
+impl Default for AnomalyMethod {
+    fn default() -> Self {
+        AnomalyMethod::ZScore { threshold: 3.0 }
+    }
+}
+
+impl AnomalyMethod {
+    pub fn threshold(&self) -> f64 {
+        match *self {
+            AnomalyMethod::ZScore { threshold } | AnomalyMethod::ModifiedZScore { threshold } => {
+                threshold
+            }
+        }
+    }
+
+    pub fn score(&self, val: f64, stats: &ColumnStats) -> Option<f64> {
+        match self {
+            AnomalyMethod::ZScore { .. } => {
+                let (mean, std_dev) = (stats.mean?, stats.std_dev?);
+                Some((val - mean).abs() / std_dev)
+            }
+            AnomalyMethod::ModifiedZScore { .. } => {
+                let (median, mad) = (stats.median?, stats.mad?);
+                if mad > 0.0 {
+                    Some(0.6745 * (val - median).abs() / mad)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// The values at which [`AnomalyMethod::score`] crosses the threshold: anything
+    /// strictly outside `(lower, upper)` is flagged (when scored against the whole column).
+    pub fn bounds(&self, stats: &ColumnStats) -> Option<(f64, f64)> {
+        let (center, spread) = match self {
+            AnomalyMethod::ZScore { .. } => (stats.mean?, stats.std_dev?),
+            AnomalyMethod::ModifiedZScore { .. } => {
+                let mad = stats.mad.filter(|&mad| mad > 0.0)?;
+                (stats.median?, mad / 0.6745)
+            }
+        };
+        let reach = self.threshold() * spread;
+        Some((center - reach, center + reach))
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            AnomalyMethod::ZScore { .. } => "|z|",
+            AnomalyMethod::ModifiedZScore { .. } => "|modified z|",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ShapeThresholds {
+    pub high_skew: f64,
+    pub moderate_skew: f64,
+    pub excess_kurtosis: f64,
+}
+
+impl Default for ShapeThresholds {
+    fn default() -> Self {
+        ShapeThresholds {
+            high_skew: 1.0,
+            moderate_skew: 0.5,
+            excess_kurtosis: 0.0,
+        }
+    }
+}
+
+impl ShapeThresholds {
+    pub fn is_skewed(&self, skew: f64) -> bool {
+        skew.abs() > self.high_skew
+    }
+
+    pub fn skew_label(&self, skew: f64) -> &'static str {
+        match skew {
+            s if s > self.high_skew => "Highly +ve skewed",
+            s if s > self.moderate_skew => "Mod. +ve skewed",
+            s if s < -self.high_skew => "Highly -ve skewed",
+            s if s < -self.moderate_skew => "Mod. -ve skewed",
+            _ => "Symmetric",
+        }
+    }
+
+    pub fn kurtosis_label(&self, kurtosis: f64) -> &'static str {
+        match kurtosis {
+            k if k > self.excess_kurtosis => "Leptokurtic",
+            k if k < self.excess_kurtosis => "Platykurtic",
+            _ => "Mesokurtic",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValueTransform {
+    Log,
+    Abs,
+    Sqrt,
+}
+
+impl ValueTransform {
+    pub fn apply(&self, val: f64) -> Option<f64> {
+        match self {
+            ValueTransform::Log if val > 0.0 => Some(val.ln()),
+            ValueTransform::Abs => Some(val.abs()),
+            ValueTransform::Sqrt if val >= 0.0 => Some(val.sqrt()),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ValueTransform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ValueTransform::Log => "log",
+            ValueTransform::Abs => "abs",
+            ValueTransform::Sqrt => "sqrt",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+pub(crate) fn column_label(header: &str, transform: Option<ValueTransform>) -> String {
+    match transform {
+        Some(transform) => format!("{}({})", transform, header),
+        None => header.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CorrelationMethod {
+    #[default]
+    Pearson,
+    Bicor,
+}
+
+/// A comparison for [`Dataset::filter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl FilterOp {
+    /// Two-character operators come first, so scanning for a symbol never reads `<=` as `<`.
+    pub const ALL: [FilterOp; 6] = [
+        FilterOp::Eq,
+        FilterOp::Ne,
+        FilterOp::Le,
+        FilterOp::Ge,
+        FilterOp::Lt,
+        FilterOp::Gt,
+    ];
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            FilterOp::Eq => "==",
+            FilterOp::Ne => "!=",
+            FilterOp::Lt => "<",
+            FilterOp::Le => "<=",
+            FilterOp::Gt => ">",
+            FilterOp::Ge => ">=",
+        }
+    }
+
+    fn holds(&self, ordering: std::cmp::Ordering) -> bool {
+        use std::cmp::Ordering::*;
+        match self {
+            FilterOp::Eq => ordering == Equal,
+            FilterOp::Ne => ordering != Equal,
+            FilterOp::Lt => ordering == Less,
+            FilterOp::Le => ordering != Greater,
+            FilterOp::Gt => ordering == Greater,
+            FilterOp::Ge => ordering != Less,
+        }
+    }
+}
+
+impl std::fmt::Display for FilterOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl std::fmt::Display for CorrelationMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CorrelationMethod::Pearson => write!(f, "pearson"),
+            CorrelationMethod::Bicor => write!(f, "bicor"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnOrder {
+    #[default]
+    File,
+    Missing,
+    Cardinality,
+    Variance,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DescribeOptions {
+    pub explain: bool,
+    pub weight_col: Option<String>,
+    pub missing_codes: HashMap<String, Vec<String>>,
+    pub approx_cardinality: bool,
+    pub anomaly_method: AnomalyMethod,
+    pub anomaly_window: Option<usize>,
+    pub linear_dependencies: bool,
+    pub boolean_encoding: BooleanEncoding,
+    pub stability_folds: Option<usize>,
+    pub type_sampling: TypeSampling,
+    pub max_cols: Option<usize>,
+    pub limit_by: ColumnOrder,
+    pub transforms: HashMap<String, ValueTransform>,
+    pub type_overrides: HashMap<String, TypeInference>,
+    pub shape_thresholds: ShapeThresholds,
+    pub min_rows: Option<usize>,
+    pub correlation_method: CorrelationMethod,
+    pub duplicate_key: Vec<String>,
+    pub analysis_exclusions: Vec<String>,
+    pub seed: Option<u64>,
+    /// Keep whitespace-only cells as values instead of treating them as missing.
+    pub keep_whitespace_cells: bool,
+    /// Columns holding delimited lists, by name, with their element delimiter.
+    pub list_columns: HashMap<String, String>,
+    /// Wall-clock budget for [`describe_with_options`]; stages left when it runs out
+    /// are skipped and listed in [`Description::skipped_stages`].
+    pub timeout: Option<Duration>,
+    /// Leave `generated_at` out of the provenance block, for byte-identical reruns.
+    pub omit_timestamp: bool,
+}
+
+const DEFAULT_SEED: u64 = 0x5eed;
+
+/// The generator behind every randomized step. Without a seed it falls back to a
+/// fixed one, so runs over the same input stay identical; `stream` separates
+/// independent draws (e.g. one per column) so parallel work stays deterministic
+/// regardless of scheduling.
+pub(crate) fn seeded_rng(seed: Option<u64>, stream: u64) -> StdRng {
+    let seed = seed.unwrap_or(DEFAULT_SEED);
+    StdRng::seed_from_u64(seed ^ stream.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+}
+
+impl DescribeOptions {
+    /// Every option as a display string keyed by field name, for the provenance block.
+    pub fn settings(&self) -> BTreeMap<String, String> {
+        fn sorted<V: std::fmt::Debug>(map: &HashMap<String, V>) -> String {
+            format!("{:?}", map.iter().collect::<BTreeMap<_, _>>())
+        }
+        [
+            ("explain", self.explain.to_string()),
+            ("weight_col", format!("{:?}", self.weight_col)),
+            ("missing_codes", sorted(&self.missing_codes)),
+            ("approx_cardinality", self.approx_cardinality.to_string()),
+            ("anomaly_method", format!("{:?}", self.anomaly_method)),
+            ("anomaly_window", format!("{:?}", self.anomaly_window)),
+            ("linear_dependencies", self.linear_dependencies.to_string()),
+            ("boolean_encoding", format!("{:?}", self.boolean_encoding)),
+            ("stability_folds", format!("{:?}", self.stability_folds)),
+            ("type_sampling", format!("{:?}", self.type_sampling)),
+            ("max_cols", format!("{:?}", self.max_cols)),
+            ("limit_by", format!("{:?}", self.limit_by)),
+            ("transforms", sorted(&self.transforms)),
+            ("type_overrides", sorted(&self.type_overrides)),
+            ("shape_thresholds", format!("{:?}", self.shape_thresholds)),
+            ("min_rows", format!("{:?}", self.min_rows)),
+            ("correlation_method", self.correlation_method.to_string()),
+            ("duplicate_key", format!("{:?}", self.duplicate_key)),
+            (
+                "analysis_exclusions",
+                format!("{:?}", self.analysis_exclusions),
+            ),
+            ("seed", format!("{:?}", self.seed)),
+            (
+                "keep_whitespace_cells",
+                self.keep_whitespace_cells.to_string(),
+            ),
+            ("list_columns", sorted(&self.list_columns)),
+            ("timeout", format!("{:?}", self.timeout)),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect()
+    }
+}
+
+/// One typed column for [`Dataset::from_columns`]; `None` is a missing value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnData {
+    Float(Vec<Option<f64>>),
+    Integer(Vec<Option<i64>>),
+    Boolean(Vec<Option<bool>>),
+    Text(Vec<Option<String>>),
+}
+
+impl ColumnData {
+    pub fn len(&self) -> usize {
+        match self {
+            ColumnData::Float(values) => values.len(),
+            ColumnData::Integer(values) => values.len(),
+            ColumnData::Boolean(values) => values.len(),
+            ColumnData::Text(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The type the column was built as. Inference can still read an Integer column
+    /// of 0/1 as Boolean, or numeric-looking Text as a number; pin it through
+    /// [`DescribeOptions::type_overrides`] to keep it.
+    pub fn column_type(&self) -> TypeInference {
+        match self {
+            ColumnData::Float(_) => TypeInference::Float,
+            ColumnData::Integer(_) => TypeInference::Integer,
+            ColumnData::Boolean(_) => TypeInference::Boolean,
+            ColumnData::Text(_) => TypeInference::String,
+        }
+    }
+
+    // Floats use the shortest text that parses back to the same value, keeping a
+    // trailing `.0` so whole numbers still read as Float.
+    fn cell(&self, row: usize) -> String {
+        match self {
+            ColumnData::Float(values) => values[row].map(|v| format!("{:?}", v)),
+            ColumnData::Integer(values) => values[row].map(|v| v.to_string()),
+            ColumnData::Boolean(values) => values[row].map(|v| v.to_string()),
+            ColumnData::Text(values) => values[row].clone(),
+        }
+        .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -32,15 +427,124 @@ impl Dataset {
         Dataset { headers, rows }
     }
 
-    pub fn from_csv(path: &str) -> Result<Self, PrestoError> {
-        let mut rdr =
-            csv::Reader::from_path(path).map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
-        let headers = rdr
-            .headers()
-            .map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?
+    /// Builds a dataset from typed in-memory columns of equal length, so numbers
+    /// need not be formatted by hand (and floats lose no precision).
+    pub fn from_columns(
+        headers: Vec<String>,
+        columns: Vec<ColumnData>,
+    ) -> Result<Self, PrestoError> {
+        if headers.len() != columns.len() {
+            return Err(PrestoError::SchemaMismatch(format!(
+                "{} headers for {} columns",
+                headers.len(),
+                columns.len()
+            )));
+        }
+        let row_count = columns.first().map_or(0, ColumnData::len);
+        if let Some((i, column)) = columns
             .iter()
-            .map(String::from)
+            .enumerate()
+            .find(|(_, column)| column.len() != row_count)
+        {
+            return Err(PrestoError::SchemaMismatch(format!(
+                "column `{}` has {} values, expected {}",
+                headers[i],
+                column.len(),
+                row_count
+            )));
+        }
+        let rows = (0..row_count)
+            .map(|row| columns.iter().map(|column| column.cell(row)).collect())
+            .collect();
+        Ok(Dataset {
+            headers: disambiguate_headers(headers),
+            rows,
+        })
+    }
+
+    pub fn to_csv_string(&self) -> String {
+        report::csv_text(&self.headers, &self.rows)
+    }
+
+    pub fn from_csv(path: &str) -> Result<Self, PrestoError> {
+        Dataset::from_csv_with_delimiter(path, b',')
+    }
+
+    pub fn from_csv_with_delimiter(path: &str, delimiter: u8) -> Result<Self, PrestoError> {
+        Dataset::read_csv(path, delimiter, None)
+    }
+
+    /// Reads a CSV that has no header row, naming its columns `headers` (for
+    /// example from [`Dataset::read_names_file`]).
+    pub fn from_headerless_csv(
+        path: &str,
+        delimiter: u8,
+        headers: Vec<String>,
+    ) -> Result<Self, PrestoError> {
+        Dataset::read_csv(path, delimiter, Some(headers))
+    }
+
+    /// Column names from a names file shipped beside a headerless data file: one
+    /// name per line or comma-separated, with `|` and `#` starting comments. For
+    /// C4.5-style `.names` files (`age: continuous.`, as in the UCI repository)
+    /// the declared attributes are taken, plus a trailing `class` column when the
+    /// file opens with the class labels.
+    pub fn read_names_file(path: &str) -> Result<Vec<String>, PrestoError> {
+        let text = read_text(path)?;
+        let lines: Vec<&str> = text
+            .lines()
+            .map(|line| line.split(['|', '#']).next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty())
             .collect();
+        let names: Vec<String> = match lines.iter().position(|line| line.contains(':')) {
+            Some(first_attribute) => {
+                let mut names: Vec<String> = lines
+                    .iter()
+                    .filter_map(|line| line.split_once(':'))
+                    .map(|(name, _)| name.trim().to_string())
+                    .collect();
+                if first_attribute > 0 {
+                    names.push("class".to_string());
+                }
+                names
+            }
+            None => lines
+                .iter()
+                .flat_map(|line| line.split(','))
+                .map(|name| name.trim().trim_end_matches('.').trim().to_string())
+                .filter(|name| !name.is_empty())
+                .collect(),
+        };
+        if names.is_empty() {
+            return Err(PrestoError::SchemaMismatch(format!(
+                "no column names found in {}",
+                path
+            )));
+        }
+        Ok(names)
+    }
+
+    fn read_csv(
+        path: &str,
+        delimiter: u8,
+        headers: Option<Vec<String>>,
+    ) -> Result<Self, PrestoError> {
+        let text = read_text(path)?;
+        let mut rdr = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .has_headers(headers.is_none())
+            .from_reader(text.as_bytes());
+        let named = headers.is_some();
+        let headers = match headers {
+            Some(headers) => headers,
+            None => rdr
+                .headers()
+                .map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?
+                .iter()
+                .map(String::from)
+                .collect(),
+        };
+        let headers = disambiguate_headers(headers);
         let rows: Vec<Vec<String>> = rdr
             .records()
             .map(|r| {
@@ -48,16 +552,481 @@ impl Dataset {
                 Ok(record.iter().map(String::from).collect::<Vec<String>>())
             })
             .collect::<Result<Vec<_>, _>>()?;
+        if named
+            && let Some(row) = rows.first()
+            && row.len() != headers.len()
+        {
+            return Err(PrestoError::SchemaMismatch(format!(
+                "{} column names for {} columns in {}",
+                headers.len(),
+                row.len(),
+                path
+            )));
+        }
+        Ok(Dataset { headers, rows })
+    }
+
+    pub fn normalize_missing(
+        &self,
+        missing_codes: &HashMap<String, Vec<String>>,
+    ) -> Result<Dataset, PrestoError> {
+        let mut normalized = self.clone();
+        for (name, codes) in missing_codes {
+            let col_idx = self.column_index(name)?;
+            normalized.rows.par_iter_mut().for_each(|row| {
+                if is_missing_code(codes, &row[col_idx]) {
+                    row[col_idx] = "NA".to_string();
+                }
+            });
+        }
+        Ok(normalized)
+    }
+
+    pub fn reorder_columns(&self, order: &[usize]) -> Dataset {
+        Dataset {
+            headers: permute(&self.headers, order),
+            rows: self.rows.iter().map(|row| permute(row, order)).collect(),
+        }
+    }
+
+    // With whitespace-only cells blanked, as `describe` does unless they are kept.
+    fn whitespace_blanked(&self, keep_whitespace_cells: bool) -> Option<Dataset> {
+        let has_blank = |v: &String| !v.is_empty() && v.trim().is_empty();
+        (!keep_whitespace_cells && self.rows.par_iter().any(|row| row.iter().any(has_blank)))
+            .then(|| blank_whitespace_cells(self).0)
+    }
+
+    pub fn group_by(
+        &self,
+        key_col: &str,
+    ) -> Result<HashMap<String, Vec<ColumnStats>>, PrestoError> {
+        self.group_by_with(key_col, false)
+    }
+
+    /// [`Dataset::group_by`], but with `keep_whitespace_cells` whitespace-only cells
+    /// count as values rather than missing, as in [`DescribeOptions`].
+    pub fn group_by_with(
+        &self,
+        key_col: &str,
+        keep_whitespace_cells: bool,
+    ) -> Result<HashMap<String, Vec<ColumnStats>>, PrestoError> {
+        if let Some(blanked) = self.whitespace_blanked(keep_whitespace_cells) {
+            return blanked.group_by_with(key_col, true);
+        }
+        let key_idx = self.column_index(key_col)?;
+        let mut groups: HashMap<String, Vec<Vec<String>>> = HashMap::new();
+        for row in &self.rows {
+            let key = &row[key_idx];
+            if key.is_empty() || key == "NA" {
+                continue;
+            }
+            groups.entry(key.clone()).or_default().push(row.clone());
+        }
+        groups
+            .into_par_iter()
+            .map(|(key, rows)| {
+                let stats = (0..self.headers.len())
+                    .map(|col_idx| stats::compute_stats(&rows, col_idx))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((key, stats))
+            })
+            .collect()
+    }
+
+    /// [`Dataset::group_by`] as a tidy CSV with one row per (group, column): the
+    /// column's non-missing count, mean, std_dev, min and max within the group.
+    /// Groups are in key order (numeric keys numerically); the key column is left out.
+    /// Whitespace-only cells count as missing, as in `describe`.
+    pub fn group_stats_csv(&self, key_col: &str) -> Result<String, PrestoError> {
+        self.group_stats_csv_with(key_col, false)
+    }
+
+    /// [`Dataset::group_stats_csv`], but with `keep_whitespace_cells` whitespace-only
+    /// cells count as values.
+    pub fn group_stats_csv_with(
+        &self,
+        key_col: &str,
+        keep_whitespace_cells: bool,
+    ) -> Result<String, PrestoError> {
+        if let Some(blanked) = self.whitespace_blanked(keep_whitespace_cells) {
+            return blanked.group_stats_csv_with(key_col, true);
+        }
+        let key_idx = self.column_index(key_col)?;
+        let groups = self.group_by_with(key_col, true)?;
+        let mut counts: HashMap<&str, Vec<usize>> = HashMap::new();
+        for row in &self.rows {
+            let key = row[key_idx].as_str();
+            if key.is_empty() || key == "NA" {
+                continue;
+            }
+            let group_counts = counts
+                .entry(key)
+                .or_insert_with(|| vec![0; self.headers.len()]);
+            for (count, val) in group_counts.iter_mut().zip(row) {
+                *count += usize::from(!val.is_empty() && val != "NA");
+            }
+        }
+        let mut keys: Vec<&String> = groups.keys().collect();
+        keys.sort_by(
+            |a, b| match (stats::parse_finite(a), stats::parse_finite(b)) {
+                (Some(x), Some(y)) => x.total_cmp(&y),
+                _ => a.cmp(b),
+            },
+        );
+        let rows: Vec<Vec<String>> = keys
+            .into_iter()
+            .flat_map(|key| {
+                let (stats, counts) = (&groups[key], &counts[key.as_str()]);
+                (0..self.headers.len())
+                    .filter(move |&col_idx| col_idx != key_idx)
+                    .map(move |col_idx| {
+                        let column = &stats[col_idx];
+                        vec![
+                            key.clone(),
+                            self.headers[col_idx].clone(),
+                            counts[col_idx].to_string(),
+                            report::csv_number(column.mean),
+                            // A single value has no sample deviation.
+                            report::csv_number(column.std_dev.filter(|v| v.is_finite())),
+                            report::csv_number(column.min),
+                            report::csv_number(column.max),
+                        ]
+                    })
+            })
+            .collect();
+        Ok(report::csv_text(
+            &["group", "column", "count", "mean", "std_dev", "min", "max"],
+            &rows,
+        ))
+    }
+
+    /// The rows where `col op value` holds, e.g. `amount > 100` or `status == active`.
+    /// Cells are compared numerically when both sides parse as numbers and as text
+    /// otherwise; missing cells (empty, `NA` or whitespace-only) never match.
+    pub fn filter(&self, col: &str, op: FilterOp, value: &str) -> Result<Dataset, PrestoError> {
+        self.filter_with(col, op, value, false)
+    }
+
+    /// [`Dataset::filter`], but with `keep_whitespace_cells` whitespace-only cells
+    /// count as values rather than missing, as in [`DescribeOptions`].
+    pub fn filter_with(
+        &self,
+        col: &str,
+        op: FilterOp,
+        value: &str,
+        keep_whitespace_cells: bool,
+    ) -> Result<Dataset, PrestoError> {
+        let col_idx = self.column_index(col)?;
+        let target = stats::parse_finite(value);
+        let rows = self
+            .rows
+            .iter()
+            .filter(|row| {
+                let cell = &row[col_idx];
+                if is_missing_cell(cell, keep_whitespace_cells) {
+                    return false;
+                }
+                let ordering = match (stats::parse_finite(cell), target) {
+                    (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal),
+                    _ => cell.as_str().cmp(value),
+                };
+                op.holds(ordering)
+            })
+            .cloned()
+            .collect();
+        Ok(Dataset {
+            headers: self.headers.clone(),
+            rows,
+        })
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// `(rows, columns)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows.len(), self.headers.len())
+    }
+
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    /// Values of the named column in row order. Rows are stored row-major, so this
+    /// collects borrowed values rather than returning a slice.
+    pub fn column(&self, name: &str) -> Option<Vec<&str>> {
+        let idx = self.headers.iter().position(|h| h == name)?;
+        Some(self.rows.iter().map(|row| row[idx].as_str()).collect())
+    }
+
+    pub fn row(&self, i: usize) -> Option<&[String]> {
+        self.rows.get(i).map(Vec::as_slice)
+    }
+
+    pub fn validate(&self) -> Result<(usize, usize), PrestoError> {
+        if self.headers.is_empty() || self.rows.is_empty() {
+            return Err(PrestoError::EmptyDataset);
+        }
+        if let Some((idx, row)) = self
+            .rows
+            .iter()
+            .enumerate()
+            .find(|(_, row)| row.len() != self.headers.len())
+        {
+            return Err(PrestoError::SchemaMismatch(format!(
+                "row {} has {} fields, expected {}",
+                idx + 1,
+                row.len(),
+                self.headers.len()
+            )));
+        }
+        Ok((self.rows.len(), self.headers.len()))
+    }
+
+    pub fn guess_delimiter(&self) -> Option<char> {
+        if self.headers.len() != 1 {
+            return None;
+        }
+        let lines: Vec<&str> = std::iter::once(self.headers[0].as_str())
+            .chain(self.rows.iter().take(20).map(|row| row[0].as_str()))
+            .collect();
+        [';', '\t', '|', ',', ':']
+            .into_iter()
+            .filter_map(|delim| {
+                let first = lines[0].matches(delim).count();
+                let consistent = lines
+                    .iter()
+                    .all(|line| line.matches(delim).count() == first);
+                (first > 0 && consistent).then_some((delim, first))
+            })
+            .max_by_key(|&(_, count)| count)
+            .map(|(delim, _)| delim)
+    }
+
+    pub fn apply_transforms(
+        &self,
+        transforms: &HashMap<String, ValueTransform>,
+    ) -> Result<Dataset, PrestoError> {
+        let mut transformed = self.clone();
+        for (name, transform) in transforms {
+            let col_idx = self.column_index(name)?;
+            transformed.rows.par_iter_mut().for_each(|row| {
+                if let Ok(val) = row[col_idx].parse::<f64>() {
+                    row[col_idx] = match transform.apply(val) {
+                        Some(out) => out.to_string(),
+                        None => "NA".to_string(),
+                    };
+                }
+            });
+        }
+        Ok(transformed)
+    }
+
+    pub fn add_column(&mut self, name: &str, values: Vec<String>) -> Result<(), PrestoError> {
+        if self.headers.iter().any(|h| h == name) {
+            return Err(PrestoError::SchemaMismatch(format!(
+                "column `{}` already exists",
+                name
+            )));
+        }
+        if values.len() != self.rows.len() {
+            return Err(PrestoError::SchemaMismatch(format!(
+                "column `{}` has {} values, expected {}",
+                name,
+                values.len(),
+                self.rows.len()
+            )));
+        }
+        self.headers.push(name.to_string());
+        for (row, value) in self.rows.iter_mut().zip(values) {
+            row.push(value);
+        }
+        Ok(())
+    }
+
+    pub fn add_computed_column(&mut self, name: &str, expression: &str) -> Result<(), PrestoError> {
+        let expr = Expr::parse(expression, &self.headers)?;
+        let values = self
+            .rows
+            .par_iter()
+            .map(|row| expr.eval(row).map_or("NA".to_string(), |v| v.to_string()))
+            .collect();
+        self.add_column(name, values)
+    }
+
+    fn column_index(&self, name: &str) -> Result<usize, PrestoError> {
+        self.headers
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| PrestoError::ColumnNotFound(name.to_string()))
+    }
+
+    pub fn from_fixed_width(
+        path: &str,
+        widths: &[usize],
+        headers: Option<Vec<String>>,
+    ) -> Result<Self, PrestoError> {
+        let content = read_text(path)?;
+        let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+        let headers = match headers {
+            Some(headers) => headers,
+            None => split_fixed_width(lines.next().ok_or(PrestoError::EmptyDataset)?, widths),
+        };
+        if headers.len() != widths.len() {
+            return Err(PrestoError::SchemaMismatch(format!(
+                "{} headers for {} fixed-width columns",
+                headers.len(),
+                widths.len()
+            )));
+        }
+        let headers = disambiguate_headers(headers);
+        let rows = lines.map(|line| split_fixed_width(line, widths)).collect();
         Ok(Dataset { headers, rows })
     }
+
+    pub fn from_csv_files(paths: &[String]) -> Result<Self, PrestoError> {
+        let (first, rest) = paths
+            .split_first()
+            .ok_or_else(|| PrestoError::InvalidNumeric("No CSV files provided".to_string()))?;
+        let mut dataset = Dataset::from_csv(first)?;
+        for path in rest {
+            let part = Dataset::from_csv(path)?;
+            if part.headers != dataset.headers {
+                return Err(PrestoError::SchemaMismatch(format!(
+                    "{} has headers {:?}, expected {:?} (from {})",
+                    path, part.headers, dataset.headers, first
+                )));
+            }
+            dataset.rows.extend(part.rows);
+        }
+        Ok(dataset)
+    }
+
+    pub fn from_csv_dir(dir: &str) -> Result<Self, PrestoError> {
+        Dataset::from_csv_glob(&Path::new(dir).join("*.csv").to_string_lossy())
+    }
+
+    pub fn from_csv_glob(pattern: &str) -> Result<Self, PrestoError> {
+        let pattern_path = Path::new(pattern);
+        let dir = match pattern_path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let file_pattern = pattern_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .ok_or_else(|| PrestoError::InvalidNumeric(format!("Invalid pattern: {}", pattern)))?;
+        let mut paths: Vec<String> = std::fs::read_dir(dir)
+            .map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file())
+            .filter(|path| {
+                path.file_name()
+                    .is_some_and(|name| wildcard_match(&file_pattern, &name.to_string_lossy()))
+            })
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        if paths.is_empty() {
+            return Err(PrestoError::InvalidNumeric(format!(
+                "No CSV files matched {}",
+                pattern
+            )));
+        }
+        paths.sort();
+        Dataset::from_csv_files(&paths)
+    }
+}
+
+#[cfg(not(feature = "encoding"))]
+fn read_text(path: &str) -> Result<String, PrestoError> {
+    let text =
+        std::fs::read_to_string(path).map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
+    Ok(match text.strip_prefix('\u{feff}') {
+        Some(stripped) => stripped.to_string(),
+        None => text,
+    })
+}
+
+#[cfg(feature = "encoding")]
+fn read_text(path: &str) -> Result<String, PrestoError> {
+    let bytes = std::fs::read(path).map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
+    let text = match encoding_rs::Encoding::for_bom(&bytes) {
+        Some((encoding, bom_len)) => encoding.decode_without_bom_handling(&bytes[bom_len..]).0,
+        None => match std::str::from_utf8(&bytes) {
+            Ok(text) => std::borrow::Cow::Borrowed(text),
+            Err(_) => {
+                encoding_rs::WINDOWS_1252
+                    .decode_without_bom_handling(&bytes)
+                    .0
+            }
+        },
+    };
+    Ok(text.into_owned())
+}
+
+// Repeated names get a `.N` suffix (`id`, `id.1`, `id.2`) so name-based
+// lookups always resolve to a single column.
+fn disambiguate_headers(headers: Vec<String>) -> Vec<String> {
+    let mut seen: HashSet<String> = HashSet::new();
+    headers
+        .into_iter()
+        .map(|header| {
+            let mut name = header.clone();
+            let mut suffix = 0;
+            while !seen.insert(name.clone()) {
+                suffix += 1;
+                name = format!("{}.{}", header, suffix);
+            }
+            name
+        })
+        .collect()
+}
+
+fn split_fixed_width(line: &str, widths: &[usize]) -> Vec<String> {
+    let mut chars = line.chars();
+    widths
+        .iter()
+        .map(|&width| {
+            chars
+                .by_ref()
+                .take(width)
+                .collect::<String>()
+                .trim()
+                .to_string()
+        })
+        .collect()
+}
+
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = backtrack {
+            p = star_p + 1;
+            n = star_n + 1;
+            backtrack = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
 }
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct Description {
     stats: Vec<ColumnStats>,
     missing: Vec<usize>,
     duplicates: usize,
-    outliers: Vec<Vec<usize>>,
+    outliers: Vec<Vec<(usize, f64)>>,
     types: Vec<TypeInference>,
     dependency_scores: Vec<f64>,
     drift_scores: Vec<f64>,
@@ -75,69 +1044,630 @@ pub struct Description {
     correlations: Vec<Vec<f64>>,
     feature_importance: Vec<(usize, f64)>,
     anomalies: Vec<(usize, f64, usize)>,
+    co_missingness: Vec<Vec<f64>>,
+    anomaly_explanations: Vec<String>,
+    cardinality_estimated: bool,
+    empty_columns: Vec<usize>,
+    positive_rates: Vec<Option<f64>>,
+    outlier_counts: Vec<usize>,
+    stability: Vec<Option<(f64, f64)>>,
+    sequence_columns: Vec<usize>,
+    type_confidence: Vec<f64>,
+    duplicate_columns: Vec<(usize, usize)>,
+    drift_series: Vec<Vec<f64>>,
+    retained_columns: Option<Vec<usize>>,
+    source_columns: usize,
+    integer_widths: Vec<Option<IntegerWidth>>,
+    transforms: Vec<Option<ValueTransform>>,
+    missing_codes: Vec<Vec<String>>,
+    recommended_dtype: Vec<String>,
+    gini: Vec<f64>,
+    whitespace_counts: Vec<usize>,
+    shape_thresholds: ShapeThresholds,
+    boolean_encoding: BooleanEncoding,
+    non_finite_counts: Vec<usize>,
+    semantic_types: Vec<Option<SemanticType>>,
+    min_rows: Option<usize>,
+    categorical_drift: Vec<Option<f64>>,
+    correlation_method: CorrelationMethod,
+    duplicate_key: Vec<String>,
+    type_mismatches: Vec<Option<(TypeInference, Vec<String>)>>,
+    sentinels: Vec<Option<(f64, usize)>>,
+    correlation_ratios: Vec<(usize, usize, f64)>,
+    linear_combinations: Option<Vec<LinearDependency>>,
+    provenance: Provenance,
+    cardinality_ratio: Vec<f64>,
+    parse_rate: Vec<Option<f64>>,
+    case_variants: Vec<Vec<CaseVariantGroup>>,
+    column_hashes: Vec<u64>,
+    dataset_hash: u64,
+    outlier_bounds: Vec<Option<(f64, f64)>>,
+    anomaly_bounds: Vec<Option<(f64, f64)>>,
+    list_stats: Vec<Option<ListStats>>,
+    skipped_stages: Vec<String>,
+    granularity: Vec<Option<Granularity>>,
+    normality: Vec<Option<(f64, f64)>>,
 }
 
+fn covariance_term(x: f64, y: f64, mean_x: f64, mean_y: f64) -> f64 {
+    (x - mean_x) * (y - mean_y)
+}
+
+const SUMMARY_DRIFT_THRESHOLD: f64 = 0.5;
+const SUMMARY_JS_DRIFT_THRESHOLD: f64 = 0.1;
+const DRIFT_SEGMENTS: usize = 10;
+
 impl Description {
-    pub fn new(
-        stats: Vec<ColumnStats>,
-        missing: Vec<usize>,
-        duplicates: usize,
-        outliers: Vec<Vec<usize>>,
-        types: Vec<TypeInference>,
-        dependency_scores: Vec<f64>,
-        drift_scores: Vec<f64>,
-        cardinality: Vec<usize>,
-        distributions: Vec<Vec<(f64, usize)>>,
-        consistency_issues: Vec<usize>,
-        temporal_patterns: Vec<String>,
-        transform_suggestions: Vec<String>,
-        noise_scores: Vec<f64>,
-        redundancy_pairs: Vec<(usize, usize, f64)>,
-        total_rows: usize,
-        missing_pct: f64,
-        unique_pct: f64,
-        top_values: Vec<(String, Vec<(String, usize)>)>,
-        correlations: Vec<Vec<f64>>,
-        feature_importance: Vec<(usize, f64)>,
-        anomalies: Vec<(usize, f64, usize)>,
-    ) -> Self {
-        Description {
-            stats,
-            missing,
-            duplicates,
-            outliers,
-            types,
-            dependency_scores,
-            drift_scores,
-            cardinality,
-            distributions,
-            consistency_issues,
-            temporal_patterns,
-            transform_suggestions,
-            noise_scores,
-            redundancy_pairs,
-            total_rows,
-            missing_pct,
-            unique_pct,
-            top_values,
-            correlations,
-            feature_importance,
-            anomalies,
-        }
+    pub fn summary_line(&self) -> String {
+        let num_cols = self.stats.len();
+        let dup_pct = if self.total_rows > 0 {
+            self.duplicates as f64 / self.total_rows as f64 * 100.0
+        } else {
+            0.0
+        };
+        let drift_cols = self
+            .drift_scores
+            .iter()
+            .zip(&self.categorical_drift)
+            .filter(|&(&d, js)| {
+                d > SUMMARY_DRIFT_THRESHOLD || js.is_some_and(|js| js > SUMMARY_JS_DRIFT_THRESHOLD)
+            })
+            .count();
+        format!(
+            "rows={} cols={} missing={:.1}% dup={:.1}% drift_cols={} outlier_rows={}{}",
+            self.total_rows,
+            num_cols,
+            self.missing_pct,
+            dup_pct,
+            drift_cols,
+            self.outlier_rows(),
+            if self.small_sample().is_some() {
+                " small_sample"
+            } else {
+                ""
+            }
+        )
     }
-}
 
-pub fn describe(dataset: &Dataset) -> Result<Description, PrestoError> {
-    if dataset.rows.is_empty() {
-        return Err(PrestoError::EmptyDataset);
+    fn outlier_rows(&self) -> usize {
+        self.outliers
+            .iter()
+            .flat_map(|col| col.iter().map(|&(row, _)| row))
+            .collect::<std::collections::HashSet<usize>>()
+            .len()
     }
 
-    let num_cols = dataset.headers.len();
+    pub fn quality_score(&self) -> f64 {
+        if self.total_rows == 0 {
+            return 0.0;
+        }
+        let completeness = 1.0 - self.missing_pct / 100.0;
+        let row_uniqueness = 1.0 - self.duplicates as f64 / self.total_rows as f64;
+        let clean_rows = 1.0 - self.outlier_rows() as f64 / self.total_rows as f64;
+        (completeness + row_uniqueness + clean_rows) / 3.0 * 100.0
+    }
 
-    let stats: Vec<ColumnStats> = (0..num_cols)
-        .into_par_iter()
-        .map(|col_idx| stats::compute_stats(&dataset.rows, col_idx))
-        .collect::<Result<_, _>>()?;
+    pub fn column_order(&self, order: ColumnOrder) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.stats.len()).collect();
+        match order {
+            ColumnOrder::File => {}
+            ColumnOrder::Missing => indices.sort_by_key(|&i| std::cmp::Reverse(self.missing[i])),
+            ColumnOrder::Cardinality => {
+                indices.sort_by_key(|&i| std::cmp::Reverse(self.cardinality[i]))
+            }
+            ColumnOrder::Variance => indices.sort_by(|&a, &b| {
+                let (va, vb) = (self.stats[a].variance, self.stats[b].variance);
+                vb.partial_cmp(&va).unwrap_or(std::cmp::Ordering::Equal)
+            }),
+        }
+        indices
+    }
+
+    pub fn retained_columns(&self) -> Option<&[usize]> {
+        self.retained_columns.as_deref()
+    }
+
+    pub fn source_columns(&self) -> usize {
+        self.source_columns
+    }
+
+    pub fn shape_thresholds(&self) -> ShapeThresholds {
+        self.shape_thresholds
+    }
+
+    pub fn correlation_method(&self) -> CorrelationMethod {
+        self.correlation_method
+    }
+
+    pub fn provenance(&self) -> &Provenance {
+        &self.provenance
+    }
+
+    /// Records where the data came from (a path, query, ...) in the provenance block.
+    pub fn set_source(&mut self, source: &str) {
+        self.provenance.source = Some(source.to_string());
+    }
+
+    /// Stable FNV-1a hash of each column's values, for spotting which columns changed
+    /// between two versions of a file.
+    pub fn column_hashes(&self) -> &[u64] {
+        &self.column_hashes
+    }
+
+    /// Fingerprint of the profiled data (headers and values); equal hashes mean the
+    /// data is unchanged and need not be profiled again.
+    pub fn dataset_hash(&self) -> u64 {
+        self.dataset_hash
+    }
+
+    pub fn duplicate_key(&self) -> &[String] {
+        &self.duplicate_key
+    }
+
+    pub fn total_rows(&self) -> usize {
+        self.total_rows
+    }
+
+    /// `(min, Q1, median, Q3, max)` of column `col`; `None` for non-numeric columns.
+    pub fn five_number_summary(&self, col: usize) -> Option<(f64, f64, f64, f64, f64)> {
+        self.stats.get(col)?.five_number_summary()
+    }
+
+    pub fn value_share(&self, col: usize, count: usize) -> f64 {
+        let non_missing = self.total_rows - self.missing[col];
+        if non_missing == 0 {
+            0.0
+        } else {
+            count as f64 / non_missing as f64 * 100.0
+        }
+    }
+
+    /// A raw cell of column `col` as the profile saw it: `None` for missing codes and
+    /// non-numeric cells, otherwise the value after the column's `--transform`.
+    pub(crate) fn profiled_value(&self, col: usize, raw: &str) -> Option<f64> {
+        if is_missing_code(&self.missing_codes[col], raw) {
+            return None;
+        }
+        let val = parse_finite(raw)?;
+        match self.transforms[col] {
+            Some(transform) => transform.apply(val),
+            None => Some(val),
+        }
+    }
+
+    /// The `limit` rows with the largest share of the covariance sum between columns
+    /// `a` and `b`, as `(row, x, y, percent)`. Shares are signed: rows pulling against
+    /// the overall direction come out negative.
+    pub fn correlation_contributions(
+        &self,
+        dataset: &Dataset,
+        a: usize,
+        b: usize,
+        limit: usize,
+    ) -> Vec<(usize, f64, f64, f64)> {
+        let (Some(mean_a), Some(mean_b)) = (self.stats[a].mean, self.stats[b].mean) else {
+            return Vec::new();
+        };
+        let mut terms: Vec<(usize, f64, f64, f64)> = dataset
+            .rows
+            .iter()
+            .enumerate()
+            .filter_map(|(row, values)| {
+                let (x, y) = (
+                    self.profiled_value(a, &values[a])?,
+                    self.profiled_value(b, &values[b])?,
+                );
+                Some((row, x, y, covariance_term(x, y, mean_a, mean_b)))
+            })
+            .collect();
+        let total: f64 = terms.iter().map(|&(_, _, _, term)| term).sum();
+        if total == 0.0 {
+            return Vec::new();
+        }
+        terms.sort_by(|p, q| q.3.abs().total_cmp(&p.3.abs()));
+        terms
+            .into_iter()
+            .take(limit)
+            .map(|(row, x, y, term)| (row, x, y, term / total * 100.0))
+            .collect()
+    }
+
+    /// Per column, the rows of `dataset` whose profiled value lies more than `floor`
+    /// standard deviations from the column mean, as `(row, |z|)` with the highest first.
+    /// Missing codes and non-numeric cells are skipped and `--transform` is applied, so
+    /// the scores agree with the profile's own outlier counts.
+    pub fn z_scores_above(&self, dataset: &Dataset, floor: f64) -> Vec<Vec<(usize, f64)>> {
+        (0..dataset.headers.len())
+            .map(|col| {
+                let (Some(mean), Some(std_dev)) = (self.stats[col].mean, self.stats[col].std_dev)
+                else {
+                    return Vec::new();
+                };
+                let mut scores: Vec<(usize, f64)> = dataset
+                    .rows
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(row, values)| {
+                        let z = (self.profiled_value(col, &values[col])? - mean).abs() / std_dev;
+                        (z.is_finite() && z > floor).then_some((row, z))
+                    })
+                    .collect();
+                scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+                scores
+            })
+            .collect()
+    }
+
+    /// Stats for column `col` of `dataset` recomputed as `column_type`, reading the
+    /// cells as the profile did: missing codes blanked, `--transform` applied and
+    /// booleans parsed with the profile's encoding.
+    pub fn retyped_stats(
+        &self,
+        dataset: &Dataset,
+        col: usize,
+        column_type: &TypeInference,
+    ) -> Result<ColumnStats, PrestoError> {
+        let rows: Vec<Vec<String>> = dataset
+            .rows
+            .iter()
+            .map(|row| {
+                let raw = &row[col];
+                let val = if is_missing_code(&self.missing_codes[col], raw) {
+                    "NA".to_string()
+                } else {
+                    match (self.transforms[col], raw.parse::<f64>()) {
+                        (Some(transform), Ok(val)) => transform
+                            .apply(val)
+                            .map_or_else(|| "NA".to_string(), |out| out.to_string()),
+                        _ => raw.clone(),
+                    }
+                };
+                vec![val]
+            })
+            .collect();
+        stats::compute_stats_as(&rows, 0, column_type, &self.boolean_encoding)
+    }
+
+    /// Analysis stages cut by [`DescribeOptions::timeout`]; their fields hold
+    /// placeholders (zeros, empty lists, "not computed").
+    pub fn skipped_stages(&self) -> &[String] {
+        &self.skipped_stages
+    }
+
+    pub fn small_sample(&self) -> Option<usize> {
+        self.min_rows.filter(|&min| self.total_rows < min)
+    }
+
+    pub fn reorder_columns(&self, order: &[usize]) -> Description {
+        let mut position = vec![0; order.len()];
+        for (new_idx, &old_idx) in order.iter().enumerate() {
+            position[old_idx] = new_idx;
+        }
+        let permute_matrix = |matrix: &[Vec<f64>]| -> Vec<Vec<f64>> {
+            permute(matrix, order)
+                .iter()
+                .map(|row| permute(row, order))
+                .collect()
+        };
+        Description {
+            stats: permute(&self.stats, order),
+            missing: permute(&self.missing, order),
+            duplicates: self.duplicates,
+            outliers: permute(&self.outliers, order),
+            types: permute(&self.types, order),
+            dependency_scores: permute(&self.dependency_scores, order),
+            drift_scores: permute(&self.drift_scores, order),
+            cardinality: permute(&self.cardinality, order),
+            distributions: permute(&self.distributions, order),
+            consistency_issues: permute(&self.consistency_issues, order),
+            temporal_patterns: permute(&self.temporal_patterns, order),
+            transform_suggestions: permute(&self.transform_suggestions, order),
+            noise_scores: permute(&self.noise_scores, order),
+            redundancy_pairs: self
+                .redundancy_pairs
+                .iter()
+                .map(|&(a, b, score)| (position[a], position[b], score))
+                .collect(),
+            total_rows: self.total_rows,
+            missing_pct: self.missing_pct,
+            unique_pct: self.unique_pct,
+            top_values: permute(&self.top_values, order),
+            correlations: permute_matrix(&self.correlations),
+            feature_importance: self
+                .feature_importance
+                .iter()
+                .map(|&(col, score)| (position[col], score))
+                .collect(),
+            anomalies: self
+                .anomalies
+                .iter()
+                .map(|&(col, value, row)| (position[col], value, row))
+                .collect(),
+            co_missingness: permute_matrix(&self.co_missingness),
+            anomaly_explanations: self.anomaly_explanations.clone(),
+            cardinality_estimated: self.cardinality_estimated,
+            empty_columns: self.empty_columns.iter().map(|&c| position[c]).collect(),
+            positive_rates: permute(&self.positive_rates, order),
+            outlier_counts: permute(&self.outlier_counts, order),
+            stability: permute(&self.stability, order),
+            sequence_columns: self.sequence_columns.iter().map(|&c| position[c]).collect(),
+            type_confidence: permute(&self.type_confidence, order),
+            duplicate_columns: self
+                .duplicate_columns
+                .iter()
+                .map(|&(a, b)| (position[a].min(position[b]), position[a].max(position[b])))
+                .collect(),
+            drift_series: permute(&self.drift_series, order),
+            retained_columns: self
+                .retained_columns
+                .as_ref()
+                .map(|cols| permute(cols, order)),
+            source_columns: self.source_columns,
+            integer_widths: permute(&self.integer_widths, order),
+            transforms: permute(&self.transforms, order),
+            missing_codes: permute(&self.missing_codes, order),
+            recommended_dtype: permute(&self.recommended_dtype, order),
+            gini: permute(&self.gini, order),
+            whitespace_counts: permute(&self.whitespace_counts, order),
+            shape_thresholds: self.shape_thresholds,
+            boolean_encoding: self.boolean_encoding.clone(),
+            non_finite_counts: permute(&self.non_finite_counts, order),
+            semantic_types: permute(&self.semantic_types, order),
+            min_rows: self.min_rows,
+            categorical_drift: permute(&self.categorical_drift, order),
+            correlation_method: self.correlation_method,
+            duplicate_key: self.duplicate_key.clone(),
+            type_mismatches: permute(&self.type_mismatches, order),
+            sentinels: permute(&self.sentinels, order),
+            correlation_ratios: self
+                .correlation_ratios
+                .iter()
+                .map(|&(cat, num, eta)| (position[cat], position[num], eta))
+                .collect(),
+            provenance: self.provenance.clone(),
+            cardinality_ratio: permute(&self.cardinality_ratio, order),
+            parse_rate: permute(&self.parse_rate, order),
+            case_variants: permute(&self.case_variants, order),
+            column_hashes: permute(&self.column_hashes, order),
+            dataset_hash: self.dataset_hash,
+            outlier_bounds: permute(&self.outlier_bounds, order),
+            anomaly_bounds: permute(&self.anomaly_bounds, order),
+            list_stats: permute(&self.list_stats, order),
+            skipped_stages: self.skipped_stages.clone(),
+            granularity: permute(&self.granularity, order),
+            normality: permute(&self.normality, order),
+            linear_combinations: self.linear_combinations.as_ref().map(|found| {
+                found
+                    .iter()
+                    .map(|(col, terms, r_squared)| {
+                        (
+                            position[*col],
+                            terms
+                                .iter()
+                                .map(|&(other, coefficient)| (position[other], coefficient))
+                                .collect(),
+                            *r_squared,
+                        )
+                    })
+                    .collect()
+            }),
+        }
+    }
+}
+
+fn rank_columns(dataset: &Dataset, order: ColumnOrder) -> Result<Vec<usize>, PrestoError> {
+    let num_cols = dataset.headers.len();
+    let mut indices: Vec<usize> = (0..num_cols).collect();
+    match order {
+        ColumnOrder::File => {}
+        ColumnOrder::Missing => indices.sort_by_cached_key(|&i| {
+            std::cmp::Reverse(
+                dataset
+                    .rows
+                    .iter()
+                    .filter(|row| row[i].is_empty() || row[i] == "NA")
+                    .count(),
+            )
+        }),
+        ColumnOrder::Cardinality => {
+            let cardinality = compute_cardinality(dataset)?;
+            indices.sort_by_key(|&i| std::cmp::Reverse(cardinality[i]));
+        }
+        ColumnOrder::Variance => {
+            let variances: Vec<Option<f64>> = (0..num_cols)
+                .into_par_iter()
+                .map(|i| stats::compute_stats(&dataset.rows, i).map(|s| s.variance))
+                .collect::<Result<_, _>>()?;
+            indices.sort_by(|&a, &b| {
+                variances[b]
+                    .partial_cmp(&variances[a])
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        }
+    }
+    Ok(indices)
+}
+
+/// Whether `val` is one of `codes`, textually or as the same number (`-1` matches `-1.0`).
+// Empty and `NA` cells are missing, and so are whitespace-only cells unless they
+// are kept as values.
+fn is_missing_cell(val: &str, keep_whitespace_cells: bool) -> bool {
+    val.is_empty() || val == "NA" || (!keep_whitespace_cells && val.trim().is_empty())
+}
+
+fn is_missing_code(codes: &[String], val: &str) -> bool {
+    codes.iter().any(|code| {
+        code == val || matches!((code.parse::<f64>(), val.parse::<f64>()), (Ok(c), Ok(v)) if c == v)
+    })
+}
+
+fn permute<T: Clone>(values: &[T], order: &[usize]) -> Vec<T> {
+    order
+        .iter()
+        .filter_map(|&i| values.get(i).cloned())
+        .collect()
+}
+
+const NOT_COMPUTED: &str = "not computed";
+
+/// Walks describe's stages against the timeout: once the deadline passes, every
+/// remaining stage is recorded as skipped instead of run.
+struct StageClock<'a> {
+    deadline: Option<Instant>,
+    progress: &'a (dyn Fn(&str) + Sync),
+    skipped: Vec<String>,
+}
+
+impl StageClock<'_> {
+    fn start(&mut self, stage: &str) -> bool {
+        if !self.skipped.is_empty() || self.deadline.is_some_and(|d| Instant::now() >= d) {
+            self.skipped.push(stage.to_string());
+            return false;
+        }
+        (self.progress)(stage);
+        true
+    }
+}
+
+pub fn describe(dataset: &Dataset) -> Result<Description, PrestoError> {
+    describe_with_options(dataset, &DescribeOptions::default())
+}
+
+pub fn describe_with_options(
+    dataset: &Dataset,
+    options: &DescribeOptions,
+) -> Result<Description, PrestoError> {
+    describe_with_progress(dataset, options, &|_| {})
+}
+
+/// Like [`describe_with_options`], calling `progress` with the name of each
+/// stage as it starts.
+pub fn describe_with_progress(
+    dataset: &Dataset,
+    options: &DescribeOptions,
+    progress: &(dyn Fn(&str) + Sync),
+) -> Result<Description, PrestoError> {
+    // Column statistics and types always complete; later stages answer to the clock.
+    let mut clock = StageClock {
+        deadline: options.timeout.map(|timeout| Instant::now() + timeout),
+        progress,
+        skipped: Vec::new(),
+    };
+    let blanked;
+    let dataset = if options.keep_whitespace_cells
+        || !dataset
+            .rows
+            .par_iter()
+            .any(|row| row.iter().any(|v| !v.is_empty() && v.trim().is_empty()))
+    {
+        dataset
+    } else {
+        blanked = blank_whitespace_cells(dataset).0;
+        &blanked
+    };
+    let normalized;
+    let dataset = if options.missing_codes.is_empty() {
+        dataset
+    } else {
+        normalized = dataset.normalize_missing(&options.missing_codes)?;
+        &normalized
+    };
+    let transformed;
+    let dataset = if options.transforms.is_empty() {
+        dataset
+    } else {
+        transformed = dataset.apply_transforms(&options.transforms)?;
+        &transformed
+    };
+    if dataset.rows.is_empty() {
+        return Err(PrestoError::EmptyDataset);
+    }
+
+    // Resolved before --max-cols trimming so a key column need not be profiled.
+    let keyed_duplicates = if options.duplicate_key.is_empty() {
+        None
+    } else {
+        let key_cols = options
+            .duplicate_key
+            .iter()
+            .map(|name| dataset.column_index(name))
+            .collect::<Result<Vec<_>, _>>()?;
+        Some(detect_duplicates_by(&dataset.rows, &key_cols))
+    };
+
+    for name in options
+        .analysis_exclusions
+        .iter()
+        .chain(options.list_columns.keys())
+    {
+        dataset.column_index(name)?;
+    }
+
+    let source_columns = dataset.headers.len();
+    let limited;
+    let (dataset, retained_columns) = match options.max_cols {
+        Some(max_cols) if max_cols < source_columns => {
+            let mut kept = rank_columns(dataset, options.limit_by)?;
+            kept.truncate(max_cols);
+            kept.sort_unstable();
+            limited = dataset.reorder_columns(&kept);
+            (&limited, Some(kept))
+        }
+        _ => (dataset, None),
+    };
+
+    let num_cols = dataset.headers.len();
+    let transforms: Vec<Option<ValueTransform>> = dataset
+        .headers
+        .iter()
+        .map(|header| options.transforms.get(header).copied())
+        .collect();
+    let missing_codes: Vec<Vec<String>> = dataset
+        .headers
+        .iter()
+        .map(|header| {
+            options
+                .missing_codes
+                .get(header)
+                .cloned()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    // Sorted and deduplicated: a name listed twice must not count twice.
+    let mut excluded: Vec<usize> = options
+        .analysis_exclusions
+        .iter()
+        .filter_map(|name| dataset.headers.iter().position(|h| h == name))
+        .collect();
+    excluded.sort_unstable();
+    excluded.dedup();
+    let weight_idx = options
+        .weight_col
+        .as_deref()
+        .map(|name| dataset.column_index(name))
+        .transpose()?;
+    let overrides: Vec<Option<&TypeInference>> = dataset
+        .headers
+        .iter()
+        .map(|header| options.type_overrides.get(header))
+        .collect();
+    progress("column statistics");
+    let stats: Vec<ColumnStats> = (0..num_cols)
+        .into_par_iter()
+        .map(|col_idx| match (overrides[col_idx], weight_idx) {
+            (Some(column_type), _) => stats::compute_stats_as(
+                &dataset.rows,
+                col_idx,
+                column_type,
+                &options.boolean_encoding,
+            ),
+            (None, Some(weight_idx)) if weight_idx != col_idx => {
+                stats::compute_weighted_stats(&dataset.rows, col_idx, weight_idx)
+            }
+            (None, _) => stats::compute_stats(&dataset.rows, col_idx),
+        })
+        .collect::<Result<_, _>>()?;
 
     let missing: Vec<usize> = (0..num_cols)
         .into_par_iter()
@@ -150,202 +1680,2984 @@ pub fn describe(dataset: &Dataset) -> Result<Description, PrestoError> {
         })
         .collect();
 
-    let duplicates = detect_duplicates(&dataset.rows);
+    let empty_columns = cleaning::detect_empty_columns(&missing, dataset.rows.len());
+
+    let duplicates = keyed_duplicates.unwrap_or_else(|| detect_duplicates(&dataset.rows));
 
-    let outliers: Vec<Vec<usize>> = (0..num_cols)
+    let outliers: Vec<Vec<(usize, f64)>> = (0..num_cols)
         .into_par_iter()
         .map(|col_idx| detect_outliers(&dataset.rows, col_idx, &stats[col_idx]))
         .collect();
+    let outlier_counts: Vec<usize> = outliers.iter().map(|o| o.len()).collect();
+    let outlier_bounds: Vec<Option<(f64, f64)>> = stats
+        .iter()
+        .map(|s| cleaning::outlier_bounds(s, cleaning::OUTLIER_Z_THRESHOLD))
+        .collect();
+    // Rolling-window scores have no column-wide fence.
+    let anomaly_bounds: Vec<Option<(f64, f64)>> = match options.anomaly_window {
+        Some(_) => vec![None; num_cols],
+        None => stats
+            .iter()
+            .map(|s| options.anomaly_method.bounds(s))
+            .collect(),
+    };
 
-    let types: Vec<TypeInference> = (0..num_cols)
+    let list_stats: Vec<Option<ListStats>> = (0..num_cols)
         .into_par_iter()
-        .map(|col_idx| types::infer_type(&dataset.rows, col_idx))
+        .map(|col_idx| {
+            let delimiter = options.list_columns.get(&dataset.headers[col_idx])?;
+            stats::compute_list_stats(&dataset.rows, col_idx, delimiter)
+        })
         .collect();
 
-    let dependency_scores = compute_dependency_scores(dataset, &stats)?;
-    let drift_scores = detect_drift(dataset, &stats)?;
-    let cardinality = compute_cardinality(dataset)?;
-    let distributions = compute_distribution(dataset, &stats)?;
-    let consistency_issues = check_consistency(dataset)?;
-    let temporal_patterns = detect_temporal_patterns(dataset)?;
-    let transform_suggestions = suggest_transformations(&stats)?;
-    let noise_scores = estimate_noise(dataset, &stats)?;
-    let redundancy_pairs = detect_redundancy(dataset)?;
-
-    let total_rows = dataset.rows.len();
-    let total_cells = total_rows * num_cols;
-    let missing_pct = missing.iter().sum::<usize>() as f64 / total_cells as f64 * 100.0;
-    let unique_rows: std::collections::HashSet<&Vec<String>> = dataset.rows.iter().collect();
-    let unique_pct = unique_rows.len() as f64 / total_rows as f64 * 100.0;
-
-    let top_values: Vec<(String, Vec<(String, usize)>)> = (0..num_cols)
+    progress("type inference");
+    let type_rows = types::sample_rows(&dataset.rows, options.type_sampling, options.seed);
+    let types: Vec<TypeInference> = (0..num_cols)
+        .into_par_iter()
+        .map(|col_idx| match overrides[col_idx] {
+            Some(column_type) => column_type.clone(),
+            None => types::infer_type_with(&type_rows, col_idx, &options.boolean_encoding),
+        })
+        .collect();
+    let type_confidence: Vec<f64> = (0..num_cols)
         .into_par_iter()
         .map(|col_idx| {
-            let mut counts: std::collections::HashMap<String, usize> =
-                std::collections::HashMap::new();
-            for row in &dataset.rows {
-                let val = &row[col_idx];
-                if !val.is_empty() && val != "NA" {
-                    *counts.entry(val.clone()).or_insert(0) += 1;
-                }
-            }
-            let mut sorted: Vec<(String, usize)> = counts.into_iter().collect();
-            sorted.sort_by(|a, b| b.1.cmp(&a.1));
-            (
-                dataset.headers[col_idx].clone(),
-                sorted.into_iter().take(5).collect(),
+            types::type_confidence(
+                &type_rows,
+                col_idx,
+                &types[col_idx],
+                &options.boolean_encoding,
             )
         })
         .collect();
-
-    let correlations: Vec<Vec<f64>> = (0..num_cols)
+    let type_mismatches: Vec<Option<(TypeInference, Vec<String>)>> = (0..num_cols)
         .into_par_iter()
-        .map(|i| {
-            (0..num_cols)
-                .map(|j| {
-                    if i == j {
-                        return 1.0;
-                    }
-                    let col_i: Vec<f64> = dataset
-                        .rows
-                        .iter()
-                        .filter_map(|row| row[i].parse::<f64>().ok())
-                        .collect();
-                    let col_j: Vec<f64> = dataset
-                        .rows
-                        .iter()
-                        .filter_map(|row| row[j].parse::<f64>().ok())
-                        .collect();
-                    if col_i.len() != col_j.len() || col_i.is_empty() {
-                        return 0.0;
-                    }
-                    if let (Some(mean_i), Some(std_i)) = (stats[i].mean, stats[i].std_dev) {
-                        if let (Some(mean_j), Some(std_j)) = (stats[j].mean, stats[j].std_dev) {
-                            let cov = col_i
-                                .iter()
-                                .zip(col_j.iter())
-                                .map(|(&x, &y)| (x - mean_i) * (y - mean_j))
-                                .sum::<f64>()
-                                / col_i.len() as f64;
-                            cov / (std_i * std_j)
-                        } else {
-                            0.0
-                        }
-                    } else {
-                        0.0
-                    }
-                })
-                .collect()
+        .map(|col_idx| {
+            types::type_mismatch_samples(
+                &type_rows,
+                col_idx,
+                &types[col_idx],
+                &options.boolean_encoding,
+            )
         })
         .collect();
-
-    let target_idx = dataset
-        .headers
-        .iter()
-        .position(|h| h.to_lowercase().contains("target"))
-        .unwrap_or(0);
-    let target_values: Vec<f64> = dataset
-        .rows
-        .iter()
-        .filter_map(|row| row[target_idx].parse::<f64>().ok())
+    let parse_rate: Vec<Option<f64>> = (0..num_cols)
+        .into_par_iter()
+        .map(|col_idx| {
+            types::parse_rate(
+                &dataset.rows,
+                col_idx,
+                &types[col_idx],
+                &options.boolean_encoding,
+            )
+        })
         .collect();
-    let feature_importance: Vec<(usize, f64)> = (0..num_cols)
+
+    let integer_widths: Vec<Option<IntegerWidth>> = (0..num_cols)
         .into_par_iter()
-        .filter_map(|col_idx| {
-            let col_values: Vec<f64> = dataset
-                .rows
-                .iter()
-                .filter_map(|row| row[col_idx].parse::<f64>().ok())
-                .collect();
-            if col_idx != target_idx
-                && !col_values.is_empty()
-                && col_values.len() == target_values.len()
-            {
-                let corr = if let (Some(mean_i), Some(std_i)) =
-                    (stats[col_idx].mean, stats[col_idx].std_dev)
-                {
-                    if let (Some(mean_t), Some(std_t)) =
-                        (stats[target_idx].mean, stats[target_idx].std_dev)
-                    {
-                        let cov = col_values
-                            .iter()
-                            .zip(target_values.iter())
-                            .map(|(&x, &y)| (x - mean_i) * (y - mean_t))
-                            .sum::<f64>()
-                            / col_values.len() as f64;
-                        cov / (std_i * std_t)
-                    } else {
-                        0.0
-                    }
-                } else {
-                    0.0
-                };
-                Some((col_idx, corr.abs()))
-            } else {
-                None
-            }
+        .map(|col_idx| match types[col_idx] {
+            TypeInference::Integer => types::integer_width(&dataset.rows, col_idx),
+            _ => None,
         })
         .collect();
-    let mut feature_importance = feature_importance;
-    feature_importance.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
 
-    let anomalies: Vec<(usize, f64, usize)> = (0..num_cols)
+    let positive_rates: Vec<Option<f64>> = (0..num_cols)
         .into_par_iter()
-        .flat_map(|col_idx| {
-            let col_values: Vec<(f64, usize)> = dataset
-                .rows
-                .iter()
-                .enumerate()
-                .filter_map(|(idx, row)| row[col_idx].parse::<f64>().ok().map(|v| (v, idx)))
-                .collect();
-            if let (Some(mean), Some(std_dev)) = (stats[col_idx].mean, stats[col_idx].std_dev) {
-                col_values
-                    .into_iter()
-                    .filter(|&(val, _)| (val - mean).abs() / std_dev > 3.0)
-                    .map(move |(val, idx)| (col_idx, val, idx))
-                    .collect::<Vec<_>>()
-            } else {
-                Vec::new()
+        .map(|col_idx| match types[col_idx] {
+            TypeInference::Boolean => {
+                types::boolean_positive_rate(&dataset.rows, col_idx, &options.boolean_encoding)
             }
+            _ => None,
         })
         .collect();
 
-    let description = Description::new(
-        stats,
-        missing,
-        duplicates,
-        outliers,
-        types,
+    let column_hashes = compute_column_hashes(dataset);
+    let dataset_hash = compute_dataset_hash(dataset, &column_hashes);
+
+    let (
         dependency_scores,
         drift_scores,
+        drift_series,
+        categorical_drift,
+        sentinels,
+        case_variants,
         cardinality,
+    ) = if clock.start("dependency and drift") {
+        (
+            compute_dependency_scores(dataset, &stats, &excluded)?,
+            detect_drift(dataset, &stats)?,
+            compute_drift_series(dataset, &stats, DRIFT_SEGMENTS)?,
+            detect_categorical_drift(dataset, &types)?,
+            detect_sentinels(dataset, &types)?,
+            detect_case_variants(dataset, &types)?,
+            if options.approx_cardinality {
+                estimate_cardinality(dataset)?
+            } else {
+                compute_cardinality(dataset)?
+            },
+        )
+    } else {
+        (
+            vec![0.0; num_cols],
+            vec![0.0; num_cols],
+            vec![Vec::new(); num_cols],
+            vec![None; num_cols],
+            vec![None; num_cols],
+            vec![Vec::new(); num_cols],
+            vec![0; num_cols],
+        )
+    };
+    // Distinct over non-missing values: ~1.0 is ID-like, ~0 nearly constant. The
+    // HyperLogLog estimate can overshoot, hence the clamp.
+    let cardinality_ratio: Vec<f64> = cardinality
+        .iter()
+        .zip(&missing)
+        .map(|(&distinct, &missing)| {
+            let present = dataset.rows.len() - missing;
+            if present == 0 {
+                0.0
+            } else {
+                (distinct as f64 / present as f64).min(1.0)
+            }
+        })
+        .collect();
+    let (
         distributions,
         consistency_issues,
+        whitespace_counts,
+        non_finite_counts,
         temporal_patterns,
+        sequence_columns,
+        semantic_types,
+        recommended_dtype,
         transform_suggestions,
+        granularity,
+        normality,
+    ) = if clock.start("distributions and consistency") {
+        let temporal_patterns = detect_temporal_patterns(dataset)?;
+        let semantic_types: Vec<Option<SemanticType>> = (0..num_cols)
+            .into_par_iter()
+            .map(|col_idx| types::semantic_type(&dataset.rows, col_idx, &types[col_idx]))
+            .collect();
+        let recommended_dtype: Vec<String> = (0..num_cols)
+            .into_par_iter()
+            .map(|col_idx| {
+                types::recommended_dtype(
+                    &types[col_idx],
+                    integer_widths[col_idx],
+                    types::has_leading_zeros(&dataset.rows, col_idx),
+                    temporal_patterns[col_idx] == "Date-like",
+                    cardinality[col_idx],
+                    dataset.rows.len() - missing[col_idx],
+                    missing[col_idx],
+                )
+                .to_string()
+            })
+            .collect();
+        let normality = compute_normality(dataset, &types)?;
+        (
+            compute_distribution(dataset, &stats)?,
+            check_consistency(dataset)?,
+            count_untrimmed(dataset)?,
+            count_non_finite(dataset)?,
+            temporal_patterns,
+            detect_sequence_columns(dataset)?,
+            semantic_types,
+            recommended_dtype,
+            suggest_transformations(
+                &stats,
+                &normality,
+                options.explain,
+                &options.shape_thresholds,
+            )?,
+            detect_granularity(dataset, &types)?,
+            normality,
+        )
+    } else {
+        (
+            vec![Vec::new(); num_cols],
+            vec![0; num_cols],
+            vec![0; num_cols],
+            vec![0; num_cols],
+            vec![NOT_COMPUTED.to_string(); num_cols],
+            Vec::new(),
+            vec![None; num_cols],
+            vec![NOT_COMPUTED.to_string(); num_cols],
+            vec![NOT_COMPUTED.to_string(); num_cols],
+            vec![None; num_cols],
+            vec![None; num_cols],
+        )
+    };
+    let (
         noise_scores,
         redundancy_pairs,
-        total_rows,
-        missing_pct,
-        unique_pct,
-        top_values,
-        correlations,
-        feature_importance,
-        anomalies,
-    );
+        correlation_ratios,
+        linear_combinations,
+        duplicate_columns,
+        co_missingness,
+        stability,
+    ) = if clock.start("noise and redundancy") {
+        (
+            estimate_noise(dataset, &stats, options.seed)?,
+            detect_redundancy(dataset, &excluded)?,
+            compute_correlation_ratios(dataset, &types, &stats, &excluded)?,
+            if options.linear_dependencies {
+                Some(detect_linear_combinations(
+                    dataset, &types, &stats, &excluded,
+                )?)
+            } else {
+                None
+            },
+            detect_duplicate_columns(dataset, &column_hashes)?,
+            compute_co_missingness(dataset)?,
+            match options.stability_folds {
+                Some(folds) => compute_stability(dataset, folds)?,
+                None => Vec::new(),
+            },
+        )
+    } else {
+        (
+            vec![0.0; num_cols],
+            Vec::new(),
+            Vec::new(),
+            None,
+            Vec::new(),
+            vec![vec![0.0; num_cols]; num_cols],
+            Vec::new(),
+        )
+    };
 
-    render_tui(dataset, &description)?;
+    let total_rows = dataset.rows.len();
+    let total_cells = total_rows * num_cols;
+    let missing_pct = missing.iter().sum::<usize>() as f64 / total_cells as f64 * 100.0;
+    let unique_rows: std::collections::HashSet<&Vec<String>> = dataset.rows.iter().collect();
+    let unique_pct = unique_rows.len() as f64 / total_rows as f64 * 100.0;
 
-    Ok(description)
-}
+    let (top_values, gini) = if clock.start("value frequencies") {
+        let value_counts: Vec<HashMap<&str, usize>> = (0..num_cols)
+            .into_par_iter()
+            .map(|col_idx| {
+                let mut counts: HashMap<&str, usize> = HashMap::new();
+                for row in &dataset.rows {
+                    let val = &row[col_idx];
+                    if !val.is_empty() && val != "NA" {
+                        *counts.entry(val.as_str()).or_insert(0) += 1;
+                    }
+                }
+                counts
+            })
+            .collect();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let top_values: Vec<(String, Vec<(String, usize)>)> = value_counts
+            .par_iter()
+            .enumerate()
+            .map(|(col_idx, counts)| {
+                let mut sorted: Vec<(String, usize)> = counts
+                    .iter()
+                    .map(|(&val, &count)| (val.to_string(), count))
+                    .collect();
+                sorted.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                (
+                    column_label(&dataset.headers[col_idx], transforms[col_idx]),
+                    sorted.into_iter().take(5).collect(),
+                )
+            })
+            .collect();
 
-    #[test]
-    fn test_describe_empty() {
-        let dataset = Dataset::new(vec![], vec![]);
-        assert!(matches!(describe(&dataset), Err(PrestoError::EmptyDataset)));
+        let gini: Vec<f64> = value_counts
+            .par_iter()
+            .map(|counts| {
+                let total: usize = counts.values().sum();
+                if total == 0 {
+                    return 0.0;
+                }
+                // Summed as integers: a float sum would depend on the map's iteration order.
+                let sum_of_squares: u128 =
+                    counts.values().map(|&count| (count as u128).pow(2)).sum();
+                1.0 - sum_of_squares as f64 / (total as f64).powi(2)
+            })
+            .collect();
+        (top_values, gini)
+    } else {
+        (
+            dataset
+                .headers
+                .iter()
+                .zip(&transforms)
+                .map(|(header, &transform)| (column_label(header, transform), Vec::new()))
+                .collect(),
+            vec![0.0; num_cols],
+        )
+    };
+
+    let mut correlations = vec![vec![0.0; num_cols]; num_cols];
+    for (i, row) in correlations.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+    if clock.start("correlations") {
+        // Only columns with a mean and a non-zero spread can correlate with anything;
+        // parse those once and fill the rest of the matrix with 0.0.
+        let numeric_cols: Vec<usize> = (0..num_cols)
+            .filter(|&i| {
+                stats[i].mean.is_some()
+                    && stats[i].std_dev.is_some_and(|s| s > 0.0)
+                    && !sequence_columns.contains(&i)
+                    && !excluded.contains(&i)
+            })
+            .collect();
+        // Kept per row so each pair is computed over the rows both columns share.
+        let numeric_values: Vec<Vec<Option<f64>>> = numeric_cols
+            .par_iter()
+            .map(|&i| stats::row_values(&dataset.rows, i))
+            .collect();
+        let dense: Vec<Vec<f64>> = (0..numeric_cols.len())
+            .into_par_iter()
+            .map(|a| {
+                (a + 1..numeric_cols.len())
+                    .map(|b| {
+                        let (i, j) = (numeric_cols[a], numeric_cols[b]);
+                        let (col_i, col_j) =
+                            stats::paired_values(&numeric_values[a], &numeric_values[b]);
+                        if options.correlation_method == CorrelationMethod::Bicor {
+                            if col_i.len() < 2 {
+                                return 0.0;
+                            }
+                            return stats::bicor_terms(&col_i)
+                                .iter()
+                                .zip(&stats::bicor_terms(&col_j))
+                                .map(|(x, y)| x * y)
+                                .sum();
+                        }
+                        if col_i.is_empty() {
+                            return 0.0;
+                        }
+                        let (mean_i, std_i) = (
+                            stats[i].mean.unwrap_or(0.0),
+                            stats[i].std_dev.unwrap_or(1.0),
+                        );
+                        let (mean_j, std_j) = (
+                            stats[j].mean.unwrap_or(0.0),
+                            stats[j].std_dev.unwrap_or(1.0),
+                        );
+                        let cov = col_i
+                            .iter()
+                            .zip(col_j.iter())
+                            .map(|(&x, &y)| covariance_term(x, y, mean_i, mean_j))
+                            .sum::<f64>()
+                            / col_i.len() as f64;
+                        cov / (std_i * std_j)
+                    })
+                    .collect()
+            })
+            .collect();
+        for (a, row) in dense.iter().enumerate() {
+            for (offset, &r) in row.iter().enumerate() {
+                let (i, j) = (numeric_cols[a], numeric_cols[a + 1 + offset]);
+                correlations[i][j] = r;
+                correlations[j][i] = r;
+            }
+        }
+    }
+
+    let (feature_importance, anomalies, anomaly_explanations) = if clock
+        .start("feature importance and anomalies")
+    {
+        let target_idx = dataset
+            .headers
+            .iter()
+            .position(|h| h.to_lowercase().contains("target"))
+            .unwrap_or(0);
+        let target_rows = stats::row_values(&dataset.rows, target_idx);
+        let feature_importance: Vec<(usize, f64)> = (0..num_cols)
+            .into_par_iter()
+            .filter_map(|col_idx| {
+                let (col_values, target_values) =
+                    stats::paired_values(&stats::row_values(&dataset.rows, col_idx), &target_rows);
+                if col_idx != target_idx
+                    && !sequence_columns.contains(&col_idx)
+                    && !col_values.is_empty()
+                {
+                    let corr = if let (Some(mean_i), Some(std_i)) =
+                        (stats[col_idx].mean, stats[col_idx].std_dev)
+                    {
+                        if let (Some(mean_t), Some(std_t)) =
+                            (stats[target_idx].mean, stats[target_idx].std_dev)
+                        {
+                            let cov = col_values
+                                .iter()
+                                .zip(target_values.iter())
+                                .map(|(&x, &y)| (x - mean_i) * (y - mean_t))
+                                .sum::<f64>()
+                                / col_values.len() as f64;
+                            if std_i > 0.0 && std_t > 0.0 {
+                                cov / (std_i * std_t)
+                            } else {
+                                0.0
+                            }
+                        } else {
+                            0.0
+                        }
+                    } else {
+                        0.0
+                    };
+                    Some((col_idx, corr.abs()))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let mut feature_importance = feature_importance;
+        feature_importance
+            .sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        let anomaly_method = options.anomaly_method;
+        let scored_anomalies: Vec<(usize, f64, usize, f64)> = (0..num_cols)
+            .into_par_iter()
+            .flat_map(|col_idx| {
+                let col_values: Vec<(f64, usize)> = dataset
+                    .rows
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(idx, row)| parse_finite(&row[col_idx]).map(|v| (v, idx)))
+                    .collect();
+                let values: Vec<f64> = col_values.iter().map(|&(val, _)| val).collect();
+                let col_stats = &stats[col_idx];
+                col_values
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(pos, &(val, idx))| {
+                        let score = match options.anomaly_window {
+                            // Rows are taken to be in time order: score each value against
+                            // its neighbours on either side, excluding itself.
+                            Some(window) => {
+                                let half = (window / 2).max(1);
+                                let neighbours: Vec<f64> = values[pos.saturating_sub(half)..pos]
+                                    .iter()
+                                    .chain(&values[pos + 1..(pos + 1 + half).min(values.len())])
+                                    .copied()
+                                    .collect();
+                                if neighbours.len() < 2 {
+                                    return None;
+                                }
+                                anomaly_method.score(val, &stats::window_stats(&neighbours))
+                            }
+                            None => anomaly_method.score(val, col_stats),
+                        }?;
+                        (score > anomaly_method.threshold()).then_some((col_idx, val, idx, score))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let anomalies: Vec<(usize, f64, usize)> = scored_anomalies
+            .iter()
+            .map(|&(col_idx, val, idx, _)| (col_idx, val, idx))
+            .collect();
+
+        let anomaly_explanations: Vec<String> = if options.explain {
+            let window = options
+                .anomaly_window
+                .map(|window| format!(" (rolling window {})", window))
+                .unwrap_or_default();
+            scored_anomalies
+                .iter()
+                .map(|&(_, _, _, score)| {
+                    format!(
+                        "{} {:.2} > {:.1}{}",
+                        anomaly_method.label(),
+                        score,
+                        anomaly_method.threshold(),
+                        window
+                    )
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        (feature_importance, anomalies, anomaly_explanations)
+    } else {
+        (Vec::new(), Vec::new(), Vec::new())
+    };
+
+    let description = Description {
+        stats,
+        missing,
+        duplicates,
+        outliers,
+        types,
+        dependency_scores,
+        drift_scores,
+        cardinality,
+        distributions,
+        consistency_issues,
+        temporal_patterns,
+        transform_suggestions,
+        noise_scores,
+        redundancy_pairs,
+        total_rows,
+        missing_pct,
+        unique_pct,
+        top_values,
+        correlations,
+        feature_importance,
+        anomalies,
+        co_missingness,
+        anomaly_explanations,
+        cardinality_estimated: options.approx_cardinality,
+        empty_columns,
+        positive_rates,
+        outlier_counts,
+        stability,
+        sequence_columns,
+        type_confidence,
+        duplicate_columns,
+        drift_series,
+        retained_columns,
+        source_columns,
+        integer_widths,
+        transforms,
+        missing_codes,
+        recommended_dtype,
+        gini,
+        whitespace_counts,
+        shape_thresholds: options.shape_thresholds,
+        boolean_encoding: options.boolean_encoding.clone(),
+        non_finite_counts,
+        semantic_types,
+        min_rows: options.min_rows,
+        categorical_drift,
+        correlation_method: options.correlation_method,
+        duplicate_key: options.duplicate_key.clone(),
+        type_mismatches,
+        sentinels,
+        correlation_ratios,
+        linear_combinations,
+        provenance: Provenance {
+            presto_version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at: (!options.omit_timestamp)
+                .then(|| chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+            source: None,
+            rows: total_rows,
+            columns: source_columns,
+            options: options.settings(),
+        },
+        cardinality_ratio,
+        parse_rate,
+        case_variants,
+        column_hashes,
+        dataset_hash,
+        outlier_bounds,
+        anomaly_bounds,
+        list_stats,
+        skipped_stages: clock.skipped,
+        granularity,
+        normality,
+    };
+
+    Ok(description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_empty() {
+        let dataset = Dataset::new(vec![], vec![]);
+        assert!(matches!(describe(&dataset), Err(PrestoError::EmptyDataset)));
+    }
+
+    #[test]
+    fn test_weighted_stats() {
+        let rows: Vec<Vec<String>> = [("1", "3"), ("5", "1"), ("9", "0")]
+            .iter()
+            .map(|&(x, w)| vec![x.to_string(), w.to_string()])
+            .collect();
+        let weighted = stats::compute_weighted_stats(&rows, 0, 1).unwrap();
+        assert_eq!(weighted.mean, Some(2.0));
+        assert_eq!(weighted.median, Some(1.0));
+        assert_eq!(weighted.max, Some(5.0));
+    }
+
+    #[test]
+    fn test_modified_zscore() {
+        let rows: Vec<Vec<String>> = ["1", "2", "3", "4", "100"]
+            .iter()
+            .map(|v| vec![v.to_string()])
+            .collect();
+        let col_stats = stats::compute_stats(&rows, 0).unwrap();
+        assert_eq!(col_stats.mad, Some(1.0));
+        let method = AnomalyMethod::ModifiedZScore { threshold: 3.5 };
+        assert!(method.score(100.0, &col_stats).unwrap() > method.threshold());
+        assert!(method.score(4.0, &col_stats).unwrap() < method.threshold());
+    }
+
+    #[test]
+    fn test_validate_against_schema() {
+        let dataset = Dataset::new(
+            vec!["id".into(), "name".into()],
+            vec![vec!["1".into(), "a".into()], vec!["x".into(), "".into()]],
+        );
+        let schema = ExpectedSchema {
+            columns: vec![
+                ExpectedColumn {
+                    name: "id".into(),
+                    column_type: Some(TypeInference::Integer),
+                    required: false,
+                },
+                ExpectedColumn {
+                    name: "name".into(),
+                    column_type: None,
+                    required: true,
+                },
+                ExpectedColumn {
+                    name: "age".into(),
+                    column_type: None,
+                    required: false,
+                },
+            ],
+        };
+        let violations = validate_against_schema(&dataset, &schema);
+        assert_eq!(violations.len(), 3);
+        assert!(matches!(
+            violations[0],
+            SchemaViolation::TypeMismatch { .. }
+        ));
+        assert!(matches!(
+            violations[1],
+            SchemaViolation::UnexpectedNulls { count: 1, .. }
+        ));
+        assert!(matches!(
+            violations[2],
+            SchemaViolation::MissingColumn { .. }
+        ));
+    }
+
+    #[test]
+    fn test_schema_categorical_and_date_types() {
+        let dataset = Dataset::new(
+            vec!["city".into(), "joined".into(), "count".into()],
+            vec![
+                vec!["Oslo".into(), "2024-01-05".into(), "1".into()],
+                vec!["Rome".into(), "NA".into(), "2".into()],
+                vec!["Oslo".into(), "2024-02-01 08:30:00".into(), "3".into()],
+            ],
+        );
+        let column = |name: &str, column_type| ExpectedColumn {
+            name: name.into(),
+            column_type: Some(column_type),
+            required: false,
+        };
+        let schema = ExpectedSchema {
+            columns: vec![
+                column("city", TypeInference::Categorical),
+                column("joined", TypeInference::Date),
+                column("count", TypeInference::Integer),
+            ],
+        };
+        assert!(validate_against_schema(&dataset, &schema).is_empty());
+
+        // Text that is not a date, and numbers, are not dates or categories.
+        let schema = ExpectedSchema {
+            columns: vec![
+                column("city", TypeInference::Date),
+                column("joined", TypeInference::Date),
+                column("count", TypeInference::Categorical),
+            ],
+        };
+        let violations = validate_against_schema(&dataset, &schema);
+        assert_eq!(violations.len(), 2);
+        assert!(matches!(
+            &violations[0],
+            SchemaViolation::TypeMismatch { column, .. } if column == "city"
+        ));
+        assert!(matches!(
+            &violations[1],
+            SchemaViolation::TypeMismatch { column, .. } if column == "count"
+        ));
+    }
+
+    #[test]
+    fn test_winsorize() {
+        let rows: Vec<Vec<String>> = (1..=100)
+            .map(|v| vec![v.to_string(), "x".to_string()])
+            .chain(std::iter::once(vec!["10000".to_string(), "y".to_string()]))
+            .collect();
+        let dataset = Dataset::new(vec!["value".into(), "label".into()], rows);
+        let (winsorized, clipped) = winsorize(&dataset, 0.01, 0.99).unwrap();
+        assert_eq!(clipped, vec![2, 0]);
+        assert_eq!(winsorized.rows[100][0], "100");
+        assert_eq!(winsorized.rows[0][0], "2");
+        assert!(winsorize(&dataset, 0.9, 0.1).is_err());
+
+        // Infinities are not finite values, so they are neither bounds nor clipped.
+        let mut rows = dataset.rows.clone();
+        rows.push(vec!["inf".to_string(), "z".to_string()]);
+        let dataset = Dataset::new(vec!["value".into(), "label".into()], rows);
+        let (winsorized, clipped) = winsorize(&dataset, 0.01, 0.99).unwrap();
+        assert_eq!(clipped, vec![2, 0]);
+        assert_eq!(winsorized.rows[101][0], "inf");
+    }
+
+    #[test]
+    fn test_boolean_encodings() {
+        let rows: Vec<Vec<String>> = ["Yes", "no", "Y", "N", "", "on"]
+            .iter()
+            .map(|v| vec![v.to_string(), "1".to_string()])
+            .collect();
+        let encoding = BooleanEncoding::default();
+        assert_eq!(
+            types::infer_type_with(&rows, 0, &encoding),
+            TypeInference::Boolean
+        );
+        assert_eq!(types::boolean_positive_rate(&rows, 0, &encoding), Some(0.6));
+        let binary_as_int = BooleanEncoding {
+            binary_as_boolean: false,
+            ..BooleanEncoding::default()
+        };
+        assert_eq!(
+            types::infer_type_with(&rows, 1, &binary_as_int),
+            TypeInference::Integer
+        );
+    }
+
+    #[test]
+    fn test_hyperloglog_estimate() {
+        let mut sketch = stats::HyperLogLog::default();
+        for i in 0..50_000 {
+            sketch.insert(&i.to_string());
+            sketch.insert(&i.to_string());
+        }
+        let estimate = sketch.estimate() as f64;
+        assert!((estimate - 50_000.0).abs() / 50_000.0 < 0.05);
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("part-*.csv", "part-00001.csv"));
+        assert!(wildcard_match("part-0000?.csv", "part-00001.csv"));
+        assert!(!wildcard_match("part-*.csv", "part-00001.tsv"));
+        assert!(!wildcard_match("part-*.csv", "other.csv"));
+    }
+
+    #[test]
+    fn test_explained_suggestions() {
+        let rows: Vec<Vec<String>> = ["-50", "1", "2", "3", "400"]
+            .iter()
+            .map(|v| vec![v.to_string()])
+            .collect();
+        let stats = vec![stats::compute_stats(&rows, 0).unwrap()];
+        let plain =
+            suggest_transformations(&stats, &[None], false, &ShapeThresholds::default()).unwrap();
+        assert!(plain[0].contains("Shift positive") && !plain[0].contains(':'));
+        let explained =
+            suggest_transformations(&stats, &[None], true, &ShapeThresholds::default()).unwrap();
+        assert!(explained[0].contains("Shift positive: min -50.00 < 0.0"));
+        assert!(explained[0].contains("Normalize: range 450.00 > 100.0"));
+    }
+
+    #[test]
+    fn test_outliers_ranked_by_severity() {
+        let mut rows: Vec<Vec<String>> = (0..100).map(|i| vec![(i % 2).to_string()]).collect();
+        rows.push(vec!["20".into()]);
+        rows.push(vec!["40".into()]);
+        let stats = stats::compute_stats(&rows, 0).unwrap();
+        let outliers = detect_outliers(&rows, 0, &stats);
+        assert_eq!(
+            outliers.iter().map(|&(row, _)| row).collect::<Vec<_>>(),
+            vec![101, 100]
+        );
+        assert!(outliers[0].1 > outliers[1].1 && outliers[1].1 > 3.0);
+    }
+
+    #[test]
+    fn test_normalize_missing_matches_numeric_codes() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into()],
+            [
+                ["-1", "-1"],
+                ["-1.0", "-1.0"],
+                ["-1.5", "x"],
+                ["n/a", "n/a"],
+            ]
+            .iter()
+            .map(|row| row.map(String::from).to_vec())
+            .collect(),
+        );
+        let codes = HashMap::from([
+            ("a".to_string(), vec!["-1".to_string(), "n/a".to_string()]),
+            ("b".to_string(), vec!["-1.0".to_string()]),
+        ]);
+        let normalized = dataset.normalize_missing(&codes).unwrap();
+        let column = |idx: usize| -> Vec<&str> {
+            normalized
+                .rows
+                .iter()
+                .map(|row| row[idx].as_str())
+                .collect()
+        };
+        assert_eq!(column(0), vec!["NA", "NA", "-1.5", "NA"]);
+        assert_eq!(column(1), vec!["NA", "NA", "x", "n/a"]);
+        let unknown = HashMap::from([("c".to_string(), vec!["-1".to_string()])]);
+        assert!(dataset.normalize_missing(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_report_records_serialize_with_named_fields() {
+        let pair = ColumnPair {
+            column_a: "a".into(),
+            column_b: "b".into(),
+            score: 0.5,
+        };
+        assert_eq!(
+            serde_json::to_string(&pair).unwrap(),
+            r#"{"column_a":"a","column_b":"b","score":0.5}"#
+        );
+        let outlier = Outlier {
+            row: 3,
+            z_score: 4.0,
+        };
+        assert_eq!(
+            serde_json::to_string(&outlier).unwrap(),
+            r#"{"row":3,"z_score":4.0}"#
+        );
+        let mut anomaly = Anomaly {
+            column: "a".into(),
+            value: 9.0,
+            row: 7,
+            explanation: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&anomaly).unwrap(),
+            r#"{"column":"a","value":9.0,"row":7}"#
+        );
+        anomaly.explanation = Some("|z| 3.50 > 3.0".into());
+        assert!(
+            serde_json::to_string(&anomaly)
+                .unwrap()
+                .ends_with(r#""explanation":"|z| 3.50 > 3.0"}"#)
+        );
+    }
+
+    #[test]
+    fn test_stats_row_cells() {
+        let rows: Vec<Vec<String>> = (1..=5).map(|i| vec![i.to_string()]).collect();
+        let stats = stats::compute_stats(&rows, 0).unwrap();
+        let cells = tui::stats_row_cells("x", &stats, &[], 0, &ShapeThresholds::default());
+        assert_eq!(cells[0], "x");
+        for value in ["3.00", "1.00", "5.00"] {
+            assert!(
+                cells.iter().any(|cell| cell == value),
+                "{} in {:?}",
+                value,
+                cells
+            );
+        }
+        assert!(cells.iter().any(|cell| cell.ends_with("(Symmetric)")));
+    }
+
+    #[test]
+    fn test_status_time_left() {
+        use std::time::{Duration, Instant};
+        assert!(tui::status_time_left(Instant::now()) > Duration::from_secs(3));
+        let stale = Instant::now().checked_sub(Duration::from_secs(10)).unwrap();
+        assert_eq!(tui::status_time_left(stale), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_detect_empty_columns() {
+        assert_eq!(cleaning::detect_empty_columns(&[3, 0, 3, 2], 3), vec![0, 2]);
+        assert!(cleaning::detect_empty_columns(&[0, 1], 3).is_empty());
+    }
+
+    #[test]
+    fn test_summary_line() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into()],
+            [["1", "x"], ["2", "y"], ["2", "y"], ["1", ""]]
+                .iter()
+                .map(|row| row.map(String::from).to_vec())
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        assert_eq!(
+            description.summary_line(),
+            "rows=4 cols=2 missing=12.5% dup=25.0% drift_cols=1 outlier_rows=0"
+        );
+    }
+
+    #[test]
+    fn test_centered_rect() {
+        let area = ratatui::layout::Rect::new(0, 0, 100, 50);
+        assert_eq!(
+            tui::centered_rect(60, 60, area),
+            ratatui::layout::Rect::new(20, 10, 60, 30)
+        );
+    }
+
+    #[test]
+    fn test_outlier_counts() {
+        let mut rows: Vec<Vec<String>> = (0..30)
+            .map(|i| vec![(i % 2).to_string(), i.to_string()])
+            .collect();
+        rows.push(vec!["100".into(), "30".into()]);
+        let dataset = Dataset::new(vec!["a".into(), "b".into()], rows);
+        let description = describe(&dataset).unwrap();
+        assert_eq!(description.outlier_counts, vec![1, 0]);
+        let report = description.to_report();
+        assert_eq!(report.columns[0].outlier_count, 1);
+        assert_eq!(report.columns[0].outliers[0].row, 30);
+        let cells = tui::stats_row_cells(
+            "a",
+            &description.stats[0],
+            &description.distributions[0],
+            1,
+            &ShapeThresholds::default(),
+        );
+        assert_eq!(cells.last().map(String::as_str), Some("1"));
+    }
+
+    #[test]
+    fn test_fold_stability() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into()],
+            [["1", "x"], ["2", "y"], ["10", "x"], ["20", "y"]]
+                .iter()
+                .map(|row| row.map(String::from).to_vec())
+                .collect(),
+        );
+        let stability = compute_stability(&dataset, 2).unwrap();
+        let (mean_variance, std_dev_variance) = stability[0].unwrap();
+        assert!((mean_variance - 91.125).abs() < 1e-9);
+        assert!((std_dev_variance - 20.25).abs() < 1e-9);
+        assert_eq!(stability[1], None);
+        assert!(compute_stability(&dataset, 1).is_err());
+        assert!(compute_stability(&dataset, 5).is_err());
+    }
+
+    #[test]
+    fn test_displayed_line_count() {
+        let lines = vec!["x".repeat(25), String::new(), "y".repeat(10)];
+        assert_eq!(tui::displayed_line_count(&lines, 10, false), 3);
+        assert_eq!(tui::displayed_line_count(&lines, 10, true), 5);
+        assert_eq!(tui::displayed_line_count(&lines, 0, true), 3);
+    }
+
+    #[test]
+    fn test_box_plot() {
+        let rows: Vec<Vec<String>> = (1..=9).map(|i| vec![i.to_string()]).collect();
+        let stats = stats::compute_stats(&rows, 0).unwrap();
+        assert_eq!((stats.q1, stats.q3), (Some(3.0), Some(7.0)));
+        let (plot, labels) = tui::box_plot_lines(&stats, 17).unwrap();
+        assert_eq!(plot, "|---[===|===]---|");
+        assert_eq!(labels, "min 1.00  q1 3.00  median 5.00  q3 7.00  max 9.00");
+        let text: Vec<Vec<String>> = vec![vec!["a".into()]];
+        assert!(tui::box_plot_lines(&stats::compute_stats(&text, 0).unwrap(), 17).is_none());
+    }
+
+    #[test]
+    fn test_duplicate_columns() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into(), "c".into()],
+            [["1", "1", "1"], ["2", "2", "3"]]
+                .iter()
+                .map(|row| row.map(String::from).to_vec())
+                .collect(),
+        );
+        let hashes = cleaning::compute_column_hashes(&dataset);
+        let pairs = cleaning::detect_duplicate_columns(&dataset, &hashes).unwrap();
+        assert_eq!(pairs, vec![(0, 1)]);
+        // Force a collision: every column hashes the same, only a and b hold equal values.
+        let pairs = cleaning::detect_duplicate_columns(&dataset, &[7, 7, 7]).unwrap();
+        assert_eq!(pairs, vec![(0, 1)]);
+        let pairs = cleaning::detect_duplicate_columns(&dataset, &[7, 8, 7]).unwrap();
+        assert!(pairs.is_empty());
+    }
+
+    #[test]
+    fn test_drift_series() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into()],
+            [["1", "x"], ["2", "y"], ["3", "x"], ["4", "y"]]
+                .iter()
+                .map(|row| row.map(String::from).to_vec())
+                .collect(),
+        );
+        let stats: Vec<_> = (0..2)
+            .map(|col| stats::compute_stats(&dataset.rows, col).unwrap())
+            .collect();
+        let series = stats::compute_drift_series(&dataset, &stats, 2).unwrap();
+        assert_eq!(series[0].len(), 2);
+        assert!(series[0][0] < 0.0 && series[0][1] > 0.0);
+        assert!((series[0][0] + series[0][1]).abs() < 1e-9);
+        assert!(series[1].is_empty());
+    }
+
+    #[test]
+    fn test_max_cols_keeps_top_ranked_columns() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into(), "c".into()],
+            [
+                ["1", "", "x"],
+                ["2", "NA", ""],
+                ["3", "", "y"],
+                ["4", "5", "z"],
+            ]
+            .iter()
+            .map(|row| row.map(String::from).to_vec())
+            .collect(),
+        );
+        let options = DescribeOptions {
+            max_cols: Some(2),
+            limit_by: ColumnOrder::Missing,
+            ..DescribeOptions::default()
+        };
+        let description = describe_with_options(&dataset, &options).unwrap();
+        assert_eq!(description.retained_columns(), Some(&[1, 2][..]));
+        assert_eq!(description.source_columns(), 3);
+        assert_eq!(description.missing, vec![3, 1]);
+
+        let options = DescribeOptions {
+            max_cols: Some(3),
+            ..options
+        };
+        let description = describe_with_options(&dataset, &options).unwrap();
+        assert_eq!(description.retained_columns(), None);
+    }
+
+    #[test]
+    fn test_quality_score_in_report() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into()],
+            [["1", "x"], ["2", "y"], ["2", "y"], ["1", ""]]
+                .iter()
+                .map(|row| row.map(String::from).to_vec())
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        assert!((description.quality_score() - 87.5).abs() < 1e-9);
+        let report = description.to_report();
+        assert_eq!(report.schema_version, REPORT_SCHEMA_VERSION);
+        assert!((report.quality_score - 87.5).abs() < 1e-9);
+        assert!(report.violations.is_empty());
+    }
+
+    #[test]
+    fn test_transforms_drop_out_of_domain_values() {
+        assert_eq!(ValueTransform::Log.apply(0.0), None);
+        assert_eq!(ValueTransform::Log.apply(-1.0), None);
+        assert_eq!(ValueTransform::Sqrt.apply(-4.0), None);
+        assert_eq!(ValueTransform::Sqrt.apply(0.0), Some(0.0));
+        assert_eq!(ValueTransform::Abs.apply(-3.0), Some(3.0));
+
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into()],
+            [["1", "4"], ["0", "-4"], ["-2", "x"]]
+                .iter()
+                .map(|row| row.map(String::from).to_vec())
+                .collect(),
+        );
+        let transforms = HashMap::from([
+            ("a".to_string(), ValueTransform::Log),
+            ("b".to_string(), ValueTransform::Sqrt),
+        ]);
+        let transformed = dataset.apply_transforms(&transforms).unwrap();
+        let column = |idx: usize| -> Vec<&str> {
+            transformed
+                .rows
+                .iter()
+                .map(|row| row[idx].as_str())
+                .collect()
+        };
+        assert_eq!(column(0), vec!["0", "NA", "NA"]);
+        assert_eq!(column(1), vec!["2", "NA", "x"]);
+    }
+
+    #[test]
+    fn test_gini_impurity() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into()],
+            [["x", "k"], ["x", "k"], ["y", "k"], ["y", "k"]]
+                .iter()
+                .map(|row| row.map(String::from).to_vec())
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        assert!((description.gini[0] - 0.5).abs() < 1e-9);
+        assert_eq!(description.gini[1], 0.0);
+        // Many distinct values: the result must not depend on hash iteration order.
+        let wide = Dataset::new(
+            vec!["v".into()],
+            (0..200).map(|i| vec![format!("v{}", i % 37)]).collect(),
+        );
+        let first = describe(&wide).unwrap().gini[0];
+        for _ in 0..5 {
+            assert_eq!(describe(&wide).unwrap().gini[0], first);
+        }
+    }
+
+    #[test]
+    fn test_report_diff() {
+        let report = |headers: [&str; 2], rows: [[&str; 2]; 3]| {
+            let dataset = Dataset::new(
+                headers.iter().map(|h| h.to_string()).collect(),
+                rows.iter()
+                    .map(|row| row.map(String::from).to_vec())
+                    .collect(),
+            );
+            let json = serde_json::to_string(&describe(&dataset).unwrap().to_report()).unwrap();
+            serde_json::from_str::<Report>(&json).unwrap()
+        };
+        let old = report(["a", "b"], [["1", "x"], ["2", "y"], ["3", "x"]]);
+        let new = report(["a", "c"], [["1", "p"], ["2", "q"], ["4", "p"]]);
+        let lines = old.diff(&new);
+        assert_eq!(lines[0], "rows: 3 -> 3");
+        assert!(lines.contains(&"removed column `b`".to_string()));
+        assert!(lines.contains(&"added column `c`".to_string()));
+        assert!(
+            lines
+                .iter()
+                .any(|l| l.starts_with("`a` mean: 2.000 -> 2.333"))
+        );
+        assert!(old.diff(&old).iter().all(|l| !l.starts_with('`')));
+    }
+
+    #[test]
+    fn test_guess_delimiter() {
+        let single = |header: &str, rows: &[&str]| {
+            Dataset::new(
+                vec![header.to_string()],
+                rows.iter().map(|row| vec![row.to_string()]).collect(),
+            )
+        };
+        assert_eq!(
+            single("a;b;c", &["1;2;3", "4;5;6"]).guess_delimiter(),
+            Some(';')
+        );
+        assert_eq!(single("a\tb", &["1\t2"]).guess_delimiter(), Some('\t'));
+        assert_eq!(single("a;b", &["1;2;3"]).guess_delimiter(), None);
+        assert_eq!(single("a", &["1", "2"]).guess_delimiter(), None);
+        let wide = Dataset::new(
+            vec!["a;b".into(), "c".into()],
+            vec![vec!["1;2".into(), "3".into()]],
+        );
+        assert_eq!(wide.guess_delimiter(), None);
+    }
+
+    #[test]
+    fn test_correlation_edges_include_threshold() {
+        let dataset = Dataset::new(
+            vec!["x".into(), "y".into(), "z".into()],
+            (0..12)
+                .map(|i| {
+                    let x = (i * 5) % 12;
+                    vec![
+                        x.to_string(),
+                        (-3 * x).to_string(),
+                        ((i * 5) % 7).to_string(),
+                    ]
+                })
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        let r = description.correlations[0][1];
+        assert!(r < 0.0);
+        let edges = description.correlation_edges(r.abs());
+        assert!(
+            edges
+                .iter()
+                .any(|e| (e.column_a.as_str(), e.column_b.as_str(), e.score) == ("x", "y", r))
+        );
+        let above = description.correlation_edges(r.abs().next_up());
+        assert!(
+            above
+                .iter()
+                .all(|e| (e.column_a.as_str(), e.column_b.as_str()) != ("x", "y"))
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into()],
+            vec![vec!["1".into(), "2".into()], vec!["3".into(), "4".into()]],
+        );
+        assert_eq!(dataset.validate().unwrap(), (2, 2));
+        let ragged = Dataset::new(
+            vec!["a".into(), "b".into()],
+            vec![vec!["1".into(), "2".into()], vec!["3".into()]],
+        );
+        assert!(matches!(
+            ragged.validate(),
+            Err(PrestoError::SchemaMismatch(msg)) if msg == "row 2 has 1 fields, expected 2"
+        ));
+        let empty = Dataset::new(vec!["a".into()], Vec::new());
+        assert!(matches!(empty.validate(), Err(PrestoError::EmptyDataset)));
+    }
+
+    #[test]
+    fn test_legend_lines_match_thresholds() {
+        let legend = tui::legend_lines(&ShapeThresholds::default(), 2.5);
+        for line in [
+            "  Highly +ve skewed: skew > 1.0 (long right tail)",
+            "  Symmetric: -0.5 <= skew <= 0.5",
+            "  Leptokurtic: kurtosis > 0.0 (heavy tails, sharp peak)",
+            "  Platykurtic: kurtosis < 0.0 (light tails, flat peak)",
+            "Outliers: values more than 2.5 standard deviations from the mean (+/- to adjust).",
+        ] {
+            assert!(
+                legend.iter().any(|l| l == line),
+                "missing legend line {line:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_clamp_selection_after_reload() {
+        let mut state = ratatui::widgets::TableState::default();
+        state.select(Some(5));
+        tui::clamp_selection(&mut state, 3);
+        assert_eq!(state.selected(), Some(2));
+        tui::clamp_selection(&mut state, 4);
+        assert_eq!(state.selected(), Some(2));
+        tui::clamp_selection(&mut state, 0);
+        assert_eq!(state.selected(), None);
+    }
+
+    #[test]
+    fn test_fit_widths() {
+        assert_eq!(tui::fit_widths(&[15, 10], 8, 100), vec![15, 10]);
+        assert_eq!(tui::fit_widths(&[20, 10], 5, 22), vec![13, 7]);
+        assert_eq!(tui::fit_widths(&[15, 10, 10], 8, 20), vec![8, 8, 8]);
+        assert_eq!(tui::visible_end(&[10, 10, 10], 0, 21), 2);
+        assert_eq!(tui::visible_end(&[10, 10, 10], 0, 32), 3);
+        assert_eq!(tui::visible_end(&[10, 10, 10], 1, 5), 2);
+    }
+
+    #[test]
+    fn test_command_palette_matching() {
+        assert_eq!(tui::fuzzy_score("box", "box plots"), Some(0));
+        assert_eq!(tui::fuzzy_score("plots", "box plots"), Some(1));
+        assert_eq!(tui::fuzzy_score("bp", "box plots"), Some(5));
+        assert_eq!(tui::fuzzy_score("pb", "box plots"), None);
+        let name = |query: &str| tui::best_command(query).map(|command| command.name);
+        assert_eq!(name("plots"), Some("plots"));
+        assert_eq!(name(" CORR "), Some("correlations"));
+        assert_eq!(name("crow"), Some("copy row"));
+        assert_eq!(name("report"), Some("export report"));
+        assert_eq!(name("zzz"), None);
+        assert_eq!(name("  "), None);
+    }
+
+    #[test]
+    fn test_preview_bounds() {
+        let options = TuiOptions::default();
+        assert_eq!(tui::preview_bounds(25, &options), (10, 15));
+        assert_eq!(tui::preview_bounds(12, &options), (10, 10));
+        assert_eq!(tui::preview_bounds(5, &options), (5, 5));
+        let tail_only = TuiOptions {
+            head_rows: 0,
+            tail_rows: 3,
+            ..TuiOptions::default()
+        };
+        assert_eq!(tui::preview_bounds(5, &tail_only), (0, 2));
+    }
+
+    #[test]
+    fn test_correlations_skip_non_numeric_columns() {
+        let dataset = Dataset::new(
+            vec!["x".into(), "y".into(), "c".into(), "t".into()],
+            (1..=6)
+                .map(|i| {
+                    let x = (i * 5) % 7;
+                    vec![
+                        x.to_string(),
+                        (2 * x).to_string(),
+                        "5".into(),
+                        format!("v{i}"),
+                    ]
+                })
+                .collect(),
+        );
+        let corr = describe(&dataset).unwrap().correlations;
+        for (i, row) in corr.iter().enumerate() {
+            assert_eq!(row[i], 1.0);
+            for (j, &r) in row.iter().enumerate() {
+                assert_eq!(r, corr[j][i]);
+            }
+        }
+        assert!(corr[0][1] > 0.8);
+        assert_eq!(corr[0][2], 0.0);
+        assert_eq!(corr[0][3], 0.0);
+        assert_eq!(corr[2][3], 0.0);
+    }
+
+    #[test]
+    fn test_histogram_lines() {
+        let headers = vec!["a".to_string(), "t".to_string()];
+        let distributions = vec![vec![(1.0, 2), (2.0, 1)], Vec::new()];
+        let lines: Vec<String> = tui::histogram_lines(&headers, &distributions, 2)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], "a:");
+        assert_eq!(lines[1].matches('█').count(), 1);
+        assert_eq!(lines[2].matches('█').count(), 2);
+        assert_eq!(lines[3], "   0 | 1.0 2.0 ");
+        assert_eq!(lines[4], "");
+        assert_eq!(lines[6], "  (No numeric data)");
+    }
+
+    #[test]
+    fn test_describe_with_progress_reports_stages() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into()],
+            (0..10)
+                .map(|i| vec![i.to_string(), format!("v{}", i % 3)])
+                .collect(),
+        );
+        let stages = std::sync::Mutex::new(Vec::new());
+        let description = describe_with_progress(&dataset, &DescribeOptions::default(), &|stage| {
+            stages.lock().unwrap().push(stage.to_string());
+        })
+        .unwrap();
+        assert_eq!(description.total_rows(), dataset.row_count());
+        let stages = stages.into_inner().unwrap();
+        assert_eq!(
+            stages.first().map(String::as_str),
+            Some("column statistics")
+        );
+        assert_eq!(
+            stages.last().map(String::as_str),
+            Some("feature importance and anomalies")
+        );
+        assert_eq!(stages.len(), 8);
+    }
+
+    #[test]
+    #[cfg(feature = "sql")]
+    fn test_sql_column_types() {
+        use tokio_postgres::types::Type;
+        assert_eq!(sql::column_type(&Type::INT8), Some(TypeInference::Integer));
+        assert_eq!(sql::column_type(&Type::NUMERIC), Some(TypeInference::Float));
+        assert_eq!(
+            sql::column_type(&Type::TIMESTAMP),
+            Some(TypeInference::Date)
+        );
+        assert_eq!(
+            sql::column_type(&Type::VARCHAR),
+            Some(TypeInference::String)
+        );
+        assert_eq!(sql::column_type(&Type::JSONB), None);
+    }
+
+    #[test]
+    fn test_page_step_clamps() {
+        assert_eq!(tui::step(3, 10, 20), 13);
+        assert_eq!(tui::step(15, 10, 20), 20);
+        assert_eq!(tui::step(3, -10, 20), 0);
+        assert_eq!(tui::step(7, isize::MIN / 2, 20), 0);
+        assert_eq!(tui::step(7, isize::MAX / 2, 20), 20);
+    }
+
+    #[test]
+    fn test_scroll_into_view() {
+        let widths = [10, 10, 10, 10];
+        assert_eq!(tui::scroll_into_view(0, 3, &widths, 21), 2);
+        assert_eq!(tui::scroll_into_view(2, 1, &widths, 21), 1);
+        assert_eq!(tui::scroll_into_view(1, 2, &widths, 21), 1);
+        assert_eq!(tui::scroll_into_view(0, 3, &widths, 5), 3);
+    }
+
+    #[test]
+    fn test_distribution_sparkline() {
+        assert_eq!(
+            tui::distribution_sparkline(&[(0.0, 0), (1.0, 2), (2.0, 4), (3.0, 7)]),
+            "▁▃▅█"
+        );
+        assert_eq!(tui::distribution_sparkline(&[]), "N/A");
+        assert_eq!(tui::distribution_sparkline(&[(0.0, 0)]), "N/A");
+    }
+
+    #[test]
+    fn test_clip_marks_cut_values() {
+        assert_eq!(tui::clip("short", 5), "short");
+        assert_eq!(tui::clip("longer", 5), "long…");
+        assert_eq!(tui::clip("héllo wörld", 6), "héllo…");
+        assert_eq!(tui::clip("abc", 1), "…");
+    }
+
+    #[test]
+    fn test_z_scores_above_threshold() {
+        let mut rows: Vec<Vec<String>> = (0..30)
+            .map(|i| vec![(i % 2).to_string(), format!("v{i}")])
+            .collect();
+        rows.push(vec!["100".into(), "v30".into()]);
+        let dataset = Dataset::new(vec!["a".into(), "b".into()], rows);
+        let description = describe(&dataset).unwrap();
+        let scores = description.z_scores_above(&dataset, 0.5);
+        assert_eq!(scores[0][0].0, 30);
+        assert!(scores[0].windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert!(scores[0].iter().all(|&(_, z)| z > 0.5));
+        assert_eq!(scores[0].partition_point(|&(_, z)| z > 3.0), 1);
+        assert!(scores[1].is_empty());
+        assert!(description.z_scores_above(&dataset, 100.0)[0].is_empty());
+    }
+
+    #[test]
+    fn test_columns_by_missing() {
+        assert_eq!(
+            tui::columns_by_missing(&[0, 5, 2, 5, 0]),
+            vec![1, 3, 2, 0, 4]
+        );
+        assert!(tui::columns_by_missing(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_co_missingness() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into(), "c".into()],
+            vec![
+                vec!["".into(), "NA".into(), "1".into()],
+                vec!["".into(), "".into(), "".into()],
+                vec!["1".into(), "".into(), "2".into()],
+            ],
+        );
+        let matrix = compute_co_missingness(&dataset).unwrap();
+        assert_eq!(matrix[0][1], 2.0 / 3.0);
+        assert_eq!(matrix[0][2], 0.5);
+        assert_eq!(matrix[1][0], matrix[0][1]);
+    }
+
+    #[test]
+    fn test_sequence_columns() {
+        let dataset = Dataset::new(
+            vec!["id".into(), "step".into(), "value".into()],
+            vec![
+                vec!["1".into(), "100".into(), "3.5".into()],
+                vec!["2".into(), "200".into(), "4.0".into()],
+                vec!["3".into(), "300".into(), "7.2".into()],
+                vec!["4".into(), "400".into(), "9.9".into()],
+            ],
+        );
+        assert_eq!(
+            stats::detect_sequence_columns(&dataset).unwrap(),
+            vec![0, 1]
+        );
+        let description = describe(&dataset).unwrap();
+        assert_eq!(description.correlations[0][2], 0.0);
+        assert!(
+            description
+                .feature_importance
+                .iter()
+                .all(|&(col, _)| col == 2)
+        );
+    }
+
+    #[test]
+    fn test_column_order() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into(), "c".into()],
+            vec![
+                vec!["1".into(), "".into(), "x".into()],
+                vec!["2".into(), "".into(), "".into()],
+                vec!["3".into(), "5".into(), "y".into()],
+            ],
+        );
+        let description = describe(&dataset).unwrap();
+        let order = description.column_order(ColumnOrder::Missing);
+        assert_eq!(order, vec![1, 2, 0]);
+        let reordered = description.reorder_columns(&order);
+        assert_eq!(reordered.missing, vec![2, 1, 0]);
+        assert_eq!(reordered.correlations[2][2], 1.0);
+        assert_eq!(dataset.reorder_columns(&order).headers, vec!["b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_type_sampling() {
+        let rows: Vec<Vec<String>> = ["1", "2", "3", "x"]
+            .iter()
+            .map(|v| vec![v.to_string()])
+            .collect();
+        let encoding = BooleanEncoding::default();
+        let head = types::sample_rows(&rows, TypeSampling::Head(3), None);
+        assert_eq!(head.len(), 3);
+        assert_eq!(
+            types::infer_type_with(&head, 0, &encoding),
+            TypeInference::Integer
+        );
+        let full = types::infer_type_with(&rows, 0, &encoding);
+        assert_eq!(full, TypeInference::String);
+        assert_eq!(types::type_confidence(&rows, 0, &full, &encoding), 0.25);
+        assert_eq!(
+            types::sample_rows(&rows, TypeSampling::Random(2), None).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_integer_width() {
+        let rows: Vec<Vec<String>> = ["1", "3000000000", "-5"]
+            .iter()
+            .map(|v| vec![v.to_string()])
+            .collect();
+        assert_eq!(types::integer_width(&rows, 0), Some(IntegerWidth::I64));
+        let huge = vec![vec!["170141183460469231731687303715884105727".to_string()]];
+        assert_eq!(types::infer_type(&huge, 0), TypeInference::Integer);
+        assert_eq!(types::integer_width(&huge, 0), Some(IntegerWidth::I128));
+        assert_eq!(types::integer_width(&[vec!["1.5".to_string()]], 0), None);
+    }
+
+    #[test]
+    fn test_from_fixed_width() {
+        let path = std::env::temp_dir().join("presto_fixed_width_test.txt");
+        std::fs::write(&path, "id  name   age\n1   Alice  30\n22  Bob\n").unwrap();
+        let dataset = Dataset::from_fixed_width(path.to_str().unwrap(), &[4, 7, 3], None).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(dataset.headers, vec!["id", "name", "age"]);
+        assert_eq!(dataset.rows[0], vec!["1", "Alice", "30"]);
+        assert_eq!(dataset.rows[1], vec!["22", "Bob", ""]);
+    }
+
+    #[test]
+    fn test_cardinality_excludes_missing() {
+        let dataset = Dataset::new(
+            vec!["grade".into()],
+            vec![
+                vec!["A".into()],
+                vec!["".into()],
+                vec!["B".into()],
+                vec!["NA".into()],
+            ],
+        );
+        assert_eq!(stats::compute_cardinality(&dataset).unwrap(), vec![2]);
+        assert_eq!(stats::estimate_cardinality(&dataset).unwrap(), vec![2]);
+        let report = describe(&dataset).unwrap().to_report();
+        assert_eq!(report.columns[0].cardinality_including_missing, 3);
+    }
+
+    #[test]
+    fn test_shape_thresholds() {
+        let dataset = Dataset::new(
+            vec!["x".into()],
+            ["1", "1", "1", "2", "2", "3", "50"]
+                .iter()
+                .map(|v| vec![v.to_string()])
+                .collect(),
+        );
+        let skew = describe(&dataset).unwrap().stats[0].skewness.unwrap();
+        let options = DescribeOptions {
+            shape_thresholds: ShapeThresholds {
+                high_skew: skew + 1.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let description = describe_with_options(&dataset, &options).unwrap();
+        let thresholds = description.shape_thresholds();
+        assert_eq!(thresholds.skew_label(skew), "Mod. +ve skewed");
+        assert!(!description.transform_suggestions[0].contains("Log transform"));
+        assert!(describe(&dataset).unwrap().transform_suggestions[0].contains("Log transform"));
+    }
+
+    #[test]
+    fn test_non_finite_values() {
+        let dataset = Dataset::new(
+            vec!["x".into(), "y".into()],
+            vec![
+                vec!["1".into(), "2".into()],
+                vec!["inf".into(), "4".into()],
+                vec!["3".into(), "6".into()],
+                vec!["NaN".into(), "8".into()],
+                vec!["-inf".into(), "10".into()],
+            ],
+        );
+        let description = describe(&dataset).unwrap();
+        assert_eq!(description.non_finite_counts, vec![3, 0]);
+        assert_eq!(description.stats[0].mean, Some(2.0));
+        assert!(description.stats[0].std_dev.unwrap().is_finite());
+        assert!(
+            description
+                .correlations
+                .iter()
+                .flatten()
+                .all(|r| r.is_finite())
+        );
+    }
+
+    #[test]
+    fn test_semantic_types() {
+        let rows = [
+            [
+                "ann@example.com",
+                "https://example.com/a",
+                "10.0.0.1",
+                "555-123-4567",
+                "2024-01-15",
+            ],
+            [
+                "bob@mail.example.org",
+                "http://x.io",
+                "::1",
+                "+1 (555) 987-6543",
+                "2024-02-01",
+            ],
+        ];
+        let dataset = Dataset::new(
+            ["email", "site", "ip", "phone", "day"]
+                .map(String::from)
+                .to_vec(),
+            rows.iter().map(|r| r.map(String::from).to_vec()).collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        assert_eq!(
+            description.semantic_types,
+            vec![
+                Some(SemanticType::Email),
+                Some(SemanticType::Url),
+                Some(SemanticType::Ip),
+                Some(SemanticType::Phone),
+                None,
+            ]
+        );
+        assert!(description.to_report().columns[0].likely_pii);
+    }
+
+    #[test]
+    fn test_min_rows() {
+        let dataset = Dataset::new(
+            vec!["x".into()],
+            vec![vec!["1".into()], vec!["2".into()], vec!["4".into()]],
+        );
+        let options = DescribeOptions {
+            min_rows: Some(10),
+            ..Default::default()
+        };
+        let description = describe_with_options(&dataset, &options).unwrap();
+        assert_eq!(description.small_sample(), Some(10));
+        assert!(description.summary_line().ends_with("small_sample"));
+        assert_eq!(describe(&dataset).unwrap().small_sample(), None);
+    }
+
+    #[test]
+    fn test_add_computed_column() {
+        let mut dataset = Dataset::new(
+            vec!["a".into(), "b".into()],
+            vec![
+                vec!["6".into(), "3".into()],
+                vec!["1".into(), "0".into()],
+                vec!["NA".into(), "2".into()],
+            ],
+        );
+        dataset
+            .add_computed_column("ratio", "-a / b + sqrt(b * 3) ^ 2")
+            .unwrap();
+        assert_eq!(dataset.headers[2], "ratio");
+        let ratio: Vec<&str> = dataset.rows.iter().map(|r| r[2].as_str()).collect();
+        assert_eq!(ratio, vec!["7", "NA", "NA"]);
+        assert!(dataset.add_computed_column("c", "a +").is_err());
+        assert!(matches!(
+            dataset.add_computed_column("c", "log(z)"),
+            Err(PrestoError::ColumnNotFound(_))
+        ));
+        assert!(dataset.add_column("ratio", vec![String::new(); 3]).is_err());
+        assert_eq!(describe(&dataset).unwrap().stats.len(), 3);
+    }
+
+    #[test]
+    fn test_top_value_percent() {
+        let dataset = Dataset::new(
+            vec!["country".into()],
+            ["USA", "USA", "USA", "UK", "", "NA"]
+                .iter()
+                .map(|v| vec![v.to_string()])
+                .collect(),
+        );
+        let report = describe(&dataset).unwrap().to_report();
+        let top = &report.columns[0].top_values;
+        assert_eq!((top[0].value.as_str(), top[0].count), ("USA", 3));
+        assert_eq!(top[0].percent, 75.0);
+        assert_eq!(top[1].percent, 25.0);
+    }
+
+    #[test]
+    fn test_categorical_drift() {
+        let values = ["a", "a", "b", "b", "c", "c", "c", "c"];
+        let dataset = Dataset::new(
+            vec!["shifted".into(), "stable".into()],
+            values
+                .iter()
+                .zip(["x", "y", "x", "y", "x", "y", "x", "y"])
+                .map(|(a, b)| vec![a.to_string(), b.to_string()])
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        assert_eq!(description.categorical_drift[0], Some(1.0));
+        assert_eq!(description.categorical_drift[1], Some(0.0));
+        assert!(description.summary_line().contains("drift_cols=1"));
+
+        // Many categories: the divergence must not depend on hash iteration order.
+        let wide = Dataset::new(
+            vec!["v".into()],
+            (0..200)
+                .map(|i| vec![format!("v{}", if i < 100 { i % 23 } else { i % 31 })])
+                .collect(),
+        );
+        let first = describe(&wide).unwrap().categorical_drift[0];
+        assert!(first.is_some_and(|js| js > 0.0));
+        for _ in 0..5 {
+            assert_eq!(describe(&wide).unwrap().categorical_drift[0], first);
+        }
+    }
+
+    #[test]
+    fn test_output_formats() {
+        let dataset = Dataset::new(
+            vec!["x".into(), "label".into()],
+            (0..10)
+                .map(|i| vec![i.to_string(), format!("l{}", i % 3)])
+                .collect(),
+        );
+        let report = describe(&dataset).unwrap().to_report();
+        let yaml = report.render(OutputFormat::Yaml).unwrap();
+        assert!(yaml.contains("schema_version: 1"));
+        let toml = report.render(OutputFormat::Toml).unwrap();
+        assert!(toml.contains("schema_version = 1"));
+        let json = report.render(OutputFormat::Json).unwrap();
+        let parsed: Report = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.total_rows, 10);
+    }
+
+    #[test]
+    fn test_duplicate_headers_disambiguated() {
+        let headers = ["id", "name", "id", "id.1", "id"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(
+            disambiguate_headers(headers),
+            vec!["id", "name", "id.1", "id.1.1", "id.2"]
+        );
+    }
+
+    #[test]
+    fn test_bicor_resists_outliers() {
+        let dataset = Dataset::new(
+            vec!["x".into(), "y".into()],
+            (1..=20)
+                .map(|i| {
+                    let y = if i == 20 { -1000 } else { i * 2 + i % 3 };
+                    vec![(i * 10 + i % 4).to_string(), y.to_string()]
+                })
+                .collect(),
+        );
+        let pearson = describe(&dataset).unwrap().correlations[0][1];
+        let options = DescribeOptions {
+            correlation_method: CorrelationMethod::Bicor,
+            ..Default::default()
+        };
+        let bicor = describe_with_options(&dataset, &options)
+            .unwrap()
+            .correlations[0][1];
+        assert!(pearson < 0.0, "pearson {}", pearson);
+        assert!(bicor > 0.9, "bicor {}", bicor);
+    }
+
+    #[test]
+    fn test_bicor_pairs_values_by_row() {
+        // Each column misses a different row, so position-wise pairing would misalign.
+        let dataset = Dataset::new(
+            vec!["x".into(), "y".into()],
+            (1..=20)
+                .map(|i| {
+                    let x = if i == 3 {
+                        "NA".to_string()
+                    } else {
+                        (i * i).to_string()
+                    };
+                    let y = if i == 15 {
+                        "NA".to_string()
+                    } else {
+                        (i * i * 2).to_string()
+                    };
+                    vec![x, y]
+                })
+                .collect(),
+        );
+        let options = DescribeOptions {
+            correlation_method: CorrelationMethod::Bicor,
+            ..Default::default()
+        };
+        let bicor = describe_with_options(&dataset, &options)
+            .unwrap()
+            .correlations[0][1];
+        assert!((bicor - 1.0).abs() < 1e-9, "bicor {}", bicor);
+    }
+
+    #[test]
+    fn test_pearson_pairs_values_by_row() {
+        // y and target are exact multiples of x, but every column misses a different row.
+        let dataset = Dataset::new(
+            vec!["x".into(), "y".into(), "target".into()],
+            (1..=30)
+                .map(|i| {
+                    let x = (i * 7) % 10;
+                    let cell = |missing_at: usize, value: usize| {
+                        if i == missing_at {
+                            "NA".to_string()
+                        } else {
+                            value.to_string()
+                        }
+                    };
+                    vec![cell(3, x), cell(15, x * 2), cell(24, x * 3)]
+                })
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        assert!(
+            description.correlations[0][1] > 0.95,
+            "pearson {}",
+            description.correlations[0][1]
+        );
+        assert!(description.dependency_scores[0] > 0.95);
+        let (col, importance) = description.feature_importance[0];
+        assert!(col < 2 && importance > 0.95, "importance {}", importance);
+    }
+
+    #[test]
+    fn test_duplicates_by_key() {
+        let dataset = Dataset::new(
+            vec!["user_id".into(), "date".into(), "amount".into()],
+            [
+                ["1", "2024-01-01", "10"],
+                ["1", "2024-01-01", "12"],
+                ["1", "2024-01-02", "10"],
+                ["2", "2024-01-01", "10"],
+            ]
+            .iter()
+            .map(|row| row.map(String::from).to_vec())
+            .collect(),
+        );
+        assert_eq!(describe(&dataset).unwrap().duplicates, 0);
+        let options = DescribeOptions {
+            duplicate_key: vec!["user_id".into(), "date".into()],
+            ..Default::default()
+        };
+        let description = describe_with_options(&dataset, &options).unwrap();
+        assert_eq!(description.duplicates, 1);
+        assert_eq!(description.to_report().duplicate_key, ["user_id", "date"]);
+    }
+
+    #[test]
+    fn test_type_mismatch_samples() {
+        let dataset = Dataset::new(
+            vec!["score".into(), "name".into()],
+            ["1", "2", "N/A", "3", "unknown", "4", "N/A", "—", "5"]
+                .iter()
+                .zip(["a", "b", "c", "d", "e", "f", "g", "h", "i"])
+                .map(|(a, b)| vec![a.to_string(), b.to_string()])
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        assert_eq!(description.types[0], TypeInference::String);
+        assert_eq!(
+            description.type_mismatches[0],
+            Some((
+                TypeInference::Integer,
+                vec!["N/A".into(), "unknown".into(), "—".into()]
+            ))
+        );
+        assert_eq!(description.type_mismatches[1], None);
+    }
+
+    #[test]
+    fn test_exclude_from_analysis() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into(), "c".into()],
+            [3, 1, 4, 1, 5, 9, 2, 6]
+                .iter()
+                .map(|v| vec![v.to_string(), (v * 2).to_string(), v.to_string()])
+                .collect(),
+        );
+        let options = DescribeOptions {
+            analysis_exclusions: vec!["c".into()],
+            ..Default::default()
+        };
+        let description = describe_with_options(&dataset, &options).unwrap();
+        assert!(description.correlations[0][1] > 0.8);
+        assert_eq!(description.correlations[0][2], 0.0);
+        assert_eq!(description.dependency_scores[2], 0.0);
+        assert!(description.redundancy_pairs.is_empty());
+        assert!(description.stats[2].mean.is_some());
+
+        let options = DescribeOptions {
+            analysis_exclusions: vec!["b".into(), "c".into()],
+            ..Default::default()
+        };
+        let description = describe_with_options(&dataset, &options).unwrap();
+        assert_eq!(description.dependency_scores, vec![0.0; 3]);
+        let json = description.to_report().render(OutputFormat::Json).unwrap();
+        let report: Report = serde_json::from_str(&json).unwrap();
+        assert!(
+            report
+                .diff(&report)
+                .iter()
+                .all(|line| !line.contains("NaN"))
+        );
+
+        // A repeated name excludes its column once, so the divisor stays right.
+        let options = DescribeOptions {
+            analysis_exclusions: vec!["c".into(), "c".into(), "c".into()],
+            ..Default::default()
+        };
+        let repeated = describe_with_options(&dataset, &options).unwrap();
+        let once = describe_with_options(
+            &dataset,
+            &DescribeOptions {
+                analysis_exclusions: vec!["c".into()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(repeated.dependency_scores, once.dependency_scores);
+        assert!(repeated.dependency_scores[0] > 0.8);
+
+        // Names dropped by --max-cols no longer match a column and are ignored.
+        let options = DescribeOptions {
+            analysis_exclusions: vec!["c".into(), "c".into()],
+            max_cols: Some(2),
+            ..Default::default()
+        };
+        let limited = describe_with_options(&dataset, &options).unwrap();
+        assert!(limited.dependency_scores[0] > 0.8);
+
+        let options = DescribeOptions {
+            analysis_exclusions: vec!["zzz".into()],
+            ..Default::default()
+        };
+        assert!(describe_with_options(&dataset, &options).is_err());
+    }
+
+    #[test]
+    fn test_sentinel_detection() {
+        let dataset = Dataset::new(
+            vec!["income".into(), "age".into()],
+            (0..40)
+                .map(|i| {
+                    let income = if i % 10 == 0 { -9999 } else { 40_000 + i * 500 };
+                    vec![income.to_string(), (20 + i % 7).to_string()]
+                })
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        assert_eq!(description.sentinels[0], Some((-9999.0, 4)));
+        assert_eq!(description.sentinels[1], None);
+        let report = description.to_report();
+        assert_eq!(
+            report.columns[0].likely_sentinel.as_ref().unwrap().percent,
+            10.0
+        );
+    }
+
+    #[test]
+    fn test_columns_table() {
+        let dataset = Dataset::new(
+            vec!["id".into(), "note".into()],
+            vec![
+                vec!["1".into(), "a|b".into()],
+                vec!["2".into(), "a|b".into()],
+                vec!["3".into(), "".into()],
+            ],
+        );
+        let description = describe(&dataset).unwrap();
+        let csv = description.to_columns_table(TableFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("column,type,"));
+        assert!(lines[1].starts_with("id,Integer,"));
+        assert!(lines[1].contains(",2.0000,"));
+        let markdown = description.to_columns_table(TableFormat::Markdown);
+        assert_eq!(markdown.lines().count(), 4);
+        assert!(markdown.contains("a\\|b"));
+    }
+
+    #[test]
+    fn test_correlation_ratio() {
+        let dataset = Dataset::new(
+            vec!["dept".into(), "salary".into(), "noise".into()],
+            (0..30)
+                .map(|i| {
+                    let (dept, base) = match i % 3 {
+                        0 => ("eng", 100),
+                        1 => ("ops", 60),
+                        _ => ("sales", 80),
+                    };
+                    vec![
+                        dept.to_string(),
+                        (base + i % 5).to_string(),
+                        ((i * 7) % 10).to_string(),
+                    ]
+                })
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        let (cat, num, eta) = description.correlation_ratios[0];
+        assert_eq!((cat, num), (0, 1));
+        assert!(eta > 0.95);
+        assert!(
+            description
+                .correlation_ratios
+                .iter()
+                .all(|&(_, num, eta)| num != 2 || eta < 0.5)
+        );
+    }
+
+    #[test]
+    fn test_rolling_anomaly_window() {
+        // A steady upward trend with one local spike that stays inside the global range.
+        let dataset = Dataset::new(
+            vec!["level".into()],
+            (0..100)
+                .map(|i| {
+                    let level = if i == 20 { 600 } else { i * 10 + i % 3 };
+                    vec![level.to_string()]
+                })
+                .collect(),
+        );
+        let global = describe(&dataset).unwrap();
+        assert!(global.anomalies.iter().all(|&(_, _, row)| row != 20));
+        let options = DescribeOptions {
+            anomaly_window: Some(10),
+            ..Default::default()
+        };
+        let rolling = describe_with_options(&dataset, &options).unwrap();
+        assert_eq!(
+            rolling
+                .anomalies
+                .iter()
+                .map(|&(_, _, row)| row)
+                .collect::<Vec<_>>(),
+            vec![20]
+        );
+    }
+
+    #[test]
+    fn test_correlation_contributions() {
+        // Uncorrelated noise plus one leverage point far out on both axes.
+        let mut rows: Vec<Vec<String>> = (0..20)
+            .map(|i| vec![(i % 5).to_string(), ((i * 3) % 7).to_string()])
+            .collect();
+        rows.push(vec!["100".into(), "100".into()]);
+        let dataset = Dataset::new(vec!["x".into(), "y".into()], rows);
+        let description = describe(&dataset).unwrap();
+        let top = description.correlation_contributions(&dataset, 0, 1, 3);
+        assert_eq!(top.len(), 3);
+        assert_eq!((top[0].0, top[0].1, top[0].2), (20, 100.0, 100.0));
+        assert!(top[0].3 > 90.0);
+    }
+
+    #[test]
+    fn test_correlation_contributions_use_profiled_values() {
+        let rows: Vec<Vec<String>> = [
+            ("1", "1"),
+            ("10", "2"),
+            ("-999", "2.5"),
+            ("100", "3"),
+            ("1000", "4"),
+            ("100000", "5"),
+        ]
+        .iter()
+        .map(|&(x, y)| vec![x.to_string(), y.to_string()])
+        .collect();
+        let dataset = Dataset::new(vec!["x".into(), "y".into()], rows);
+        let options = DescribeOptions {
+            transforms: HashMap::from([("x".to_string(), ValueTransform::Log)]),
+            missing_codes: HashMap::from([("x".to_string(), vec!["-999".to_string()])]),
+            ..Default::default()
+        };
+        let description = describe_with_options(&dataset, &options).unwrap();
+        let top = description.correlation_contributions(&dataset, 0, 1, 10);
+        assert_eq!(top.len(), 5);
+        assert!(top.iter().all(|&(row, _, _, _)| row != 2));
+        let (_, x, _, _) = top.iter().find(|&&(row, _, _, _)| row == 5).unwrap();
+        assert!((x - 100000f64.ln()).abs() < 1e-9);
+        let total: f64 = top.iter().map(|&(_, _, _, pct)| pct).sum();
+        assert!((total - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_z_scores_skip_missing_codes() {
+        let mut rows: Vec<Vec<String>> = (0..30).map(|i| vec![(i % 5).to_string()]).collect();
+        rows.push(vec!["-999".into()]);
+        rows.push(vec!["   ".into()]);
+        rows.push(vec!["40".into()]);
+        let dataset = Dataset::new(vec!["x".into()], rows);
+        let options = DescribeOptions {
+            missing_codes: HashMap::from([("x".to_string(), vec!["-999.0".to_string()])]),
+            ..Default::default()
+        };
+        let description = describe_with_options(&dataset, &options).unwrap();
+        let scores = description.z_scores_above(&dataset, cleaning::OUTLIER_Z_THRESHOLD);
+        assert_eq!(
+            scores[0].iter().map(|&(row, _)| row).collect::<Vec<_>>(),
+            vec![32]
+        );
+        assert_eq!(scores[0].len(), description.outlier_counts[0]);
+    }
+
+    #[test]
+    fn test_retyped_stats_keep_transforms_and_missing_codes() {
+        let rows: Vec<Vec<String>> = ["1", "10", "-999", "100", "1000"]
+            .iter()
+            .map(|&x| vec![x.to_string()])
+            .collect();
+        let dataset = Dataset::new(vec!["x".into()], rows);
+        let options = DescribeOptions {
+            transforms: HashMap::from([("x".to_string(), ValueTransform::Log)]),
+            missing_codes: HashMap::from([("x".to_string(), vec!["-999".to_string()])]),
+            ..Default::default()
+        };
+        let description = describe_with_options(&dataset, &options).unwrap();
+        let retyped = description
+            .retyped_stats(&dataset, 0, &TypeInference::Float)
+            .unwrap();
+        let (expected, actual) = (description.stats[0].mean.unwrap(), retyped.mean.unwrap());
+        assert!((expected - actual).abs() < 1e-9);
+        assert!((actual - 1000f64.ln() / 2.0).abs() < 1e-9);
+
+        let flags = Dataset::new(
+            vec!["flag".into()],
+            ["si", "no", "si", "si"]
+                .iter()
+                .map(|&v| vec![v.to_string()])
+                .collect(),
+        );
+        let options = DescribeOptions {
+            boolean_encoding: BooleanEncoding {
+                pairs: vec![("si".to_string(), "no".to_string())],
+                binary_as_boolean: true,
+            },
+            ..Default::default()
+        };
+        let description = describe_with_options(&flags, &options).unwrap();
+        let retyped = description
+            .retyped_stats(&flags, 0, &TypeInference::Boolean)
+            .unwrap();
+        assert_eq!(retyped.mean, Some(0.75));
+    }
+
+    #[test]
+    fn test_dataset_introspection() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into()],
+            vec![vec!["1".into(), "x".into()], vec!["2".into(), "y".into()]],
+        );
+        assert_eq!(dataset.shape(), (2, 2));
+        assert_eq!(dataset.headers(), ["a", "b"]);
+        assert_eq!(dataset.column("b"), Some(vec!["x", "y"]));
+        assert_eq!(dataset.column("c"), None);
+        assert_eq!(
+            dataset.row(1),
+            Some(&["2".to_string(), "y".to_string()][..])
+        );
+        assert_eq!(dataset.row(2), None);
+    }
+
+    #[test]
+    fn test_linear_combination_detection() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into(), "total".into(), "other".into()],
+            (0..30)
+                .map(|i| {
+                    let (a, b) = ((i * 7) % 11, (i * 5) % 13);
+                    vec![
+                        a.to_string(),
+                        b.to_string(),
+                        (2 * a - b).to_string(),
+                        ((i * i) % 17).to_string(),
+                    ]
+                })
+                .collect(),
+        );
+        assert!(describe(&dataset).unwrap().linear_combinations.is_none());
+        let options = DescribeOptions {
+            linear_dependencies: true,
+            ..Default::default()
+        };
+        let description = describe_with_options(&dataset, &options).unwrap();
+        let found = description.linear_combinations.unwrap();
+        assert_eq!(found.len(), 1);
+        let (col, terms, r_squared) = &found[0];
+        assert_eq!(*col, 2);
+        assert!(*r_squared > 0.9999);
+        assert_eq!(terms.len(), 2);
+        assert!((terms[0].1 - 2.0).abs() < 1e-6 && terms[0].0 == 0);
+        assert!((terms[1].1 + 1.0).abs() < 1e-6 && terms[1].0 == 1);
+    }
+
+    #[test]
+    fn test_report_is_deterministic() {
+        let dataset = Dataset::new(
+            vec!["x".into(), "y".into(), "label".into()],
+            (0..30)
+                .map(|i| {
+                    vec![
+                        i.to_string(),
+                        (i * i % 17).to_string(),
+                        ["a", "b", "c"][i % 3].to_string(),
+                    ]
+                })
+                .collect(),
+        );
+        let render =
+            |description: &Description| description.to_report().render(OutputFormat::Json).unwrap();
+        let stamped = describe(&dataset).unwrap();
+        assert!(stamped.provenance().generated_at.is_some());
+        assert!(render(&stamped).contains("generated_at"));
+
+        let options = DescribeOptions {
+            omit_timestamp: true,
+            ..Default::default()
+        };
+        let first = describe_with_options(&dataset, &options).unwrap();
+        let second = describe_with_options(&dataset, &options).unwrap();
+        assert_eq!(first.provenance().generated_at, None);
+        assert_eq!(render(&first), render(&second));
+        assert!(!render(&first).contains("generated_at"));
+    }
+
+    #[test]
+    fn test_provenance() {
+        let dataset = Dataset::new(
+            vec!["x".into()],
+            (0..10).map(|i| vec![i.to_string()]).collect(),
+        );
+        let mut description = describe(&dataset).unwrap();
+        description.set_source("data.csv");
+        let provenance = description.provenance();
+        assert_eq!(provenance.presto_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(provenance.source.as_deref(), Some("data.csv"));
+        assert_eq!((provenance.rows, provenance.columns), (10, 1));
+        assert_eq!(provenance.options["correlation_method"], "pearson");
+
+        let options = DescribeOptions {
+            correlation_method: CorrelationMethod::Bicor,
+            ..Default::default()
+        };
+        let newer = describe_with_options(&dataset, &options).unwrap();
+        let diff = description.to_report().diff(&newer.to_report());
+        assert!(diff.contains(&"option `correlation_method`: pearson -> bicor".to_string()));
+    }
+
+    #[test]
+    fn test_thousands_separators() {
+        let path = std::env::temp_dir().join("presto_test_thousands.csv");
+        std::fs::write(
+            &path,
+            "amount,label\n\"1,234\",a\n1234,\"x,yz\"\n\"2,345.67\",c\n",
+        )
+        .unwrap();
+        let dataset = Dataset::from_csv(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(describe(&dataset).unwrap().types[0], TypeInference::String);
+
+        let (normalized, counts) = strip_thousands_separators(&dataset);
+        assert_eq!(counts, vec![2, 0]);
+        assert_eq!(normalized.column("label"), Some(vec!["a", "x,yz", "c"]));
+        let description = describe(&normalized).unwrap();
+        assert_eq!(description.types[0], TypeInference::Float);
+        assert_eq!(description.stats[0].max, Some(2345.67));
+        assert_eq!(description.stats[0].min, Some(1234.0));
+    }
+
+    #[test]
+    fn test_cardinality_ratio() {
+        let dataset = Dataset::new(
+            vec!["id".into(), "flag".into(), "code".into()],
+            (0..8)
+                .map(|i| {
+                    let code = if i < 4 {
+                        String::new()
+                    } else {
+                        (i % 2).to_string()
+                    };
+                    vec![i.to_string(), "y".into(), code]
+                })
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        assert_eq!(description.cardinality_ratio, vec![1.0, 0.125, 0.5]);
+        assert!(
+            description
+                .to_columns_table(TableFormat::Csv)
+                .starts_with("column,type,semantic_type,recommended_dtype,missing,missing_pct,cardinality,cardinality_ratio,")
+        );
+    }
+
+    #[cfg(feature = "snapshot")]
+    #[test]
+    fn test_snapshot_svg() {
+        use ratatui::buffer::Buffer;
+        use ratatui::layout::Rect;
+        use ratatui::style::{Color, Style};
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 2));
+        buffer.set_string(0, 0, "a<b", Style::default().fg(Color::Red));
+        let svg = snapshot::buffer_to_svg(&buffer);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r##"fill="#cd0000""##));
+        assert!(svg.contains(">a&lt;b</text>"));
+        assert_eq!(svg.matches("<text").count(), 1);
+    }
+
+    #[test]
+    fn test_parse_rate() {
+        let dataset = Dataset::new(
+            vec!["amount".into(), "name".into(), "when".into()],
+            (0..20)
+                .map(|i| {
+                    let amount = match i {
+                        0..=2 => "n/a".to_string(),
+                        3 => String::new(),
+                        _ => i.to_string(),
+                    };
+                    vec![
+                        amount,
+                        format!("user{}", i),
+                        format!("2024-01-{:02}", i + 1),
+                    ]
+                })
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        assert_eq!(description.parse_rate[0], Some(16.0 / 19.0));
+        assert_eq!(description.parse_rate[1], None);
+        assert_eq!(description.parse_rate[2], Some(1.0));
+    }
+
+    #[test]
+    fn test_seed_makes_noise_reproducible() {
+        let dataset = Dataset {
+            headers: vec!["x".to_string()],
+            rows: (0..50).map(|i| vec![((i * 37) % 11).to_string()]).collect(),
+        };
+        let options = DescribeOptions {
+            seed: Some(7),
+            ..Default::default()
+        };
+        let first = describe_with_options(&dataset, &options).unwrap();
+        let second = describe_with_options(&dataset, &options).unwrap();
+        assert_eq!(first.noise_scores, second.noise_scores);
+        // Without --seed the built-in seed keeps reruns identical too.
+        let unseeded = describe(&dataset).unwrap();
+        assert_eq!(
+            unseeded.noise_scores,
+            describe(&dataset).unwrap().noise_scores
+        );
+        let rows = &dataset.rows;
+        assert_eq!(
+            types::sample_rows(rows, TypeSampling::Random(5), Some(3)),
+            types::sample_rows(rows, TypeSampling::Random(5), Some(3))
+        );
+        assert_eq!(
+            types::sample_rows(rows, TypeSampling::Random(5), None),
+            types::sample_rows(rows, TypeSampling::Random(5), None)
+        );
+    }
+
+    #[test]
+    fn test_case_variants() {
+        let dataset = Dataset::new(
+            vec!["sex".to_string(), "n".to_string()],
+            ["Male", "male", "MALE ", "Male", "Female", "female", "Other"]
+                .iter()
+                .enumerate()
+                .map(|(i, v)| vec![v.to_string(), i.to_string()])
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        let groups = &description.case_variants[0];
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "Male");
+        assert_eq!(groups[0].1[0], ("Male".to_string(), 2));
+        assert_eq!(groups[1].0, "Female");
+        assert!(description.case_variants[1].is_empty());
+        let (merged, counts) = merge_case_variants(&dataset);
+        assert_eq!(counts, vec![3, 0]);
+        assert_eq!(merged.column("sex").unwrap()[2], "Male");
+        assert_eq!(merged.column("sex").unwrap()[5], "Female");
+
+        // Float spellings and missing tokens are not categories to merge.
+        let dataset = Dataset::new(
+            vec!["amount".to_string(), "label".to_string()],
+            [("1e3", "NA"), ("1E3", "na"), ("2.5", "NA"), ("1e3", "x")]
+                .iter()
+                .map(|(a, b)| vec![a.to_string(), b.to_string()])
+                .collect(),
+        );
+        let (merged, counts) = merge_case_variants(&dataset);
+        assert_eq!(counts, vec![0, 0]);
+        assert_eq!(merged.rows, dataset.rows);
+    }
+
+    #[test]
+    fn test_tab_exports() {
+        let dataset = Dataset::new(
+            vec!["a".to_string(), "b".to_string()],
+            (0..10)
+                .map(|i| vec![i.to_string(), (i * 2).to_string()])
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        let matrix = description.to_correlation_matrix_csv();
+        let lines: Vec<&str> = matrix.lines().collect();
+        assert_eq!(lines[0], ",a,b");
+        assert!(lines[1].starts_with("a,1,"));
+        assert_eq!(lines.len(), 3);
+        assert!(
+            description
+                .to_stats_csv()
+                .lines()
+                .nth(1)
+                .unwrap()
+                .starts_with("a,4.5,")
+        );
+        assert!(
+            description
+                .to_distribution_csv()
+                .starts_with("column,midpoint,count\na,")
+        );
+        assert_eq!(dataset.to_csv_string().lines().nth(2), Some("1,2"));
+    }
+
+    #[test]
+    fn test_dataset_filter() {
+        let dataset = Dataset::new(
+            vec!["status".to_string(), "amount".to_string()],
+            [
+                ("active", "150"),
+                ("closed", "90"),
+                ("active", "9"),
+                ("", "100"),
+                ("NA", "5"),
+                ("  ", "7"),
+            ]
+            .iter()
+            .map(|(s, a)| vec![s.to_string(), a.to_string()])
+            .collect(),
+        );
+        let active = dataset.filter("status", FilterOp::Eq, "active").unwrap();
+        assert_eq!(active.row_count(), 2);
+        // Numeric, not lexicographic: "9" < "100".
+        let large = dataset.filter("amount", FilterOp::Ge, "100").unwrap();
+        assert_eq!(large.column("amount").unwrap(), vec!["150", "100"]);
+        assert_eq!(
+            dataset
+                .filter("status", FilterOp::Ne, "active")
+                .unwrap()
+                .row_count(),
+            1
+        );
+        // Kept whitespace cells are values; `NA` stays missing either way.
+        assert_eq!(
+            dataset
+                .filter_with("status", FilterOp::Ne, "active", true)
+                .unwrap()
+                .column("amount")
+                .unwrap(),
+            vec!["90", "7"]
+        );
+        assert!(dataset.filter("missing", FilterOp::Lt, "1").is_err());
+    }
+
+    #[test]
+    fn test_column_and_dataset_hashes() {
+        let dataset = Dataset::new(
+            vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            vec![
+                vec!["1".to_string(), "x".to_string(), "1".to_string()],
+                vec!["2".to_string(), "y".to_string(), "2".to_string()],
+            ],
+        );
+        let description = describe(&dataset).unwrap();
+        let hashes = description.column_hashes();
+        assert_eq!(hashes[0], hashes[2]);
+        assert_ne!(hashes[0], hashes[1]);
+        assert_eq!(description.duplicate_columns, vec![(0, 2)]);
+        // Value boundaries count: ["ab", ""] and ["a", "b"] must not collide.
+        let split = |v: [&str; 2]| {
+            Dataset::new(
+                vec!["a".to_string()],
+                v.iter().map(|s| vec![s.to_string()]).collect(),
+            )
+        };
+        assert_ne!(
+            compute_column_hashes(&split(["ab", ""])),
+            compute_column_hashes(&split(["a", "b"]))
+        );
+
+        let mut changed = dataset.clone();
+        changed.rows[1][1] = "z".to_string();
+        let newer = describe(&changed).unwrap();
+        assert_ne!(description.dataset_hash(), newer.dataset_hash());
+        assert_eq!(newer.column_hashes()[0], hashes[0]);
+        let diff = description.to_report().diff(&newer.to_report());
+        assert!(diff.contains(&"`b` values changed".to_string()));
+        assert!(!diff.contains(&"`a` values changed".to_string()));
+    }
+
+    #[test]
+    fn test_five_number_summary() {
+        let dataset = Dataset::new(
+            vec!["x".to_string(), "label".to_string()],
+            (1..=9)
+                .map(|i| vec![i.to_string(), format!("l{}", i)])
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        let (min, q1, median, q3, max) = description.five_number_summary(0).unwrap();
+        assert_eq!((min, median, max), (1.0, 5.0, 9.0));
+        assert!(q1 < median && median < q3);
+        assert_eq!(description.five_number_summary(1), None);
+        assert_eq!(description.five_number_summary(2), None);
+    }
+
+    #[test]
+    fn test_whitespace_only_cells_are_missing() {
+        let dataset = Dataset::new(
+            vec!["n".to_string()],
+            ["1", "   ", "2", "\t", "3"]
+                .iter()
+                .map(|v| vec![v.to_string()])
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        assert_eq!(description.missing[0], 2);
+        assert_eq!(description.types[0], TypeInference::Integer);
+        assert_eq!(description.cardinality[0], 3);
+        let kept = describe_with_options(
+            &dataset,
+            &DescribeOptions {
+                keep_whitespace_cells: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(kept.missing[0], 0);
+        assert_ne!(kept.types[0], TypeInference::Integer);
+    }
+
+    #[test]
+    fn test_headerless_csv_with_names_file() {
+        let dir = std::env::temp_dir();
+        let data = dir.join("presto_test_headerless.data");
+        let names = dir.join("presto_test_headerless.names");
+        std::fs::write(&data, "5.1,3.5,setosa\n6.2,2.9,versicolor\n").unwrap();
+        std::fs::write(
+            &names,
+            "| Iris\nsetosa, versicolor, virginica.\n\nsepal length: continuous.\nsepal width: continuous.\n",
+        )
+        .unwrap();
+        let headers = Dataset::read_names_file(names.to_str().unwrap()).unwrap();
+        assert_eq!(headers, vec!["sepal length", "sepal width", "class"]);
+        let dataset = Dataset::from_headerless_csv(data.to_str().unwrap(), b',', headers).unwrap();
+        assert_eq!(dataset.shape(), (2, 3));
+        assert_eq!(
+            dataset.column("class").unwrap(),
+            vec!["setosa", "versicolor"]
+        );
+
+        std::fs::write(&names, "a\nb\n").unwrap();
+        let too_few = Dataset::read_names_file(names.to_str().unwrap()).unwrap();
+        assert!(Dataset::from_headerless_csv(data.to_str().unwrap(), b',', too_few).is_err());
+        std::fs::write(&names, "a, b, c\n").unwrap();
+        assert_eq!(
+            Dataset::read_names_file(names.to_str().unwrap()).unwrap(),
+            vec!["a", "b", "c"]
+        );
+        std::fs::remove_file(&data).unwrap();
+        std::fs::remove_file(&names).unwrap();
+    }
+
+    #[test]
+    fn test_outlier_bounds() {
+        let dataset = Dataset::new(
+            vec!["x".to_string()],
+            (0..30)
+                .map(|i| {
+                    vec![
+                        if i == 29 {
+                            "100"
+                        } else {
+                            ["1", "2", "3"][i % 3]
+                        }
+                        .to_string(),
+                    ]
+                })
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        let (lower, upper) = description.outlier_bounds[0].unwrap();
+        let stats = &description.stats[0];
+        let (mean, std_dev) = (stats.mean.unwrap(), stats.std_dev.unwrap());
+        assert!((upper - (mean + 3.0 * std_dev)).abs() < 1e-9);
+        assert!((lower - (mean - 3.0 * std_dev)).abs() < 1e-9);
+        assert!(description.outliers[0].iter().all(|&(row, _)| row == 29));
+        assert!(upper < 100.0);
+
+        let modified = AnomalyMethod::ModifiedZScore { threshold: 3.5 };
+        let (lower, upper) = modified.bounds(stats).unwrap();
+        let median = stats.median.unwrap();
+        assert!((upper - median - (median - lower)).abs() < 1e-9);
+        assert!(modified.score(upper + 1e-6, stats).unwrap() > 3.5);
+        assert!(modified.score(upper - 1e-6, stats).unwrap() < 3.5);
+        assert!(description.to_report().columns[0].anomaly_bounds.is_some());
+    }
+
+    #[test]
+    fn test_list_column_stats() {
+        let dataset = Dataset::new(
+            vec!["tags".into()],
+            vec![
+                vec!["red; green".into()],
+                vec!["red;red;blue".into()],
+                vec!["".into()],
+                vec!["green".into()],
+            ],
+        );
+        let options = DescribeOptions {
+            list_columns: HashMap::from([("tags".to_string(), ";".to_string())]),
+            ..Default::default()
+        };
+        let description = describe_with_options(&dataset, &options).unwrap();
+        let list = description.list_stats[0].as_ref().unwrap();
+        assert_eq!((list.lists, list.min_length, list.max_length), (3, 1, 3));
+        assert!((list.mean_length - 2.0).abs() < 1e-9);
+        assert_eq!(list.distinct_elements, 3);
+        assert_eq!(list.top_elements[0], ("green".to_string(), 2));
+        assert_eq!(list.top_elements[1], ("red".to_string(), 2));
+        let options = DescribeOptions {
+            list_columns: HashMap::from([("missing".to_string(), ";".to_string())]),
+            ..Default::default()
+        };
+        assert!(describe_with_options(&dataset, &options).is_err());
+    }
+
+    #[test]
+    fn test_timeout_skips_remaining_stages() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into()],
+            (0..20)
+                .map(|i| vec![i.to_string(), (i * 2).to_string()])
+                .collect(),
+        );
+        let options = DescribeOptions {
+            timeout: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        let description = describe_with_options(&dataset, &options).unwrap();
+        assert_eq!(description.skipped_stages().len(), 6);
+        assert_eq!(description.skipped_stages()[0], "dependency and drift");
+        assert_eq!(description.stats[1].mean, Some(19.0));
+        assert_eq!(description.correlations[0][1], 0.0);
+        assert_eq!(description.temporal_patterns[0], NOT_COMPUTED);
+        let report = description.to_report();
+        assert_eq!(report.skipped_stages, description.skipped_stages());
+        assert_eq!(report.columns.len(), 2);
+
+        assert!(describe(&dataset).unwrap().skipped_stages().is_empty());
+    }
+
+    #[test]
+    fn test_compare_cohorts() {
+        let dataset = Dataset::new(
+            vec!["arm".into(), "score".into(), "color".into()],
+            (0..20)
+                .map(|i| {
+                    let arm = if i % 2 == 0 { "control" } else { "treatment" };
+                    let score = if i % 2 == 0 { i % 4 } else { 10 + i % 4 };
+                    let color = if i % 2 == 0 { "red" } else { "blue" };
+                    vec![arm.into(), score.to_string(), color.into()]
+                })
+                .collect(),
+        );
+        let comparison = compare_cohorts(&dataset, "arm").unwrap();
+        assert_eq!(comparison.cohorts, ("control".into(), "treatment".into()));
+        assert_eq!(comparison.sizes, (10, 10));
+        let score = &comparison.columns[0];
+        assert_eq!(score.means, Some((1.0, 12.0)));
+        assert!(score.effect_size.unwrap() > 5.0);
+        assert!(score.p_value.unwrap() < 1e-6);
+        assert_eq!(comparison.columns[1].divergence, Some(1.0));
+        assert!(compare_cohorts(&dataset, "score").is_err());
+    }
+
+    #[test]
+    fn test_granularity_detection() {
+        let dataset = Dataset::new(
+            vec![
+                "price".into(),
+                "weight".into(),
+                "age".into(),
+                "plain".into(),
+            ],
+            (0..40)
+                .map(|i| {
+                    let age = if i % 4 == 0 { 20 + i } else { 20 + i / 5 * 5 };
+                    vec![
+                        (i * 5 + 5).to_string(),
+                        format!("{}.0", 60 + i),
+                        age.to_string(),
+                        (i * 7 % 40).to_string(),
+                    ]
+                })
+                .collect(),
+        );
+        let granularity = describe(&dataset).unwrap().granularity;
+        assert_eq!(granularity[0].unwrap().step, Some(5.0));
+        assert_eq!(granularity[1].unwrap().step, Some(1.0));
+        let age = granularity[2].unwrap();
+        assert_eq!(age.step, None);
+        assert_eq!(age.heaping.unwrap().0, 5.0);
+        assert!(granularity[3].is_none());
+    }
+
+    #[test]
+    fn test_dataset_from_columns() {
+        let precise = 0.1 + 0.2;
+        let dataset = Dataset::from_columns(
+            vec!["x".into(), "n".into(), "flag".into(), "name".into()],
+            vec![
+                ColumnData::Float(vec![Some(precise), Some(2.0), None]),
+                ColumnData::Integer(vec![Some(-3), Some(7), Some(12)]),
+                ColumnData::Boolean(vec![Some(true), None, Some(false)]),
+                ColumnData::Text(vec![Some("a".into()), Some("b".into()), None]),
+            ],
+        )
+        .unwrap();
+        assert_eq!(dataset.rows[0][0].parse::<f64>().unwrap(), precise);
+        assert_eq!(dataset.rows[1][0], "2.0");
+        assert_eq!(dataset.rows[2][0], "");
+        let description = describe(&dataset).unwrap();
+        assert_eq!(
+            description.types,
+            vec![
+                TypeInference::Float,
+                TypeInference::Integer,
+                TypeInference::Boolean,
+                TypeInference::String,
+            ]
+        );
+        assert_eq!(description.missing, vec![1, 0, 1, 1]);
+
+        let ragged = Dataset::from_columns(
+            vec!["x".into(), "n".into()],
+            vec![
+                ColumnData::Float(vec![Some(1.0)]),
+                ColumnData::Integer(vec![]),
+            ],
+        );
+        assert!(matches!(ragged, Err(PrestoError::SchemaMismatch(_))));
+    }
+
+    #[test]
+    fn test_anderson_darling_normality() {
+        let normal: Vec<f64> = (1..=99)
+            .map(|i| {
+                let p = i as f64 / 100.0;
+                statrs::function::erf::erf_inv(2.0 * p - 1.0) * std::f64::consts::SQRT_2
+            })
+            .collect();
+        let (a2, p) = stats::anderson_darling(&normal).unwrap();
+        assert!(a2 < 0.2 && p > 0.5);
+        let skewed: Vec<f64> = (0..60).map(|i| (i as f64 / 6.0).exp()).collect();
+        assert!(stats::anderson_darling(&skewed).unwrap().1 < 0.001);
+        assert!(stats::anderson_darling(&[1.0, 2.0, 3.0]).is_none());
+
+        let dataset = Dataset::new(
+            vec!["x".into(), "label".into()],
+            skewed
+                .iter()
+                .map(|v| vec![v.to_string(), "a".into()])
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        assert!(description.normality[0].is_some());
+        assert!(description.normality[1].is_none());
+        assert!(description.transform_suggestions[0].contains("Log transform"));
+    }
+
+    #[test]
+    fn test_utf8_bom_stripped() {
+        let path = std::env::temp_dir().join("presto_bom_test.csv");
+        std::fs::write(&path, "\u{feff}id,name\n1,Alice\n").unwrap();
+        let dataset = Dataset::from_csv(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(dataset.headers, vec!["id", "name"]);
+    }
+
+    #[test]
+    fn test_scientific_notation() {
+        let dataset = Dataset::new(
+            vec!["size".into()],
+            vec![
+                vec!["1e3".into()],
+                vec!["2.5e-2".into()],
+                vec!["3E4".into()],
+            ],
+        );
+        assert_eq!(types::infer_type(&dataset.rows, 0), TypeInference::Float);
+        let stats = stats::compute_stats(&dataset.rows, 0).unwrap();
+        assert_eq!(stats.min, Some(0.025));
+        assert_eq!(stats.max, Some(30_000.0));
+        assert!(stats.mean.is_some() && stats.std_dev.is_some() && stats.q3.is_some());
+        assert_eq!(check_consistency(&dataset).unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn test_recommended_dtype() {
+        let dataset = Dataset::new(
+            vec!["zip".into(), "day".into(), "city".into(), "n".into()],
+            (0..4)
+                .map(|i| {
+                    vec![
+                        format!("0{}123", i),
+                        format!("2024-01-0{}", i + 1),
+                        "Paris".into(),
+                        i.to_string(),
+                    ]
+                })
+                .collect(),
+        );
+        let description = describe(&dataset).unwrap();
+        assert_eq!(
+            description.recommended_dtype,
+            vec!["string", "datetime64[ns]", "category", "int32"]
+        );
+
+        let mut rows = dataset.rows.clone();
+        rows[1][3] = "NA".into();
+        let description = describe(&Dataset::new(dataset.headers.clone(), rows)).unwrap();
+        assert_eq!(description.recommended_dtype[3], "Int64");
+    }
+
+    #[test]
+    fn test_trim_whitespace() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into()],
+            vec![
+                vec!["foo ".into(), "1".into()],
+                vec![" foo".into(), "2".into()],
+            ],
+        );
+        assert_eq!(count_untrimmed(&dataset).unwrap(), vec![2, 0]);
+        let (trimmed, counts) = trim_whitespace(&dataset);
+        assert_eq!(counts, vec![2, 0]);
+        assert_eq!(trimmed.rows[0][0], trimmed.rows[1][0]);
+    }
+
+    #[test]
+    fn test_description_round_trip() {
+        let dataset = Dataset::new(
+            vec!["a".into(), "b".into(), "c".into()],
+            vec![
+                vec!["1".into(), "x".into(), "5".into()],
+                vec!["2".into(), "".into(), "5".into()],
+                vec!["4".into(), "y".into(), "5".into()],
+            ],
+        );
+        let description = describe(&dataset).unwrap();
+        let json = serde_json::to_string(&description).unwrap();
+        let restored: Description = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.summary_line(), description.summary_line());
+        assert_eq!(restored.types, description.types);
+        assert_eq!(restored.stats[0].mean, description.stats[0].mean);
+
+        let single = Dataset::new(
+            vec!["a".into()],
+            (0..5).map(|i| vec![i.to_string()]).collect(),
+        );
+        let description = describe(&single).unwrap();
+        assert_eq!(description.dependency_scores, vec![0.0]);
+        let json = serde_json::to_string(&description).unwrap();
+        let restored: Description = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.summary_line(), description.summary_line());
+    }
+
+    #[test]
+    fn test_report_round_trip_with_nan() {
+        let single = Dataset::new(vec!["a".into()], vec![vec!["1".into()], vec!["2".into()]]);
+        let mut report = describe(&single).unwrap().to_report();
+        report.columns[0].noise_score = f64::NAN;
+        report.columns[0].drift_series = vec![1.0, f64::NAN];
+        report.correlations[0][0] = f64::NAN;
+        let json = report.render(OutputFormat::Json).unwrap();
+        let restored: Report = serde_json::from_str(&json).unwrap();
+        assert!(restored.columns[0].noise_score.is_nan());
+        assert_eq!(restored.columns[0].drift_series[0], 1.0);
+        assert!(restored.columns[0].drift_series[1].is_nan());
+        assert!(restored.correlations[0][0].is_nan());
+        assert_eq!(restored.diff(&report)[0], "rows: 2 -> 2");
+    }
+
+    #[test]
+    fn test_group_by() {
+        let dataset = Dataset::new(
+            vec!["city".into(), "income".into()],
+            vec![
+                vec!["a".into(), "10".into()],
+                vec!["b".into(), "5".into()],
+                vec!["a".into(), "20".into()],
+                vec!["".into(), "99".into()],
+            ],
+        );
+        let groups = dataset.group_by("city").unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["a"][1].mean, Some(15.0));
+        assert_eq!(groups["b"][1].mean, Some(5.0));
+        assert!(dataset.group_by("missing").is_err());
+    }
+
+    #[test]
+    fn test_group_stats_csv() {
+        let dataset = Dataset::new(
+            vec!["year".into(), "income".into(), "note".into()],
+            vec![
+                vec!["10".into(), "4".into(), "x".into()],
+                vec!["9".into(), "5".into(), "".into()],
+                vec!["10".into(), "8".into(), "y".into()],
+                vec!["10".into(), "NA".into(), "z".into()],
+                vec!["".into(), "99".into(), "w".into()],
+            ],
+        );
+        let csv = dataset.group_stats_csv("year").unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "group,column,count,mean,std_dev,min,max");
+        assert_eq!(lines[1], "9,income,1,5,,5,5");
+        assert_eq!(lines[2], "9,note,0,,,,");
+        assert!(lines[3].starts_with("10,income,2,6,"));
+        assert!(lines[3].ends_with(",4,8"));
+        assert_eq!(lines.len(), 5);
+        assert!(dataset.group_stats_csv("missing").is_err());
+
+        // Whitespace-only cells are missing, in keys and values alike, unless kept.
+        let dataset = Dataset::new(
+            vec!["year".into(), "note".into()],
+            vec![
+                vec!["9".into(), "x".into()],
+                vec!["9".into(), "  ".into()],
+                vec![" ".into(), "y".into()],
+            ],
+        );
+        let csv = dataset.group_stats_csv("year").unwrap();
+        assert_eq!(csv.lines().collect::<Vec<_>>()[1..], ["9,note,1,,,,"]);
+        assert_eq!(dataset.group_by("year").unwrap().len(), 1);
+        let csv = dataset.group_stats_csv_with("year", true).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.contains("9,note,2,"));
     }
 }
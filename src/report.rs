@@ -0,0 +1,810 @@
+use crate::schema::SchemaViolation;
+use crate::stats::{ColumnStats, Granularity};
+use crate::types::{IntegerWidth, SemanticType, TypeInference};
+use crate::{CorrelationMethod, Description, PrestoError};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl OutputFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Yaml => "yaml",
+            OutputFormat::Toml => "toml",
+        }
+    }
+
+    pub fn next(self) -> OutputFormat {
+        match self {
+            OutputFormat::Json => OutputFormat::Yaml,
+            OutputFormat::Yaml => OutputFormat::Toml,
+            OutputFormat::Toml => OutputFormat::Json,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableFormat {
+    #[default]
+    Csv,
+    Markdown,
+}
+
+const COLUMNS_TABLE_HEADER: [&str; 17] = [
+    "column",
+    "type",
+    "semantic_type",
+    "recommended_dtype",
+    "missing",
+    "missing_pct",
+    "cardinality",
+    "cardinality_ratio",
+    "mean",
+    "std_dev",
+    "min",
+    "median",
+    "max",
+    "skewness",
+    "gini",
+    "top_value",
+    "top_value_pct",
+];
+
+fn table_number(value: Option<f64>) -> String {
+    value.map(|v| format!("{:.4}", v)).unwrap_or_default()
+}
+
+pub(crate) fn csv_number(value: Option<f64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+// serde_json writes NaN (e.g. the spread of a one-value column) as `null`; read it
+// back as NaN so every report Presto writes can be loaded again.
+#[derive(serde::Deserialize)]
+struct Nullable(Option<f64>);
+
+fn nan_if_null<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    let Nullable(value) = serde::Deserialize::deserialize(deserializer)?;
+    Ok(value.unwrap_or(f64::NAN))
+}
+
+fn nan_if_null_vec<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<f64>, D::Error> {
+    let values: Vec<Nullable> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(values
+        .into_iter()
+        .map(|Nullable(v)| v.unwrap_or(f64::NAN))
+        .collect())
+}
+
+fn nan_if_null_matrix<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Vec<f64>>, D::Error> {
+    let rows: Vec<Vec<Nullable>> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|Nullable(v)| v.unwrap_or(f64::NAN))
+                .collect()
+        })
+        .collect())
+}
+
+pub(crate) fn csv_text<H: AsRef<[u8]>>(header: &[H], rows: &[Vec<String>]) -> String {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    // Writing to an in-memory buffer cannot fail.
+    let _ = writer.write_record(header);
+    for row in rows {
+        let _ = writer.write_record(row);
+    }
+    String::from_utf8(writer.into_inner().unwrap_or_default()).unwrap_or_default()
+}
+
+/// What produced a profile: the Presto version, when it ran, the source and its
+/// size, and every analysis option in effect.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Provenance {
+    pub presto_version: String,
+    /// Absent under `--no-timestamp`, so reports stay identical across runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub rows: usize,
+    pub columns: usize,
+    pub options: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Report {
+    pub schema_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub quality_score: f64,
+    pub violations: Vec<SchemaViolation>,
+    pub total_rows: usize,
+    /// [`Description::dataset_hash`] as 16 hex digits (TOML integers cannot hold a u64).
+    #[serde(default)]
+    pub dataset_hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub small_sample_min_rows: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limited_from_columns: Option<usize>,
+    /// Stages cut by `--timeout`; the fields they fill hold placeholders.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_stages: Vec<String>,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub missing_pct: f64,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub unique_pct: f64,
+    pub duplicates: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub duplicate_key: Vec<String>,
+    pub cardinality_estimated: bool,
+    pub empty_columns: Vec<String>,
+    pub sequence_columns: Vec<String>,
+    pub columns: Vec<ColumnReport>,
+    #[serde(default)]
+    pub correlation_method: CorrelationMethod,
+    #[serde(deserialize_with = "nan_if_null_matrix")]
+    pub correlations: Vec<Vec<f64>>,
+    #[serde(deserialize_with = "nan_if_null_matrix")]
+    pub co_missingness: Vec<Vec<f64>>,
+    pub redundancy_pairs: Vec<ColumnPair>,
+    #[serde(default)]
+    pub correlation_ratios: Vec<ColumnPair>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linear_combinations: Option<Vec<LinearCombination>>,
+    pub duplicate_columns: Vec<(String, String)>,
+    pub feature_importance: Vec<FeatureImportance>,
+    pub anomalies: Vec<Anomaly>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ColumnReport {
+    pub name: String,
+    #[serde(default)]
+    pub hash: String,
+    pub inferred_type: TypeInference,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub type_confidence: f64,
+    pub recommended_dtype: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub semantic_type: Option<SemanticType>,
+    pub likely_pii: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_mismatch: Option<TypeMismatch>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_rate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integer_width: Option<IntegerWidth>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub positive_rate: Option<f64>,
+    pub stats: ColumnStats,
+    pub missing: usize,
+    pub cardinality: usize,
+    pub cardinality_including_missing: usize,
+    #[serde(default, deserialize_with = "nan_if_null")]
+    pub cardinality_ratio: f64,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub gini: f64,
+    pub consistency_issues: usize,
+    pub whitespace_values: usize,
+    pub non_finite_values: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub likely_sentinel: Option<Sentinel>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub case_variants: Vec<CaseVariants>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub granularity: Option<Granularity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list: Option<ListColumn>,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub dependency_score: f64,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub drift_score: f64,
+    #[serde(deserialize_with = "nan_if_null_vec")]
+    pub drift_series: Vec<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub categorical_drift: Option<f64>,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub noise_score: f64,
+    pub temporal_pattern: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normality: Option<Normality>,
+    pub transform_suggestion: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stability: Option<FoldStability>,
+    pub outlier_count: usize,
+    /// Values strictly outside these fences (mean ± 3 std dev) are the `outliers`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outlier_bounds: Option<Bounds>,
+    /// Fences of the configured anomaly method (absent with a rolling window).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anomaly_bounds: Option<Bounds>,
+    pub outliers: Vec<Outlier>,
+    pub distribution: Vec<HistogramBin>,
+    pub top_values: Vec<TopValue>,
+}
+
+/// Anderson-Darling test against a normal distribution (see `stats::anderson_darling`).
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Normality {
+    #[serde(deserialize_with = "nan_if_null")]
+    pub anderson_darling: f64,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub p_value: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TypeMismatch {
+    pub expected: TypeInference,
+    pub samples: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Sentinel {
+    #[serde(deserialize_with = "nan_if_null")]
+    pub value: f64,
+    pub count: usize,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub percent: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FoldStability {
+    #[serde(deserialize_with = "nan_if_null")]
+    pub mean_variance: f64,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub std_dev_variance: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Bounds {
+    #[serde(deserialize_with = "nan_if_null")]
+    pub lower: f64,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub upper: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Outlier {
+    pub row: usize,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub z_score: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct HistogramBin {
+    #[serde(deserialize_with = "nan_if_null")]
+    pub midpoint: f64,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CaseVariants {
+    pub canonical: String,
+    pub variants: Vec<TopValue>,
+}
+
+/// Element profile of a `--list-col` column; element percents are shares of lists.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ListColumn {
+    pub delimiter: String,
+    pub lists: usize,
+    pub min_length: usize,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub mean_length: f64,
+    pub max_length: usize,
+    pub distinct_elements: usize,
+    pub top_elements: Vec<TopValue>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TopValue {
+    pub value: String,
+    pub count: usize,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub percent: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ColumnPair {
+    pub column_a: String,
+    pub column_b: String,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LinearCombination {
+    pub column: String,
+    pub terms: Vec<LinearTerm>,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub r_squared: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LinearTerm {
+    pub column: String,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub coefficient: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FeatureImportance {
+    pub column: String,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Anomaly {
+    pub column: String,
+    #[serde(deserialize_with = "nan_if_null")]
+    pub value: f64,
+    pub row: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+}
+
+impl Description {
+    pub fn correlation_edges(&self, threshold: f64) -> Vec<ColumnPair> {
+        let mut edges = Vec::new();
+        for (i, row) in self.correlations.iter().enumerate() {
+            for (j, &r) in row.iter().enumerate().skip(i + 1) {
+                if r.abs() >= threshold {
+                    edges.push(ColumnPair {
+                        column_a: self.top_values[i].0.clone(),
+                        column_b: self.top_values[j].0.clone(),
+                        score: r,
+                    });
+                }
+            }
+        }
+        edges
+    }
+
+    /// One row per column with its headline profile fields, as a CSV or
+    /// Markdown data dictionary.
+    pub fn to_columns_table(&self, format: TableFormat) -> String {
+        let rows: Vec<Vec<String>> = self
+            .top_values
+            .iter()
+            .enumerate()
+            .map(|(i, (name, top))| {
+                let stats = &self.stats[i];
+                let missing_pct = if self.total_rows > 0 {
+                    self.missing[i] as f64 / self.total_rows as f64 * 100.0
+                } else {
+                    0.0
+                };
+                let (top_value, top_pct) = match top.first() {
+                    Some((value, count)) => (
+                        value.clone(),
+                        table_number(Some(self.value_share(i, *count))),
+                    ),
+                    None => (String::new(), String::new()),
+                };
+                vec![
+                    name.clone(),
+                    format!("{:?}", self.types[i]),
+                    self.semantic_types[i]
+                        .map(|s| s.to_string())
+                        .unwrap_or_default(),
+                    self.recommended_dtype[i].clone(),
+                    self.missing[i].to_string(),
+                    table_number(Some(missing_pct)),
+                    self.cardinality[i].to_string(),
+                    table_number(Some(self.cardinality_ratio[i])),
+                    table_number(stats.mean),
+                    table_number(stats.std_dev),
+                    table_number(stats.min),
+                    table_number(stats.median),
+                    table_number(stats.max),
+                    table_number(stats.skewness),
+                    table_number(Some(self.gini[i])),
+                    top_value,
+                    top_pct,
+                ]
+            })
+            .collect();
+
+        match format {
+            TableFormat::Csv => csv_text(&COLUMNS_TABLE_HEADER, &rows),
+            TableFormat::Markdown => {
+                let mut out = format!("| {} |\n", COLUMNS_TABLE_HEADER.join(" | "));
+                out.push_str(&format!("|{}\n", "---|".repeat(COLUMNS_TABLE_HEADER.len())));
+                for row in &rows {
+                    let cells: Vec<String> = row
+                        .iter()
+                        .map(|cell| cell.replace('|', "\\|").replace('\n', " "))
+                        .collect();
+                    out.push_str(&format!("| {} |\n", cells.join(" | ")));
+                }
+                out
+            }
+        }
+    }
+
+    /// The Stats tab as CSV: one row per column, values at full precision.
+    pub fn to_stats_csv(&self) -> String {
+        let rows: Vec<Vec<String>> = self
+            .stats
+            .iter()
+            .enumerate()
+            .map(|(i, stats)| {
+                vec![
+                    self.top_values[i].0.clone(),
+                    csv_number(stats.mean),
+                    csv_number(stats.median),
+                    csv_number(stats.std_dev),
+                    csv_number(stats.variance),
+                    csv_number(stats.mad),
+                    csv_number(stats.min),
+                    csv_number(stats.max),
+                    csv_number(stats.skewness),
+                    csv_number(stats.kurtosis),
+                    self.outlier_counts[i].to_string(),
+                ]
+            })
+            .collect();
+        csv_text(
+            &[
+                "column", "mean", "median", "std_dev", "variance", "mad", "min", "max", "skewness",
+                "kurtosis", "outliers",
+            ],
+            &rows,
+        )
+    }
+
+    /// The correlation matrix as CSV, with column names along both axes.
+    pub fn to_correlation_matrix_csv(&self) -> String {
+        let names: Vec<&str> = self
+            .top_values
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        let header: Vec<&str> = std::iter::once("").chain(names.iter().copied()).collect();
+        let rows: Vec<Vec<String>> = self
+            .correlations
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                std::iter::once(names[i].to_string())
+                    .chain(row.iter().map(|r| r.to_string()))
+                    .collect()
+            })
+            .collect();
+        csv_text(&header, &rows)
+    }
+
+    /// Every column's histogram as CSV, one row per bin.
+    pub fn to_distribution_csv(&self) -> String {
+        let rows: Vec<Vec<String>> = self
+            .distributions
+            .iter()
+            .enumerate()
+            .flat_map(|(i, bins)| {
+                bins.iter().map(move |(midpoint, count)| {
+                    vec![
+                        self.top_values[i].0.clone(),
+                        midpoint.to_string(),
+                        count.to_string(),
+                    ]
+                })
+            })
+            .collect();
+        csv_text(&["column", "midpoint", "count"], &rows)
+    }
+
+    pub fn to_report(&self) -> Report {
+        let names: Vec<String> = self
+            .top_values
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        let columns = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| ColumnReport {
+                name: name.clone(),
+                hash: format!("{:016x}", self.column_hashes[i]),
+                inferred_type: self.types[i].clone(),
+                type_confidence: self.type_confidence[i],
+                recommended_dtype: self.recommended_dtype[i].clone(),
+                semantic_type: self.semantic_types[i],
+                likely_pii: self.semantic_types[i].is_some_and(|s| s.is_pii()),
+                type_mismatch: self.type_mismatches[i].as_ref().map(|(expected, samples)| {
+                    TypeMismatch {
+                        expected: expected.clone(),
+                        samples: samples.clone(),
+                    }
+                }),
+                parse_rate: self.parse_rate[i],
+                integer_width: self.integer_widths[i],
+                positive_rate: self.positive_rates[i],
+                stats: self.stats[i].clone(),
+                missing: self.missing[i],
+                cardinality: self.cardinality[i],
+                cardinality_including_missing: self.cardinality[i]
+                    + usize::from(self.missing[i] > 0),
+                cardinality_ratio: self.cardinality_ratio[i],
+                gini: self.gini[i],
+                consistency_issues: self.consistency_issues[i],
+                whitespace_values: self.whitespace_counts[i],
+                non_finite_values: self.non_finite_counts[i],
+                likely_sentinel: self.sentinels[i].map(|(value, count)| Sentinel {
+                    value,
+                    count,
+                    percent: self.value_share(i, count),
+                }),
+                case_variants: self.case_variants[i]
+                    .iter()
+                    .map(|(canonical, variants)| CaseVariants {
+                        canonical: canonical.clone(),
+                        variants: variants
+                            .iter()
+                            .map(|(value, count)| TopValue {
+                                value: value.clone(),
+                                count: *count,
+                                percent: self.value_share(i, *count),
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+                granularity: self.granularity[i],
+                list: self.list_stats[i].as_ref().map(|list| ListColumn {
+                    delimiter: list.delimiter.clone(),
+                    lists: list.lists,
+                    min_length: list.min_length,
+                    mean_length: list.mean_length,
+                    max_length: list.max_length,
+                    distinct_elements: list.distinct_elements,
+                    top_elements: list
+                        .top_elements
+                        .iter()
+                        .map(|(value, count)| TopValue {
+                            value: value.clone(),
+                            count: *count,
+                            percent: *count as f64 / list.lists as f64 * 100.0,
+                        })
+                        .collect(),
+                }),
+                dependency_score: self.dependency_scores[i],
+                drift_score: self.drift_scores[i],
+                drift_series: self.drift_series[i].clone(),
+                categorical_drift: self.categorical_drift[i],
+                noise_score: self.noise_scores[i],
+                temporal_pattern: self.temporal_patterns[i].clone(),
+                normality: self.normality[i].map(|(anderson_darling, p_value)| Normality {
+                    anderson_darling,
+                    p_value,
+                }),
+                transform_suggestion: self.transform_suggestions[i].clone(),
+                stability: self.stability.get(i).copied().flatten().map(
+                    |(mean_variance, std_dev_variance)| FoldStability {
+                        mean_variance,
+                        std_dev_variance,
+                    },
+                ),
+                outlier_count: self.outlier_counts[i],
+                outlier_bounds: self.outlier_bounds[i]
+                    .map(|(lower, upper)| Bounds { lower, upper }),
+                anomaly_bounds: self.anomaly_bounds[i]
+                    .map(|(lower, upper)| Bounds { lower, upper }),
+                outliers: self.outliers[i]
+                    .iter()
+                    .map(|&(row, z_score)| Outlier { row, z_score })
+                    .collect(),
+                distribution: self.distributions[i]
+                    .iter()
+                    .map(|&(midpoint, count)| HistogramBin { midpoint, count })
+                    .collect(),
+                top_values: self.top_values[i]
+                    .1
+                    .iter()
+                    .map(|(value, count)| TopValue {
+                        value: value.clone(),
+                        count: *count,
+                        percent: self.value_share(i, *count),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Report {
+            schema_version: REPORT_SCHEMA_VERSION,
+            provenance: Some(self.provenance.clone()),
+            quality_score: self.quality_score(),
+            violations: Vec::new(),
+            total_rows: self.total_rows,
+            dataset_hash: format!("{:016x}", self.dataset_hash),
+            small_sample_min_rows: self.small_sample(),
+            limited_from_columns: self.retained_columns.as_ref().map(|_| self.source_columns),
+            skipped_stages: self.skipped_stages().to_vec(),
+            missing_pct: self.missing_pct,
+            unique_pct: self.unique_pct,
+            duplicates: self.duplicates,
+            duplicate_key: self.duplicate_key.clone(),
+            cardinality_estimated: self.cardinality_estimated,
+            empty_columns: self
+                .empty_columns
+                .iter()
+                .map(|&col| names[col].clone())
+                .collect(),
+            sequence_columns: self
+                .sequence_columns
+                .iter()
+                .map(|&col| names[col].clone())
+                .collect(),
+            columns,
+            correlation_method: self.correlation_method,
+            correlations: self.correlations.clone(),
+            co_missingness: self.co_missingness.clone(),
+            redundancy_pairs: self
+                .redundancy_pairs
+                .iter()
+                .map(|&(a, b, score)| ColumnPair {
+                    column_a: names[a].clone(),
+                    column_b: names[b].clone(),
+                    score,
+                })
+                .collect(),
+            correlation_ratios: self
+                .correlation_ratios
+                .iter()
+                .map(|&(cat, num, eta)| ColumnPair {
+                    column_a: names[cat].clone(),
+                    column_b: names[num].clone(),
+                    score: eta,
+                })
+                .collect(),
+            linear_combinations: self.linear_combinations.as_ref().map(|found| {
+                found
+                    .iter()
+                    .map(|(col, terms, r_squared)| LinearCombination {
+                        column: names[*col].clone(),
+                        terms: terms
+                            .iter()
+                            .map(|&(other, coefficient)| LinearTerm {
+                                column: names[other].clone(),
+                                coefficient,
+                            })
+                            .collect(),
+                        r_squared: *r_squared,
+                    })
+                    .collect()
+            }),
+            duplicate_columns: self
+                .duplicate_columns
+                .iter()
+                .map(|&(a, b)| (names[a].clone(), names[b].clone()))
+                .collect(),
+            feature_importance: self
+                .feature_importance
+                .iter()
+                .map(|&(col, score)| FeatureImportance {
+                    column: names[col].clone(),
+                    score,
+                })
+                .collect(),
+            anomalies: self
+                .anomalies
+                .iter()
+                .enumerate()
+                .map(|(i, &(col, value, row))| Anomaly {
+                    column: names[col].clone(),
+                    value,
+                    row,
+                    explanation: self.anomaly_explanations.get(i).cloned(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Report {
+    pub fn render(&self, format: OutputFormat) -> Result<String, PrestoError> {
+        match format {
+            OutputFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| PrestoError::Serialization(e.to_string())),
+            OutputFormat::Yaml => {
+                serde_yaml::to_string(self).map_err(|e| PrestoError::Serialization(e.to_string()))
+            }
+            OutputFormat::Toml => {
+                toml::to_string_pretty(self).map_err(|e| PrestoError::Serialization(e.to_string()))
+            }
+        }
+    }
+
+    pub fn diff(&self, newer: &Report) -> Vec<String> {
+        let mut lines = vec![
+            format!("rows: {} -> {}", self.total_rows, newer.total_rows),
+            format!(
+                "quality score: {:.1} -> {:.1}",
+                self.quality_score, newer.quality_score
+            ),
+            format!(
+                "missing: {:.1}% -> {:.1}%",
+                self.missing_pct, newer.missing_pct
+            ),
+        ];
+        if !self.dataset_hash.is_empty() && self.dataset_hash == newer.dataset_hash {
+            lines.push(format!("data unchanged (hash {})", self.dataset_hash));
+        }
+        // A changed option can look like a change in the data, so list those first.
+        if let (Some(old), Some(new)) = (&self.provenance, &newer.provenance) {
+            let keys: BTreeSet<&String> = old.options.keys().chain(new.options.keys()).collect();
+            for key in keys {
+                let (before, after) = (old.options.get(key), new.options.get(key));
+                if before != after {
+                    lines.push(format!(
+                        "option `{}`: {} -> {}",
+                        key,
+                        before.map_or("(unset)", String::as_str),
+                        after.map_or("(unset)", String::as_str)
+                    ));
+                }
+            }
+        }
+        for column in &self.columns {
+            if !newer.columns.iter().any(|c| c.name == column.name) {
+                lines.push(format!("removed column `{}`", column.name));
+            }
+        }
+        for column in &newer.columns {
+            let Some(old) = self.columns.iter().find(|c| c.name == column.name) else {
+                lines.push(format!("added column `{}`", column.name));
+                continue;
+            };
+            if !old.hash.is_empty() && !column.hash.is_empty() && old.hash != column.hash {
+                lines.push(format!("`{}` values changed", column.name));
+            }
+            if old.inferred_type != column.inferred_type {
+                lines.push(format!(
+                    "`{}` type: {:?} -> {:?}",
+                    column.name, old.inferred_type, column.inferred_type
+                ));
+            }
+            if old.missing != column.missing {
+                lines.push(format!(
+                    "`{}` missing: {} -> {}",
+                    column.name, old.missing, column.missing
+                ));
+            }
+            if let (Some(old_mean), Some(new_mean)) = (old.stats.mean, column.stats.mean) {
+                let scale = old.stats.std_dev.filter(|&s| s > 0.0).unwrap_or(1.0);
+                let shift = (new_mean - old_mean) / scale;
+                if old_mean != new_mean {
+                    lines.push(format!(
+                        "`{}` mean: {:.3} -> {:.3} (drift {:.2} std)",
+                        column.name, old_mean, new_mean, shift
+                    ));
+                }
+            }
+            if let (Some(old_std), Some(new_std)) = (old.stats.std_dev, column.stats.std_dev)
+                && old_std != new_std
+            {
+                lines.push(format!(
+                    "`{}` std dev: {:.3} -> {:.3}",
+                    column.name, old_std, new_std
+                ));
+            }
+        }
+        lines
+    }
+}
@@ -0,0 +1,155 @@
+use crate::Dataset;
+use crate::types::{TypeInference, infer_type, parse_date};
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ExpectedSchema {
+    pub columns: Vec<ExpectedColumn>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ExpectedColumn {
+    pub name: String,
+    #[serde(default, rename = "type")]
+    pub column_type: Option<TypeInference>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum SchemaViolation {
+    MissingColumn {
+        column: String,
+    },
+    UnexpectedColumn {
+        column: String,
+    },
+    OutOfOrder {
+        column: String,
+        expected_position: usize,
+        actual_position: usize,
+    },
+    TypeMismatch {
+        column: String,
+        expected: TypeInference,
+        actual: TypeInference,
+    },
+    UnexpectedNulls {
+        column: String,
+        count: usize,
+    },
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaViolation::MissingColumn { column } => write!(f, "missing column `{}`", column),
+            SchemaViolation::UnexpectedColumn { column } => {
+                write!(f, "unexpected column `{}`", column)
+            }
+            SchemaViolation::OutOfOrder {
+                column,
+                expected_position,
+                actual_position,
+            } => write!(
+                f,
+                "column `{}` at position {}, expected {}",
+                column, actual_position, expected_position
+            ),
+            SchemaViolation::TypeMismatch {
+                column,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "column `{}` has type {:?}, expected {:?}",
+                column, actual, expected
+            ),
+            SchemaViolation::UnexpectedNulls { column, count } => write!(
+                f,
+                "required column `{}` has {} missing values",
+                column, count
+            ),
+        }
+    }
+}
+
+// Inference never yields Categorical or Date, so those are checked against the
+// text values themselves: any text column is categorical, and a date column is
+// text whose every non-missing value parses as a date.
+fn type_satisfies(
+    actual: &TypeInference,
+    expected: &TypeInference,
+    rows: &[Vec<String>],
+    col_idx: usize,
+) -> bool {
+    match expected {
+        TypeInference::Categorical => {
+            matches!(actual, TypeInference::String | TypeInference::Categorical)
+        }
+        TypeInference::Date => {
+            *actual == TypeInference::String
+                && rows
+                    .iter()
+                    .map(|row| row[col_idx].as_str())
+                    .filter(|v| !v.is_empty() && *v != "NA")
+                    .all(|v| parse_date(v).is_some())
+        }
+        _ => {
+            actual == expected
+                || (*actual == TypeInference::Integer && *expected == TypeInference::Float)
+        }
+    }
+}
+
+pub fn validate_against_schema(dataset: &Dataset, schema: &ExpectedSchema) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+
+    for (expected_position, expected) in schema.columns.iter().enumerate() {
+        let Some(actual_position) = dataset.headers.iter().position(|h| *h == expected.name) else {
+            violations.push(SchemaViolation::MissingColumn {
+                column: expected.name.clone(),
+            });
+            continue;
+        };
+        if actual_position != expected_position {
+            violations.push(SchemaViolation::OutOfOrder {
+                column: expected.name.clone(),
+                expected_position,
+                actual_position,
+            });
+        }
+        if let Some(expected_type) = &expected.column_type {
+            let actual = infer_type(&dataset.rows, actual_position);
+            if !type_satisfies(&actual, expected_type, &dataset.rows, actual_position) {
+                violations.push(SchemaViolation::TypeMismatch {
+                    column: expected.name.clone(),
+                    expected: expected_type.clone(),
+                    actual,
+                });
+            }
+        }
+        if expected.required {
+            let count = dataset
+                .rows
+                .iter()
+                .filter(|row| row[actual_position].is_empty() || row[actual_position] == "NA")
+                .count();
+            if count > 0 {
+                violations.push(SchemaViolation::UnexpectedNulls {
+                    column: expected.name.clone(),
+                    count,
+                });
+            }
+        }
+    }
+
+    for header in &dataset.headers {
+        if !schema.columns.iter().any(|c| c.name == *header) {
+            violations.push(SchemaViolation::UnexpectedColumn {
+                column: header.clone(),
+            });
+        }
+    }
+
+    violations
+}
@@ -0,0 +1,165 @@
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+use std::fmt::Write;
+
+const CELL_WIDTH: f64 = 9.0;
+const CELL_HEIGHT: f64 = 18.0;
+const FONT_SIZE: f64 = 15.0;
+const DEFAULT_FG: &str = "#d0d0d0";
+const DEFAULT_BG: &str = "#1e1e1e";
+
+// The 16 ANSI colors as xterm renders them.
+fn ansi_hex(color: Color) -> Option<&'static str> {
+    Some(match color {
+        Color::Black => "#000000",
+        Color::Red => "#cd0000",
+        Color::Green => "#00cd00",
+        Color::Yellow => "#cdcd00",
+        Color::Blue => "#0000ee",
+        Color::Magenta => "#cd00cd",
+        Color::Cyan => "#00cdcd",
+        Color::Gray => "#e5e5e5",
+        Color::DarkGray => "#7f7f7f",
+        Color::LightRed => "#ff0000",
+        Color::LightGreen => "#00ff00",
+        Color::LightYellow => "#ffff00",
+        Color::LightBlue => "#5c5cff",
+        Color::LightMagenta => "#ff00ff",
+        Color::LightCyan => "#00ffff",
+        Color::White => "#ffffff",
+        _ => return None,
+    })
+}
+
+fn hex(color: Color, default: &str) -> String {
+    match color {
+        Color::Reset => default.to_string(),
+        Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        Color::Indexed(i) if i < 16 => {
+            const BASIC: [Color; 16] = [
+                Color::Black,
+                Color::Red,
+                Color::Green,
+                Color::Yellow,
+                Color::Blue,
+                Color::Magenta,
+                Color::Cyan,
+                Color::Gray,
+                Color::DarkGray,
+                Color::LightRed,
+                Color::LightGreen,
+                Color::LightYellow,
+                Color::LightBlue,
+                Color::LightMagenta,
+                Color::LightCyan,
+                Color::White,
+            ];
+            hex(BASIC[i as usize], default)
+        }
+        // 6x6x6 color cube, then a 24-step gray ramp.
+        Color::Indexed(i) if i < 232 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let i = i - 16;
+            format!(
+                "#{:02x}{:02x}{:02x}",
+                level(i / 36),
+                level(i / 6 % 6),
+                level(i % 6)
+            )
+        }
+        Color::Indexed(i) => {
+            let v = 8 + (i - 232) * 10;
+            format!("#{:02x}{:02x}{:02x}", v, v, v)
+        }
+        named => ansi_hex(named).unwrap_or(default).to_string(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Renders a terminal cell grid as a standalone SVG: one background rect and one
+/// text run per stretch of identically styled cells on each line.
+pub(crate) fn buffer_to_svg(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let (width, height) = (
+        area.width as f64 * CELL_WIDTH,
+        area.height as f64 * CELL_HEIGHT,
+    );
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="Menlo, Consolas, 'DejaVu Sans Mono', monospace" font-size="{f}">"#,
+        w = width,
+        h = height,
+        f = FONT_SIZE
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        DEFAULT_BG
+    );
+    for y in 0..area.height {
+        let mut x = 0;
+        while x < area.width {
+            let cell = &buffer[(area.x + x, area.y + y)];
+            let start = x;
+            let mut text = String::new();
+            // Wide glyphs leave an empty continuation cell behind them; it belongs to the run.
+            while x < area.width {
+                let next = &buffer[(area.x + x, area.y + y)];
+                if next.fg != cell.fg || next.bg != cell.bg || next.modifier != cell.modifier {
+                    break;
+                }
+                text.push_str(next.symbol());
+                x += 1;
+            }
+            let (mut fg, mut bg) = (hex(cell.fg, DEFAULT_FG), hex(cell.bg, DEFAULT_BG));
+            if cell.modifier.contains(Modifier::REVERSED) {
+                std::mem::swap(&mut fg, &mut bg);
+            }
+            let (px, py) = (start as f64 * CELL_WIDTH, y as f64 * CELL_HEIGHT);
+            let run_width = (x - start) as f64 * CELL_WIDTH;
+            if bg != DEFAULT_BG {
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    px, py, run_width, CELL_HEIGHT, bg
+                );
+            }
+            if text.trim().is_empty() {
+                continue;
+            }
+            // Pin each run to its cells so glyph widths in the viewer's font cannot drift.
+            let mut attributes = format!(
+                r#"fill="{}" textLength="{}" lengthAdjust="spacingAndGlyphs""#,
+                fg, run_width
+            );
+            if cell.modifier.contains(Modifier::BOLD) {
+                attributes.push_str(r#" font-weight="bold""#);
+            }
+            if cell.modifier.contains(Modifier::ITALIC) {
+                attributes.push_str(r#" font-style="italic""#);
+            }
+            if cell.modifier.contains(Modifier::UNDERLINED) {
+                attributes.push_str(r#" text-decoration="underline""#);
+            }
+            if cell.modifier.contains(Modifier::DIM) {
+                attributes.push_str(r#" opacity="0.6""#);
+            }
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="{}" {} xml:space="preserve">{}</text>"#,
+                px,
+                py + CELL_HEIGHT * 0.75,
+                attributes,
+                escape(&text)
+            );
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -0,0 +1,73 @@
+use crate::{Dataset, PrestoError, TypeInference};
+use std::collections::HashMap;
+use tokio_postgres::types::Type;
+use tokio_postgres::{NoTls, SimpleQueryMessage};
+
+pub(crate) fn column_type(sql_type: &Type) -> Option<TypeInference> {
+    match *sql_type {
+        Type::INT2 | Type::INT4 | Type::INT8 | Type::OID => Some(TypeInference::Integer),
+        Type::FLOAT4 | Type::FLOAT8 | Type::NUMERIC => Some(TypeInference::Float),
+        Type::BOOL => Some(TypeInference::Boolean),
+        Type::DATE | Type::TIMESTAMP => Some(TypeInference::Date),
+        Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UUID => {
+            Some(TypeInference::String)
+        }
+        _ => None,
+    }
+}
+
+impl Dataset {
+    /// Runs `query` against a Postgres database and loads the result as text,
+    /// with NULL as a missing value. Also returns the type implied by each
+    /// column's SQL type, ready for `DescribeOptions::type_overrides`; columns
+    /// with other SQL types are left to inference.
+    pub fn from_sql(
+        conn_str: &str,
+        query: &str,
+    ) -> Result<(Dataset, HashMap<String, TypeInference>), PrestoError> {
+        let sql_error = |e: tokio_postgres::Error| PrestoError::InvalidNumeric(e.to_string());
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
+        runtime.block_on(async {
+            let (client, connection) = tokio_postgres::connect(conn_str, NoTls)
+                .await
+                .map_err(sql_error)?;
+            tokio::spawn(connection);
+
+            let statement = client.prepare(query).await.map_err(sql_error)?;
+            let headers = crate::disambiguate_headers(
+                statement
+                    .columns()
+                    .iter()
+                    .map(|column| column.name().to_string())
+                    .collect(),
+            );
+            let types = headers
+                .iter()
+                .zip(statement.columns())
+                .filter_map(|(header, column)| {
+                    column_type(column.type_()).map(|t| (header.clone(), t))
+                })
+                .collect();
+
+            // The simple protocol returns every value as text, whatever its SQL type.
+            let rows = client
+                .simple_query(query)
+                .await
+                .map_err(sql_error)?
+                .into_iter()
+                .filter_map(|message| match message {
+                    SimpleQueryMessage::Row(row) => Some(
+                        (0..row.len())
+                            .map(|i| row.get(i).unwrap_or_default().to_string())
+                            .collect(),
+                    ),
+                    _ => None,
+                })
+                .collect();
+            Ok((Dataset::new(headers, rows), types))
+        })
+    }
+}
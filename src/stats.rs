@@ -1,21 +1,273 @@
-use crate::{Dataset, PrestoError};
+use crate::types::{BooleanEncoding, TypeInference, parse_date};
+use crate::{Dataset, PrestoError, ShapeThresholds};
 use rand::seq::SliceRandom;
 use rayon::prelude::*;
+use statrs::distribution::{ContinuousCDF, Normal, StudentsT};
 use statrs::statistics::{Data, Distribution};
-use std::str::FromStr;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
 
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ColumnStats {
     pub mean: Option<f64>,
     pub median: Option<f64>,
+    pub q1: Option<f64>,
+    pub q3: Option<f64>,
     pub min: Option<f64>,
     pub max: Option<f64>,
     pub std_dev: Option<f64>,
     pub variance: Option<f64>,
+    pub mad: Option<f64>,
     pub skewness: Option<f64>,
     pub kurtosis: Option<f64>,
 }
 
+impl ColumnStats {
+    /// `(min, Q1, median, Q3, max)`, or `None` for a column without numeric values.
+    pub fn five_number_summary(&self) -> Option<(f64, f64, f64, f64, f64)> {
+        Some((self.min?, self.q1?, self.median?, self.q3?, self.max?))
+    }
+}
+
+pub(crate) fn parse_finite(val: &str) -> Option<f64> {
+    val.parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+pub fn count_non_finite(dataset: &Dataset) -> Result<Vec<usize>, PrestoError> {
+    let num_cols = dataset.headers.len();
+    (0..num_cols)
+        .into_par_iter()
+        .map(|col_idx| {
+            Ok(dataset
+                .rows
+                .iter()
+                .filter(|row| row[col_idx].parse::<f64>().is_ok_and(|v| !v.is_finite()))
+                .count())
+        })
+        .collect::<Result<Vec<_>, _>>()
+}
+
+const SENTINEL_MIN_SHARE: f64 = 0.05;
+const SENTINEL_NINES_MIN_SHARE: f64 = 0.01;
+const SENTINEL_MIN_ROBUST_Z: f64 = 5.0;
+
+fn is_all_nines(value: f64) -> bool {
+    let digits = format!("{}", value.abs());
+    digits.len() >= 2 && digits.chars().all(|c| c == '9')
+}
+
+// The most frequent value of a numeric column when it looks like an encoded
+// missing value: it covers at least SENTINEL_MIN_SHARE of the values (or
+// SENTINEL_NINES_MIN_SHARE for 99, -9999 and the like) and sits more than
+// SENTINEL_MIN_ROBUST_Z MADs from the median of the remaining values.
+pub fn detect_sentinels(
+    dataset: &Dataset,
+    types: &[TypeInference],
+) -> Result<Vec<Option<(f64, usize)>>, PrestoError> {
+    Ok((0..dataset.headers.len())
+        .into_par_iter()
+        .map(|col_idx| {
+            if !matches!(
+                types[col_idx],
+                TypeInference::Integer | TypeInference::Float
+            ) {
+                return None;
+            }
+            let values: Vec<f64> = dataset
+                .rows
+                .iter()
+                .filter_map(|row| parse_finite(&row[col_idx]))
+                .collect();
+            let mut counts: HashMap<u64, usize> = HashMap::new();
+            for value in &values {
+                *counts.entry(value.to_bits()).or_insert(0) += 1;
+            }
+            let (bits, count) = counts
+                .into_iter()
+                .max_by_key(|&(bits, count)| (count, std::cmp::Reverse(bits)))?;
+            let candidate = f64::from_bits(bits);
+            let share = count as f64 / values.len() as f64;
+            let min_share = if is_all_nines(candidate) {
+                SENTINEL_NINES_MIN_SHARE
+            } else {
+                SENTINEL_MIN_SHARE
+            };
+            if share < min_share || count == values.len() {
+                return None;
+            }
+            let mut rest: Vec<f64> = values.into_iter().filter(|&v| v != candidate).collect();
+            rest.sort_by(|a, b| a.total_cmp(b));
+            let median = sorted_median(&rest);
+            let mut deviations: Vec<f64> = rest.iter().map(|v| (v - median).abs()).collect();
+            deviations.sort_by(|a, b| a.total_cmp(b));
+            let mad = sorted_median(&deviations);
+            let far = if mad > 0.0 {
+                (candidate - median).abs() / (1.4826 * mad) > SENTINEL_MIN_ROBUST_Z
+            } else {
+                candidate < rest[0] || candidate > rest[rest.len() - 1]
+            };
+            far.then_some((candidate, count))
+        })
+        .collect())
+}
+
+const GRANULARITY_MIN_VALUES: usize = 20;
+const GRANULARITY_STEPS: [f64; 14] = [
+    1000.0, 500.0, 100.0, 50.0, 25.0, 10.0, 5.0, 2.0, 1.0, 0.5, 0.25, 0.1, 0.05, 0.01,
+];
+const GRANULARITY_MAX_DECIMALS: i32 = 6;
+const HEAPING_MIN_RATIO: f64 = 2.5;
+
+/// Suspiciously coarse values in a numeric column, a sign of rounding or data entry.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Granularity {
+    /// Every value is a multiple of this step, which is coarser than the decimals
+    /// the values are written with (e.g. 5, or 1 in a float column).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<f64>,
+    /// A round number the values pile up on, with the share of values on its multiples.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heaping: Option<(f64, f64)>,
+}
+
+fn is_multiple(value: f64, step: f64) -> bool {
+    let ratio = value / step;
+    (ratio - ratio.round()).abs() < 1e-6
+}
+
+// The finest unit the column is written in: 1 for integers, 0.01 for `12.50`.
+fn written_unit(rows: &[Vec<String>], col_idx: usize) -> f64 {
+    let decimals = rows
+        .iter()
+        .map(|row| row[col_idx].trim())
+        .filter(|val| parse_finite(val).is_some())
+        .map(|val| match val.split_once('.') {
+            _ if val.contains(['e', 'E']) => GRANULARITY_MAX_DECIMALS,
+            Some((_, fraction)) => (fraction.len() as i32).min(GRANULARITY_MAX_DECIMALS),
+            None => 0,
+        })
+        .max()
+        .unwrap_or(0);
+    10f64.powi(-decimals)
+}
+
+// Per numeric column: the coarsest candidate step dividing every value when it is
+// coarser than the written unit, and heaping on multiples of 10x or 5x the finest
+// grain when those take HEAPING_MIN_RATIO times their expected share (the
+// stronger of the two is kept).
+pub fn detect_granularity(
+    dataset: &Dataset,
+    types: &[TypeInference],
+) -> Result<Vec<Option<Granularity>>, PrestoError> {
+    Ok((0..dataset.headers.len())
+        .into_par_iter()
+        .map(|col_idx| {
+            if !matches!(
+                types[col_idx],
+                TypeInference::Integer | TypeInference::Float
+            ) {
+                return None;
+            }
+            let values: Vec<f64> = dataset
+                .rows
+                .iter()
+                .filter_map(|row| parse_finite(&row[col_idx]))
+                .collect();
+            let (min, max) = values
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                    (lo.min(v), hi.max(v))
+                });
+            if values.len() < GRANULARITY_MIN_VALUES || min == max {
+                return None;
+            }
+            let unit = written_unit(&dataset.rows, col_idx);
+            let step = GRANULARITY_STEPS
+                .into_iter()
+                .filter(|&step| step > unit * (1.0 + 1e-9))
+                .find(|&step| values.iter().all(|&v| is_multiple(v, step)));
+            let grain = step.unwrap_or(unit);
+            let heaping = [10.0, 5.0]
+                .into_iter()
+                .filter_map(|factor| {
+                    let round = grain * factor;
+                    if max - min < 3.0 * round {
+                        return None;
+                    }
+                    let share = values.iter().filter(|&&v| is_multiple(v, round)).count() as f64
+                        / values.len() as f64;
+                    let ratio = share * factor;
+                    (ratio >= HEAPING_MIN_RATIO).then_some((ratio, round, share))
+                })
+                .max_by(|a, b| a.0.total_cmp(&b.0))
+                .map(|(_, round, share)| (round, share));
+            (step.is_some() || heaping.is_some()).then_some(Granularity { step, heaping })
+        })
+        .collect())
+}
+
+const LIST_TOP_ELEMENTS: usize = 10;
+
+/// Profile of a column whose cells hold delimited lists such as `red;green;blue`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ListStats {
+    pub delimiter: String,
+    /// Non-missing cells, each read as one list.
+    pub lists: usize,
+    pub min_length: usize,
+    pub max_length: usize,
+    pub mean_length: f64,
+    pub distinct_elements: usize,
+    /// The most common elements with the number of lists containing each.
+    pub top_elements: Vec<(String, usize)>,
+}
+
+/// Splits each cell on `delimiter`, trimming elements and dropping empty ones, and
+/// counts every element once per list it appears in.
+pub fn compute_list_stats(
+    rows: &[Vec<String>],
+    col_idx: usize,
+    delimiter: &str,
+) -> Option<ListStats> {
+    let mut lengths: Vec<usize> = Vec::new();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for row in rows {
+        let val = &row[col_idx];
+        if val.is_empty() || val == "NA" {
+            continue;
+        }
+        let mut elements: Vec<&str> = val
+            .split(delimiter)
+            .map(str::trim)
+            .filter(|element| !element.is_empty())
+            .collect();
+        lengths.push(elements.len());
+        elements.sort_unstable();
+        elements.dedup();
+        for element in elements {
+            *counts.entry(element).or_insert(0) += 1;
+        }
+    }
+    if lengths.is_empty() {
+        return None;
+    }
+    let mut top_elements: Vec<(String, usize)> = counts
+        .iter()
+        .map(|(&element, &count)| (element.to_string(), count))
+        .collect();
+    top_elements.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top_elements.truncate(LIST_TOP_ELEMENTS);
+    Some(ListStats {
+        delimiter: delimiter.to_string(),
+        lists: lengths.len(),
+        min_length: lengths.iter().copied().min().unwrap_or(0),
+        max_length: lengths.iter().copied().max().unwrap_or(0),
+        mean_length: lengths.iter().sum::<usize>() as f64 / lengths.len() as f64,
+        distinct_elements: counts.len(),
+        top_elements,
+    })
+}
+
 pub fn compute_stats(rows: &[Vec<String>], col_idx: usize) -> Result<ColumnStats, PrestoError> {
     let values: Vec<f64> = rows
         .par_iter()
@@ -23,7 +275,7 @@ pub fn compute_stats(rows: &[Vec<String>], col_idx: usize) -> Result<ColumnStats
             if row[col_idx].is_empty() || row[col_idx] == "NA" {
                 None
             } else {
-                f64::from_str(&row[col_idx]).ok()
+                parse_finite(&row[col_idx])
             }
         })
         .collect();
@@ -32,30 +284,33 @@ pub fn compute_stats(rows: &[Vec<String>], col_idx: usize) -> Result<ColumnStats
         return Ok(ColumnStats {
             mean: None,
             median: None,
+            q1: None,
+            q3: None,
             min: None,
             max: None,
             std_dev: None,
             variance: None,
+            mad: None,
             skewness: None,
             kurtosis: None,
         });
     }
 
     let data = Data::new(values.clone());
-    let mean = Some(data.mean().unwrap());
+    let mean_val = data.mean().unwrap();
+    let mean = Some(mean_val);
     let mut sorted = values.clone();
     sorted.par_sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
-    let median = Some(if sorted.len() % 2 == 0 {
-        (sorted[sorted.len() / 2 - 1] + sorted[sorted.len() / 2]) / 2.0
-    } else {
-        sorted[sorted.len() / 2]
-    });
+    let median_val = sorted_median(&sorted);
+    let median = Some(median_val);
+    let mut deviations: Vec<f64> = sorted.iter().map(|x| (x - median_val).abs()).collect();
+    deviations.par_sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+    let mad = Some(sorted_median(&deviations));
     let min = Some(*sorted.first().unwrap());
     let max = Some(*sorted.last().unwrap());
-    let std_dev = Some(data.std_dev().unwrap_or(0.0));
+    let std_dev_val = data.std_dev().unwrap_or(0.0);
+    let std_dev = Some(std_dev_val);
     let n = values.len() as f64;
-    let mean_val = mean.unwrap();
-    let std_dev_val = std_dev.unwrap();
     let variance = std_dev.map(|s| s.powi(2));
     let skewness = if std_dev_val > 0.0 {
         let skew_sum: f64 = values
@@ -79,45 +334,296 @@ pub fn compute_stats(rows: &[Vec<String>], col_idx: usize) -> Result<ColumnStats
     Ok(ColumnStats {
         mean,
         median,
+        q1: Some(quantile(&sorted, 0.25)),
+        q3: Some(quantile(&sorted, 0.75)),
         min,
         max,
         std_dev,
         variance,
+        mad,
         skewness,
         kurtosis,
     })
 }
 
+/// Location and spread (mean, sample std dev, median, MAD) of a small window of
+/// values, enough for `AnomalyMethod::score`; the shape fields are left empty.
+pub(crate) fn window_stats(values: &[f64]) -> ColumnStats {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let std_dev = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable_by(|a, b| a.total_cmp(b));
+    let median = sorted_median(&sorted);
+    let mut deviations: Vec<f64> = sorted.iter().map(|v| (v - median).abs()).collect();
+    deviations.sort_unstable_by(|a, b| a.total_cmp(b));
+    ColumnStats {
+        mean: Some(mean),
+        median: Some(median),
+        q1: None,
+        q3: None,
+        min: None,
+        max: None,
+        std_dev: Some(std_dev),
+        variance: Some(std_dev.powi(2)),
+        mad: Some(sorted_median(&deviations)),
+        skewness: None,
+        kurtosis: None,
+    }
+}
+
+pub fn compute_stats_as(
+    rows: &[Vec<String>],
+    col_idx: usize,
+    column_type: &TypeInference,
+    encoding: &BooleanEncoding,
+) -> Result<ColumnStats, PrestoError> {
+    let numeric = |val: &str| -> Option<String> {
+        match column_type {
+            TypeInference::Integer => val.parse::<i128>().ok().map(|n| n.to_string()),
+            TypeInference::Float | TypeInference::Mixed => {
+                val.parse::<f64>().ok().map(|v| v.to_string())
+            }
+            TypeInference::Boolean => encoding
+                .parse(val)
+                .map(|b| if b { "1" } else { "0" }.to_string()),
+            TypeInference::Date => {
+                parse_date(val).map(|d| (d.and_utc().timestamp() as f64 / 86_400.0).to_string())
+            }
+            TypeInference::String | TypeInference::Categorical => None,
+        }
+    };
+    let converted: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| vec![numeric(&row[col_idx]).unwrap_or_default()])
+        .collect();
+    compute_stats(&converted, 0)
+}
+
+/// Each row's finite value in `col_idx`, `None` where the cell does not hold one.
+pub(crate) fn row_values(rows: &[Vec<String>], col_idx: usize) -> Vec<Option<f64>> {
+    rows.iter().map(|row| parse_finite(&row[col_idx])).collect()
+}
+
+/// The values of two columns from the rows where both are present, so pairwise
+/// statistics compare a row with itself even when the columns miss different rows.
+pub(crate) fn paired_values(xs: &[Option<f64>], ys: &[Option<f64>]) -> (Vec<f64>, Vec<f64>) {
+    xs.iter()
+        .zip(ys)
+        .filter_map(|(&x, &y)| Some((x?, y?)))
+        .unzip()
+}
+
+pub fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - lower as f64)
+}
+
+// Biweight midcorrelation terms: deviations from the median, downweighted by
+// (1 - u^2)^2 with u = (x - median) / (9 * MAD) and zeroed beyond |u| >= 1, then
+// scaled to unit length so bicor is a dot product. A zero MAD falls back to
+// plain mean-centred (Pearson) terms, as in WGCNA.
+pub(crate) fn bicor_terms(values: &[f64]) -> Vec<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let median = sorted_median(&sorted);
+    let mut deviations: Vec<f64> = sorted.iter().map(|x| (x - median).abs()).collect();
+    deviations.sort_by(|a, b| a.total_cmp(b));
+    let mad = sorted_median(&deviations);
+    let terms: Vec<f64> = if mad > 0.0 {
+        values
+            .iter()
+            .map(|&x| {
+                let u = (x - median) / (9.0 * mad);
+                if u.abs() < 1.0 {
+                    (x - median) * (1.0 - u * u).powi(2)
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    } else {
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        values.iter().map(|&x| x - mean).collect()
+    };
+    let norm = terms.iter().map(|t| t * t).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        terms.iter().map(|t| t / norm).collect()
+    } else {
+        terms
+    }
+}
+
+fn sorted_median(sorted: &[f64]) -> f64 {
+    if sorted.len().is_multiple_of(2) {
+        (sorted[sorted.len() / 2 - 1] + sorted[sorted.len() / 2]) / 2.0
+    } else {
+        sorted[sorted.len() / 2]
+    }
+}
+
+fn weighted_median(sorted_pairs: &[(f64, f64)]) -> f64 {
+    weighted_quantile(sorted_pairs, 0.5)
+}
+
+fn weighted_quantile(sorted_pairs: &[(f64, f64)], q: f64) -> f64 {
+    let target = sorted_pairs.iter().map(|&(_, w)| w).sum::<f64>() * q.clamp(0.0, 1.0);
+    let mut cumulative = 0.0;
+    for (i, &(x, w)) in sorted_pairs.iter().enumerate() {
+        cumulative += w;
+        if cumulative > target {
+            return x;
+        }
+        if cumulative == target {
+            return (x + sorted_pairs.get(i + 1).map_or(x, |&(next, _)| next)) / 2.0;
+        }
+    }
+    sorted_pairs[sorted_pairs.len() - 1].0
+}
+
+pub fn compute_weighted_stats(
+    rows: &[Vec<String>],
+    col_idx: usize,
+    weight_idx: usize,
+) -> Result<ColumnStats, PrestoError> {
+    let mut pairs: Vec<(f64, f64)> = rows
+        .par_iter()
+        .filter_map(|row| {
+            if row[col_idx].is_empty() || row[col_idx] == "NA" {
+                return None;
+            }
+            let value = parse_finite(&row[col_idx])?;
+            let weight = parse_finite(&row[weight_idx])?;
+            if weight > 0.0 {
+                Some((value, weight))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if pairs.is_empty() {
+        return compute_stats(&[], col_idx);
+    }
+
+    pairs.par_sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let total_weight: f64 = pairs.iter().map(|&(_, w)| w).sum();
+    let mean_val = pairs.iter().map(|&(x, w)| x * w).sum::<f64>() / total_weight;
+    let median_val = weighted_median(&pairs);
+    let mut deviations: Vec<(f64, f64)> = pairs
+        .iter()
+        .map(|&(x, w)| ((x - median_val).abs(), w))
+        .collect();
+    deviations.par_sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let mad = Some(weighted_median(&deviations));
+    let min = Some(pairs[0].0);
+    let max = Some(pairs[pairs.len() - 1].0);
+    let variance_val = if total_weight > 1.0 {
+        pairs
+            .iter()
+            .map(|&(x, w)| w * (x - mean_val).powi(2))
+            .sum::<f64>()
+            / (total_weight - 1.0)
+    } else {
+        0.0
+    };
+    let std_dev_val = variance_val.sqrt();
+    let weighted_moment = |power: i32| {
+        pairs
+            .iter()
+            .map(|&(x, w)| w * ((x - mean_val) / std_dev_val).powi(power))
+            .sum::<f64>()
+            / total_weight
+    };
+    let skewness = if std_dev_val > 0.0 {
+        Some(weighted_moment(3))
+    } else {
+        None
+    };
+    let kurtosis = if std_dev_val > 0.0 {
+        Some(weighted_moment(4) - 3.0)
+    } else {
+        None
+    };
+
+    Ok(ColumnStats {
+        mean: Some(mean_val),
+        median: Some(median_val),
+        q1: Some(weighted_quantile(&pairs, 0.25)),
+        q3: Some(weighted_quantile(&pairs, 0.75)),
+        min,
+        max,
+        std_dev: Some(std_dev_val),
+        variance: Some(variance_val),
+        mad,
+        skewness,
+        kurtosis,
+    })
+}
+
+pub fn compute_stability(
+    dataset: &Dataset,
+    folds: usize,
+) -> Result<Vec<Option<(f64, f64)>>, PrestoError> {
+    if folds < 2 || folds > dataset.rows.len() {
+        return Err(PrestoError::InvalidNumeric(format!(
+            "Stability needs between 2 and {} folds, got {}",
+            dataset.rows.len(),
+            folds
+        )));
+    }
+    let fold_size = dataset.rows.len().div_ceil(folds);
+    let num_cols = dataset.headers.len();
+    (0..num_cols)
+        .into_par_iter()
+        .map(|col_idx| {
+            let fold_stats = dataset
+                .rows
+                .chunks(fold_size)
+                .map(|fold| compute_stats(fold, col_idx))
+                .collect::<Result<Vec<_>, _>>()?;
+            let means: Vec<f64> = fold_stats.iter().filter_map(|s| s.mean).collect();
+            let std_devs: Vec<f64> = fold_stats.iter().filter_map(|s| s.std_dev).collect();
+            if means.len() < 2 {
+                return Ok(None);
+            }
+            let mean_variance = Data::new(means).variance().unwrap_or(0.0);
+            let std_variance = Data::new(std_devs).variance().unwrap_or(0.0);
+            Ok(Some((mean_variance, std_variance)))
+        })
+        .collect::<Result<Vec<_>, _>>()
+}
+
 pub fn compute_dependency_scores(
     dataset: &Dataset,
     stats: &[ColumnStats],
+    excluded: &[usize],
 ) -> Result<Vec<f64>, PrestoError> {
     let num_cols = dataset.headers.len();
     let mut scores = vec![0.0; num_cols];
+    let participating = num_cols.saturating_sub(excluded.len());
+    // A lone participating column has nothing to depend on (and would divide 0 by 0).
+    if participating < 2 {
+        return Ok(scores);
+    }
 
+    let values: Vec<Vec<Option<f64>>> = (0..num_cols)
+        .into_par_iter()
+        .map(|i| row_values(&dataset.rows, i))
+        .collect();
     for i in 0..num_cols {
-        let mut total_impact = 0.0;
-        let col_values: Vec<f64> = dataset
-            .rows
-            .par_iter()
-            .filter_map(|row| row[i].parse::<f64>().ok())
-            .collect();
-
-        if col_values.is_empty() {
+        if excluded.contains(&i) {
             continue;
         }
-
+        let mut total_impact = 0.0;
         for j in 0..num_cols {
-            if i == j {
+            if i == j || excluded.contains(&j) {
                 continue;
             }
-            let other_values: Vec<f64> = dataset
-                .rows
-                .par_iter()
-                .filter_map(|row| row[j].parse::<f64>().ok())
-                .collect();
-
-            if other_values.len() != col_values.len() {
+            let (col_values, other_values) = paired_values(&values[i], &values[j]);
+            if col_values.is_empty() {
                 continue;
             }
 
@@ -129,7 +635,11 @@ pub fn compute_dependency_scores(
                         .map(|(x, y)| (x - mean_i) * (y - mean_j))
                         .sum::<f64>()
                         / col_values.len() as f64;
-                    cov / (std_i * std_j)
+                    if std_i > 0.0 && std_j > 0.0 {
+                        cov / (std_i * std_j)
+                    } else {
+                        0.0
+                    }
                 } else {
                     0.0
                 }
@@ -138,7 +648,7 @@ pub fn compute_dependency_scores(
             };
             total_impact += corr.abs();
         }
-        scores[i] = total_impact / (num_cols as f64 - 1.0);
+        scores[i] = total_impact / (participating - 1) as f64;
     }
     Ok(scores)
 }
@@ -151,39 +661,516 @@ pub fn detect_drift(dataset: &Dataset, stats: &[ColumnStats]) -> Result<Vec<f64>
     for col_idx in 0..num_cols {
         let first_half: Vec<f64> = dataset.rows[..mid]
             .par_iter()
-            .filter_map(|row| row[col_idx].parse::<f64>().ok())
+            .filter_map(|row| parse_finite(&row[col_idx]))
             .collect();
         let second_half: Vec<f64> = dataset.rows[mid..]
             .par_iter()
-            .filter_map(|row| row[col_idx].parse::<f64>().ok())
+            .filter_map(|row| parse_finite(&row[col_idx]))
             .collect();
 
         if first_half.is_empty() || second_half.is_empty() {
             continue;
         }
 
-        let first_data = Data::new(first_half.clone());
-        let second_data = Data::new(second_half.clone());
-        let first_mean = first_data.mean().unwrap_or(0.0);
-        let second_mean = second_data.mean().unwrap_or(0.0);
-        let drift = (first_mean - second_mean).abs() / stats[col_idx].std_dev.unwrap_or(1.0);
-        drift_scores[col_idx] = drift;
+        drift_scores[col_idx] = mean_shift(&first_half, &second_half, stats[col_idx].std_dev);
     }
     Ok(drift_scores)
 }
 
+/// Absolute difference of the two means in units of the whole column's standard deviation.
+fn mean_shift(first: &[f64], second: &[f64], std_dev: Option<f64>) -> f64 {
+    let first_mean = Data::new(first.to_vec()).mean().unwrap_or(0.0);
+    let second_mean = Data::new(second.to_vec()).mean().unwrap_or(0.0);
+    (first_mean - second_mean).abs() / std_dev.filter(|&s| s > 0.0).unwrap_or(1.0)
+}
+
+// Ordered so that divergence sums add up in the same order on every run.
+fn category_frequencies(rows: &[Vec<String>], col_idx: usize) -> BTreeMap<&str, f64> {
+    let mut counts: BTreeMap<&str, f64> = BTreeMap::new();
+    for row in rows {
+        let val = row[col_idx].as_str();
+        if !val.is_empty() && val != "NA" {
+            *counts.entry(val).or_insert(0.0) += 1.0;
+        }
+    }
+    let total: f64 = counts.values().sum();
+    counts.values_mut().for_each(|count| *count /= total);
+    counts
+}
+
+/// Jensen-Shannon divergence (base 2, so within 0..=1) between the category
+/// frequencies of the first and second half of the rows, for non-numeric columns.
+pub fn detect_categorical_drift(
+    dataset: &Dataset,
+    types: &[TypeInference],
+) -> Result<Vec<Option<f64>>, PrestoError> {
+    let mid = dataset.rows.len() / 2;
+    (0..dataset.headers.len())
+        .into_par_iter()
+        .map(|col_idx| {
+            if !matches!(
+                types[col_idx],
+                TypeInference::String | TypeInference::Categorical | TypeInference::Boolean
+            ) {
+                return Ok(None);
+            }
+            let first = category_frequencies(&dataset.rows[..mid], col_idx);
+            let second = category_frequencies(&dataset.rows[mid..], col_idx);
+            Ok(js_divergence(&first, &second))
+        })
+        .collect()
+}
+
+fn js_divergence(first: &BTreeMap<&str, f64>, second: &BTreeMap<&str, f64>) -> Option<f64> {
+    if first.is_empty() || second.is_empty() {
+        return None;
+    }
+    let kl_to_mixture = |p: &BTreeMap<&str, f64>, q: &BTreeMap<&str, f64>| -> f64 {
+        p.iter()
+            .map(|(value, &pv)| {
+                let m = (pv + q.get(value).copied().unwrap_or(0.0)) / 2.0;
+                pv * (pv / m).log2()
+            })
+            .sum()
+    };
+    let divergence = 0.5 * kl_to_mixture(first, second) + 0.5 * kl_to_mixture(second, first);
+    Some(divergence.clamp(0.0, 1.0))
+}
+
+/// One column of a [`CohortComparison`]. Numeric columns fill the mean-based fields,
+/// the others `divergence`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CohortColumn {
+    pub name: String,
+    pub means: Option<(f64, f64)>,
+    /// Difference of means over the column's standard deviation, as in [`detect_drift`].
+    pub drift: Option<f64>,
+    /// Cohen's d of the second cohort against the first (pooled standard deviation).
+    pub effect_size: Option<f64>,
+    /// Two-sided p-value of Welch's t-test.
+    pub p_value: Option<f64>,
+    /// Jensen-Shannon divergence of the category frequencies.
+    pub divergence: Option<f64>,
+}
+
+/// Every column profiled across the two values of a binary split column.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CohortComparison {
+    pub split_col: String,
+    /// The split column's two values, in order of first appearance.
+    pub cohorts: (String, String),
+    pub sizes: (usize, usize),
+    pub columns: Vec<CohortColumn>,
+}
+
+impl CohortComparison {
+    /// One line per column, for printing.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "{} = {} ({} rows) vs {} ({} rows)",
+            self.split_col, self.cohorts.0, self.sizes.0, self.cohorts.1, self.sizes.1
+        )];
+        let fmt = |value: Option<f64>, decimals: usize| {
+            value.map_or("-".to_string(), |v| format!("{:.*}", decimals, v))
+        };
+        for column in &self.columns {
+            lines.push(match column.means {
+                Some((first, second)) => format!(
+                    "{}: mean {:.4} vs {:.4}, drift {}, d {}, p {}",
+                    column.name,
+                    first,
+                    second,
+                    fmt(column.drift, 2),
+                    fmt(column.effect_size, 2),
+                    fmt(column.p_value, 4)
+                ),
+                None => format!(
+                    "{}: JS divergence {}",
+                    column.name,
+                    fmt(column.divergence, 3)
+                ),
+            });
+        }
+        lines
+    }
+}
+
+/// Splits the rows on the two values of `split_col` (rows missing it are dropped)
+/// and compares every other column between the cohorts: mean shift, effect size
+/// and Welch's t-test for numeric columns, category divergence for the rest.
+pub fn compare_cohorts(
+    dataset: &Dataset,
+    split_col: &str,
+) -> Result<CohortComparison, PrestoError> {
+    let split_idx = dataset.column_index(split_col)?;
+    let mut labels: Vec<&str> = Vec::new();
+    for row in &dataset.rows {
+        let val = row[split_idx].as_str();
+        if !val.is_empty() && val != "NA" && !labels.contains(&val) {
+            labels.push(val);
+            if labels.len() > 2 {
+                break;
+            }
+        }
+    }
+    let [first_label, second_label] = labels[..] else {
+        return Err(PrestoError::SchemaMismatch(format!(
+            "`{}` must hold exactly two values to split cohorts",
+            split_col
+        )));
+    };
+    let cohort = |label: &str| -> Vec<Vec<String>> {
+        dataset
+            .rows
+            .iter()
+            .filter(|row| row[split_idx] == label)
+            .cloned()
+            .collect()
+    };
+    let (first_rows, second_rows) = (cohort(first_label), cohort(second_label));
+    let both: Vec<Vec<String>> = first_rows.iter().chain(&second_rows).cloned().collect();
+
+    let columns = (0..dataset.headers.len())
+        .into_par_iter()
+        .filter(|&col_idx| col_idx != split_idx)
+        .map(|col_idx| {
+            let name = dataset.headers[col_idx].clone();
+            let numeric = matches!(
+                crate::types::infer_type(&both, col_idx),
+                TypeInference::Integer | TypeInference::Float
+            );
+            let values = |rows: &[Vec<String>]| -> Vec<f64> {
+                rows.iter()
+                    .filter_map(|row| parse_finite(&row[col_idx]))
+                    .collect()
+            };
+            let (first, second) = (values(&first_rows), values(&second_rows));
+            if !numeric || first.is_empty() || second.is_empty() {
+                return Ok(CohortColumn {
+                    name,
+                    means: None,
+                    drift: None,
+                    effect_size: None,
+                    p_value: None,
+                    divergence: js_divergence(
+                        &category_frequencies(&first_rows, col_idx),
+                        &category_frequencies(&second_rows, col_idx),
+                    ),
+                });
+            }
+            let std_dev = compute_stats(&both, col_idx)?.std_dev;
+            let summary = |values: &[f64]| {
+                let data = Data::new(values.to_vec());
+                (
+                    data.mean().unwrap_or(0.0),
+                    data.variance().unwrap_or(f64::NAN),
+                )
+            };
+            let ((m1, v1), (m2, v2)) = (summary(&first), summary(&second));
+            let (n1, n2) = (first.len() as f64, second.len() as f64);
+            let pooled = (((n1 - 1.0) * v1 + (n2 - 1.0) * v2) / (n1 + n2 - 2.0)).sqrt();
+            let effect_size = (pooled > 0.0).then(|| (m2 - m1) / pooled);
+            let standard_error = (v1 / n1 + v2 / n2).sqrt();
+            let p_value = (standard_error > 0.0)
+                .then(|| {
+                    let t = (m2 - m1) / standard_error;
+                    let df = (v1 / n1 + v2 / n2).powi(2)
+                        / ((v1 / n1).powi(2) / (n1 - 1.0) + (v2 / n2).powi(2) / (n2 - 1.0));
+                    let dist = StudentsT::new(0.0, 1.0, df).ok()?;
+                    Some(2.0 * (1.0 - dist.cdf(t.abs())))
+                })
+                .flatten();
+            Ok(CohortColumn {
+                name,
+                means: Some((m1, m2)),
+                drift: Some(mean_shift(&first, &second, std_dev)),
+                effect_size,
+                p_value,
+                divergence: None,
+            })
+        })
+        .collect::<Result<Vec<_>, PrestoError>>()?;
+    Ok(CohortComparison {
+        split_col: split_col.to_string(),
+        cohorts: (first_label.to_string(), second_label.to_string()),
+        sizes: (first_rows.len(), second_rows.len()),
+        columns,
+    })
+}
+
+const ETA_MAX_GROUPS: usize = 50;
+const ETA_MIN_REPORTED: f64 = 0.1;
+
+/// Correlation ratio η = sqrt(between-group / total sum of squares) of each numeric
+/// column grouped by each categorical column with 2..=50 distinct values, as
+/// `(categorical, numeric, eta)` sorted by η descending. Pairs below 0.1 are dropped.
+pub fn compute_correlation_ratios(
+    dataset: &Dataset,
+    types: &[TypeInference],
+    stats: &[ColumnStats],
+    excluded: &[usize],
+) -> Result<Vec<(usize, usize, f64)>, PrestoError> {
+    let num_cols = dataset.headers.len();
+    let numeric_cols: Vec<usize> = (0..num_cols)
+        .filter(|&i| {
+            matches!(types[i], TypeInference::Integer | TypeInference::Float)
+                && stats[i].std_dev.is_some_and(|s| s > 0.0)
+                && !excluded.contains(&i)
+        })
+        .collect();
+    let mut ratios: Vec<(usize, usize, f64)> = (0..num_cols)
+        .into_par_iter()
+        .filter(|&i| {
+            matches!(
+                types[i],
+                TypeInference::String | TypeInference::Categorical | TypeInference::Boolean
+            ) && !excluded.contains(&i)
+        })
+        .flat_map_iter(|cat| {
+            let mut group_ids: HashMap<&str, usize> = HashMap::new();
+            let groups: Vec<Option<usize>> = dataset
+                .rows
+                .iter()
+                .map(|row| {
+                    let val = row[cat].as_str();
+                    if val.is_empty() || val == "NA" || group_ids.len() > ETA_MAX_GROUPS {
+                        return None;
+                    }
+                    let next = group_ids.len();
+                    Some(*group_ids.entry(val).or_insert(next))
+                })
+                .collect();
+            let num_groups = group_ids.len();
+            let usable = (2..=ETA_MAX_GROUPS).contains(&num_groups);
+            numeric_cols
+                .iter()
+                .filter(move |_| usable)
+                .filter_map(move |&num| {
+                    let mut sums = vec![0.0; num_groups];
+                    let mut counts = vec![0usize; num_groups];
+                    let mut values = Vec::new();
+                    for (row, group) in dataset.rows.iter().zip(&groups) {
+                        if let (Some(group), Some(v)) = (group, parse_finite(&row[num])) {
+                            sums[*group] += v;
+                            counts[*group] += 1;
+                            values.push(v);
+                        }
+                    }
+                    if values.is_empty() {
+                        return None;
+                    }
+                    let mean = values.iter().sum::<f64>() / values.len() as f64;
+                    let total: f64 = values.iter().map(|v| (v - mean).powi(2)).sum();
+                    if total <= 0.0 {
+                        return None;
+                    }
+                    let between: f64 = sums
+                        .iter()
+                        .zip(&counts)
+                        .filter(|&(_, &n)| n > 0)
+                        .map(|(&sum, &n)| n as f64 * (sum / n as f64 - mean).powi(2))
+                        .sum();
+                    let eta = (between / total).sqrt().min(1.0);
+                    (eta >= ETA_MIN_REPORTED).then_some((cat, num, eta))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    ratios.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(ratios)
+}
+
+/// `(column, [(other, coefficient)], r_squared)`.
+pub(crate) type LinearDependency = (usize, Vec<(usize, f64)>, f64);
+
+const LINEAR_MIN_R_SQUARED: f64 = 0.999;
+const LINEAR_MIN_COEFFICIENT: f64 = 1e-3;
+
+/// Numeric columns that a linear combination of earlier numeric columns predicts with
+/// R² >= 0.999, as `(column, [(other, coefficient)], r_squared)`. Uses rows where every
+/// candidate column is present; coefficients are in the columns' own units and the
+/// intercept is omitted. Modified Gram-Schmidt over the standardized columns: a column
+/// whose residual after projecting out the earlier ones is ~0 is flagged and left out
+/// of the basis, so each redundant set reports its last member.
+pub fn detect_linear_combinations(
+    dataset: &Dataset,
+    types: &[TypeInference],
+    stats: &[ColumnStats],
+    excluded: &[usize],
+) -> Result<Vec<LinearDependency>, PrestoError> {
+    let candidates: Vec<usize> = (0..dataset.headers.len())
+        .filter(|&i| {
+            matches!(types[i], TypeInference::Integer | TypeInference::Float)
+                && stats[i].std_dev.is_some_and(|s| s > 0.0)
+                && !excluded.contains(&i)
+        })
+        .collect();
+    let complete: Vec<Vec<f64>> = dataset
+        .rows
+        .iter()
+        .filter_map(|row| candidates.iter().map(|&i| parse_finite(&row[i])).collect())
+        .collect();
+    if candidates.len() < 2 || complete.len() <= candidates.len() + 1 {
+        return Ok(Vec::new());
+    }
+
+    let mut means = Vec::with_capacity(candidates.len());
+    let mut scales = Vec::with_capacity(candidates.len());
+    // Orthonormal basis vectors, the column each came from, and its R column (the
+    // coefficients on earlier basis vectors with its own norm last).
+    let mut basis: Vec<Vec<f64>> = Vec::new();
+    let mut basis_cols: Vec<usize> = Vec::new();
+    let mut r_columns: Vec<Vec<f64>> = Vec::new();
+    let mut found = Vec::new();
+    for (c, &col) in candidates.iter().enumerate() {
+        let n = complete.len() as f64;
+        let mean = complete.iter().map(|row| row[c]).sum::<f64>() / n;
+        let scale = (complete
+            .iter()
+            .map(|row| (row[c] - mean).powi(2))
+            .sum::<f64>())
+        .sqrt();
+        means.push(mean);
+        scales.push(scale);
+        if scale == 0.0 {
+            continue;
+        }
+        let mut v: Vec<f64> = complete.iter().map(|row| (row[c] - mean) / scale).collect();
+        let mut r = Vec::with_capacity(basis.len() + 1);
+        for q in &basis {
+            let dot: f64 = q.iter().zip(&v).map(|(a, b)| a * b).sum();
+            v.iter_mut().zip(q).for_each(|(x, q)| *x -= dot * q);
+            r.push(dot);
+        }
+        let residual = v.iter().map(|x| x * x).sum::<f64>();
+        let r_squared = (1.0 - residual).clamp(0.0, 1.0);
+        if r_squared >= LINEAR_MIN_R_SQUARED && !basis.is_empty() {
+            // Solve R β = r by back substitution to express the column in terms of the
+            // standardized basis columns, then rescale to raw units.
+            let k = basis.len();
+            let mut beta = vec![0.0; k];
+            for i in (0..k).rev() {
+                let tail: f64 = (i + 1..k).map(|j| r_columns[j][i] * beta[j]).sum();
+                beta[i] = (r[i] - tail) / r_columns[i][i];
+            }
+            let terms = basis_cols
+                .iter()
+                .zip(&beta)
+                .filter(|&(_, &weight)| weight.abs() >= LINEAR_MIN_COEFFICIENT)
+                .map(|(&b, &weight)| (candidates[b], weight * scale / scales[b]))
+                .collect();
+            found.push((col, terms, r_squared));
+            continue;
+        }
+        let norm = residual.sqrt();
+        v.iter_mut().for_each(|x| *x /= norm);
+        r.push(norm);
+        basis.push(v);
+        basis_cols.push(c);
+        r_columns.push(r);
+    }
+    Ok(found)
+}
+
+pub fn compute_drift_series(
+    dataset: &Dataset,
+    stats: &[ColumnStats],
+    segments: usize,
+) -> Result<Vec<Vec<f64>>, PrestoError> {
+    let segment_size = dataset.rows.len().div_ceil(segments.max(1)).max(1);
+    let num_cols = dataset.headers.len();
+    (0..num_cols)
+        .into_par_iter()
+        .map(|col_idx| {
+            let (Some(mean), Some(std_dev)) = (stats[col_idx].mean, stats[col_idx].std_dev) else {
+                return Ok(Vec::new());
+            };
+            let scale = if std_dev > 0.0 { std_dev } else { 1.0 };
+            dataset
+                .rows
+                .chunks(segment_size)
+                .map(|segment| {
+                    Ok(compute_stats(segment, col_idx)?
+                        .mean
+                        .map_or(0.0, |segment_mean| (segment_mean - mean) / scale))
+                })
+                .collect()
+        })
+        .collect()
+}
+
 pub fn compute_cardinality(dataset: &Dataset) -> Result<Vec<usize>, PrestoError> {
     let num_cols = dataset.headers.len();
     (0..num_cols)
         .into_par_iter()
         .map(|col_idx| {
-            let unique: std::collections::HashSet<&String> =
-                dataset.rows.iter().map(|row| &row[col_idx]).collect();
+            let unique: std::collections::HashSet<&String> = dataset
+                .rows
+                .iter()
+                .map(|row| &row[col_idx])
+                .filter(|val| !val.is_empty() && *val != "NA")
+                .collect();
             Ok(unique.len())
         })
         .collect::<Result<Vec<_>, _>>()
 }
 
+const HLL_PRECISION: u32 = 14;
+
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        HyperLogLog {
+            registers: vec![0; 1 << HLL_PRECISION],
+        }
+    }
+}
+
+impl HyperLogLog {
+    pub fn insert<T: Hash + ?Sized>(&mut self, value: &T) {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+        let idx = (hash >> (64 - HLL_PRECISION)) as usize;
+        let rank = ((hash << HLL_PRECISION).leading_zeros() + 1).min(64 - HLL_PRECISION + 1) as u8;
+        if rank > self.registers[idx] {
+            self.registers[idx] = rank;
+        }
+    }
+
+    pub fn estimate(&self) -> usize {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let harmonic: f64 = self.registers.iter().map(|&r| 2f64.powi(-(r as i32))).sum();
+        let raw = alpha * m * m / harmonic;
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        if raw <= 2.5 * m && zeros > 0 {
+            (m * (m / zeros as f64).ln()).round() as usize
+        } else {
+            raw.round() as usize
+        }
+    }
+}
+
+pub fn estimate_cardinality(dataset: &Dataset) -> Result<Vec<usize>, PrestoError> {
+    let num_cols = dataset.headers.len();
+    (0..num_cols)
+        .into_par_iter()
+        .map(|col_idx| {
+            let mut sketch = HyperLogLog::default();
+            for row in &dataset.rows {
+                if !row[col_idx].is_empty() && row[col_idx] != "NA" {
+                    sketch.insert(row[col_idx].as_str());
+                }
+            }
+            Ok(sketch.estimate())
+        })
+        .collect::<Result<Vec<_>, _>>()
+}
+
 pub fn compute_distribution(
     dataset: &Dataset,
     stats: &[ColumnStats],
@@ -195,7 +1182,7 @@ pub fn compute_distribution(
             let values: Vec<f64> = dataset
                 .rows
                 .par_iter()
-                .filter_map(|row| row[col_idx].parse::<f64>().ok())
+                .filter_map(|row| parse_finite(&row[col_idx]))
                 .collect();
             if values.is_empty() {
                 return Ok(vec![]);
@@ -222,7 +1209,6 @@ pub fn compute_distribution(
 }
 
 pub fn detect_temporal_patterns(dataset: &Dataset) -> Result<Vec<String>, PrestoError> {
-    use chrono::NaiveDateTime;
     let num_cols = dataset.headers.len();
     (0..num_cols)
         .into_par_iter()
@@ -236,10 +1222,7 @@ pub fn detect_temporal_patterns(dataset: &Dataset) -> Result<Vec<String>, Presto
             if values.is_empty() {
                 return Ok("None".to_string());
             }
-            let is_date = values.iter().all(|&v| {
-                NaiveDateTime::parse_from_str(v, "%Y-%m-%d %H:%M:%S").is_ok()
-                    || NaiveDateTime::parse_from_str(v, "%Y-%m-%d").is_ok()
-            });
+            let is_date = values.iter().all(|&v| parse_date(v).is_some());
             if is_date {
                 return Ok("Date-like".to_string());
             }
@@ -261,28 +1244,157 @@ pub fn detect_temporal_patterns(dataset: &Dataset) -> Result<Vec<String>, Presto
         .collect::<Result<Vec<_>, _>>()
 }
 
-pub fn suggest_transformations(stats: &[ColumnStats]) -> Result<Vec<String>, PrestoError> {
+pub fn detect_sequence_columns(dataset: &Dataset) -> Result<Vec<usize>, PrestoError> {
+    let num_cols = dataset.headers.len();
+    let flags: Vec<bool> = (0..num_cols)
+        .into_par_iter()
+        .map(|col_idx| {
+            let Ok(nums) = dataset
+                .rows
+                .iter()
+                .map(|row| row[col_idx].as_str())
+                .filter(|&v| !v.is_empty() && v != "NA")
+                .map(|v| v.parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+            else {
+                return false;
+            };
+            if nums.len() < 3 {
+                return false;
+            }
+            let step = nums[1] - nums[0];
+            step != 0.0
+                && nums
+                    .windows(2)
+                    .all(|w| ((w[1] - w[0]) - step).abs() <= 1e-9 * step.abs().max(1.0))
+        })
+        .collect();
+    Ok(flags
+        .into_iter()
+        .enumerate()
+        .filter(|&(_, is_sequence)| is_sequence)
+        .map(|(col_idx, _)| col_idx)
+        .collect())
+}
+
+const NORMALITY_MIN_VALUES: usize = 8;
+const NORMALITY_ALPHA: f64 = 0.05;
+
+/// Anderson-Darling test of the values against a normal distribution with their own
+/// mean and standard deviation, as `(A*², p-value)`. A*² carries the small-sample
+/// correction and the p-value follows D'Agostino & Stephens (1986); `None` below
+/// 8 values or without spread.
+pub fn anderson_darling(values: &[f64]) -> Option<(f64, f64)> {
+    if values.len() < NORMALITY_MIN_VALUES {
+        return None;
+    }
+    let data = Data::new(values.to_vec());
+    let (mean, std_dev) = (data.mean()?, data.std_dev().filter(|&s| s > 0.0)?);
+    let normal = Normal::new(mean, std_dev).ok()?;
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let n = sorted.len() as f64;
+    // Clamped so a far tail value cannot take the log of 0.
+    let cdf: Vec<f64> = sorted
+        .iter()
+        .map(|&v| normal.cdf(v).clamp(1e-300, 1.0 - 1e-16))
+        .collect();
+    let sum: f64 = cdf
+        .iter()
+        .zip(cdf.iter().rev())
+        .enumerate()
+        .map(|(i, (&low, &high))| (2.0 * i as f64 + 1.0) * (low.ln() + (1.0 - high).ln()))
+        .sum();
+    let a2 = (-n - sum / n) * (1.0 + 0.75 / n + 2.25 / (n * n));
+    let p_value = if a2 >= 0.6 {
+        (1.2937 - 5.709 * a2 + 0.0186 * a2 * a2).exp()
+    } else if a2 >= 0.34 {
+        (0.9177 - 4.279 * a2 - 1.38 * a2 * a2).exp()
+    } else if a2 >= 0.2 {
+        1.0 - (-8.318 + 42.796 * a2 - 59.938 * a2 * a2).exp()
+    } else {
+        1.0 - (-13.436 + 101.14 * a2 - 223.73 * a2 * a2).exp()
+    };
+    Some((a2, p_value.clamp(0.0, 1.0)))
+}
+
+/// [`anderson_darling`] for every Integer and Float column.
+pub fn compute_normality(
+    dataset: &Dataset,
+    types: &[TypeInference],
+) -> Result<Vec<Option<(f64, f64)>>, PrestoError> {
+    Ok((0..dataset.headers.len())
+        .into_par_iter()
+        .map(|col_idx| {
+            if !matches!(
+                types[col_idx],
+                TypeInference::Integer | TypeInference::Float
+            ) {
+                return None;
+            }
+            let values: Vec<f64> = dataset
+                .rows
+                .iter()
+                .filter_map(|row| parse_finite(&row[col_idx]))
+                .collect();
+            anderson_darling(&values)
+        })
+        .collect())
+}
+
+// A log transform is suggested for a skewed column unless the Anderson-Darling
+// test (when there are enough values for it) cannot reject normality.
+pub fn suggest_transformations(
+    stats: &[ColumnStats],
+    normality: &[Option<(f64, f64)>],
+    explain: bool,
+    thresholds: &ShapeThresholds,
+) -> Result<Vec<String>, PrestoError> {
     stats
         .par_iter()
-        .map(|stat| {
+        .zip(normality)
+        .map(|(stat, normality)| {
             if stat.mean.is_none() {
                 return Ok("None".to_string());
             }
             let mut suggestions = Vec::new();
-            if let Some(skew) = stat.skewness {
-                if skew.abs() > 1.0 {
-                    suggestions.push("Log transform (skewed)");
-                }
+            if let Some(skew) = stat.skewness
+                && thresholds.is_skewed(skew)
+                && normality.is_none_or(|(_, p)| p < NORMALITY_ALPHA)
+            {
+                suggestions.push(if explain {
+                    let test = normality
+                        .map(|(_, p)| {
+                            format!(", Anderson-Darling p {:.3} < {}", p, NORMALITY_ALPHA)
+                        })
+                        .unwrap_or_default();
+                    format!(
+                        "Log transform (skewed): |skewness| {:.2} > {:.1}{}",
+                        skew.abs(),
+                        thresholds.high_skew,
+                        test
+                    )
+                } else {
+                    "Log transform (skewed)".to_string()
+                });
             }
-            if let Some(min) = stat.min {
-                if min < 0.0 {
-                    suggestions.push("Shift positive");
-                }
+            if let Some(min) = stat.min
+                && min < 0.0
+            {
+                suggestions.push(if explain {
+                    format!("Shift positive: min {:.2} < 0.0", min)
+                } else {
+                    "Shift positive".to_string()
+                });
             }
-            if let (Some(min), Some(max)) = (stat.min, stat.max) {
-                if max - min > 100.0 {
-                    suggestions.push("Normalize");
-                }
+            if let (Some(min), Some(max)) = (stat.min, stat.max)
+                && max - min > 100.0
+            {
+                suggestions.push(if explain {
+                    format!("Normalize: range {:.2} > 100.0", max - min)
+                } else {
+                    "Normalize".to_string()
+                });
             }
             Ok(if suggestions.is_empty() {
                 "None".to_string()
@@ -293,16 +1405,20 @@ pub fn suggest_transformations(stats: &[ColumnStats]) -> Result<Vec<String>, Pre
         .collect::<Result<Vec<_>, _>>()
 }
 
-pub fn estimate_noise(dataset: &Dataset, stats: &[ColumnStats]) -> Result<Vec<f64>, PrestoError> {
+pub fn estimate_noise(
+    dataset: &Dataset,
+    stats: &[ColumnStats],
+    seed: Option<u64>,
+) -> Result<Vec<f64>, PrestoError> {
     let num_cols = dataset.headers.len();
     (0..num_cols)
         .into_par_iter()
         .map(|col_idx| {
-            let mut rng = rand::thread_rng();
+            let mut rng = crate::seeded_rng(seed, col_idx as u64);
             let values: Vec<f64> = dataset
                 .rows
                 .par_iter()
-                .filter_map(|row| row[col_idx].parse::<f64>().ok())
+                .filter_map(|row| parse_finite(&row[col_idx]))
                 .collect();
             if values.len() < 10 {
                 return Ok(0.0);
@@ -5,35 +5,505 @@ use crossterm::{
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Paragraph, Row, Table, TableState, Tabs},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Tabs, Wrap},
     Terminal,
 };
 use std::io;
-use crate::{Dataset, Description, PrestoError};
-use serde_json;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use crate::types::TypeInference;
+use crate::{ColumnStats, CorrelationMethod, Dataset, Description, OutputFormat, PrestoError, ShapeThresholds, cleaning, column_label};
+
+const EXPORT_STEM: &str = "presto_insights";
+const SNAPSHOT_PATH: &str = "presto_snapshot.svg";
+const STATUS_TIMEOUT: Duration = Duration::from_secs(4);
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+const LOADING_REFRESH: Duration = Duration::from_millis(100);
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const MIN_COLUMN_WIDTH: usize = 8;
+const MAX_PREVIEW_WIDTH: usize = 20;
+const DEFAULT_MAX_CELL_WIDTH: usize = 40;
+const CORR_DRILL_ROWS: usize = 10;
+const OUTLIER_THRESHOLD_STEP: f64 = 0.5;
+const MIN_OUTLIER_THRESHOLD: f64 = 1.0;
+
+type Reload<'a> = dyn FnMut() -> Result<Option<(Dataset, Description)>, PrestoError> + 'a;
+
+#[derive(Default)]
+struct ViewState {
+    tab_index: usize,
+    table_state: TableState,
+    table_h_scroll: usize,
+    corr_state: TableState,
+    corr_h_scroll: usize,
+    corr_column: Option<usize>,
+    details_v_scroll: u16,
+    details_h_scroll: u16,
+    advanced_v_scroll: u16,
+    advanced_h_scroll: u16,
+    plots_v_scroll: u16,
+    plots_h_scroll: u16,
+    status: Option<(String, Instant)>,
+    wrap_text: bool,
+    box_plots: bool,
+    data_state: TableState,
+    data_h_scroll: usize,
+    export_format: OutputFormat,
+    snapshot_pending: bool,
+    outlier_threshold: Option<f64>,
+    missing_sorted: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TuiOptions {
+    pub head_rows: usize,
+    pub tail_rows: usize,
+    pub export_format: OutputFormat,
+    /// Longest value, in characters, shown in any table cell or text line before it is cut with `…`.
+    pub max_cell_width: usize,
+}
+
+impl Default for TuiOptions {
+    fn default() -> Self {
+        TuiOptions {
+            head_rows: 10,
+            tail_rows: 10,
+            export_format: OutputFormat::Json,
+            max_cell_width: DEFAULT_MAX_CELL_WIDTH,
+        }
+    }
+}
 
 pub fn render_tui(dataset: &Dataset, description: &Description) -> Result<(), PrestoError> {
+    render_tui_with_options(dataset, description, &TuiOptions::default())
+}
+
+pub fn render_tui_with_options(dataset: &Dataset, description: &Description, options: &TuiOptions) -> Result<(), PrestoError> {
+    render_tui_with_reload(dataset, description, options, None)
+}
+
+pub fn render_tui_watch<F>(dataset: &Dataset, description: &Description, options: &TuiOptions, mut reload: F) -> Result<(), PrestoError>
+where
+    F: FnMut() -> Result<Option<(Dataset, Description)>, PrestoError>,
+{
+    render_tui_with_reload(dataset, description, options, Some(&mut reload))
+}
+
+/// Opens the TUI straight away with a progress screen while `analyze` runs on a
+/// background thread, then shows its result. `analyze` receives a callback for
+/// naming the current stage (see [`crate::describe_with_progress`]).
+pub fn render_tui_loading<A>(message: &str, options: &TuiOptions, analyze: A, reload: Option<&mut Reload>) -> Result<(), PrestoError>
+where
+    A: FnOnce(&(dyn Fn(&str) + Sync)) -> Result<(Dataset, Description), PrestoError> + Send,
+{
+    let mut terminal = init_terminal()?;
+    let analyzed = std::thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel::<String>();
+        let worker = scope.spawn(move || analyze(&move |stage: &str| {
+            let _ = sender.send(stage.to_string());
+        }));
+        let started = Instant::now();
+        let mut stage = String::new();
+        while !worker.is_finished() {
+            if let Some(latest) = receiver.try_iter().last() {
+                stage = latest;
+            }
+            draw_loading(&mut terminal, message, &stage, started.elapsed())?;
+            std::thread::sleep(LOADING_REFRESH);
+        }
+        Ok(worker.join())
+    });
+    let result = match analyzed {
+        Ok(Ok(Ok((dataset, description)))) => view_loop(&mut terminal, &dataset, &description, options, reload),
+        Ok(Ok(Err(e))) | Err(e) => Err(e),
+        Ok(Err(panic)) => {
+            restore_terminal(&mut terminal)?;
+            std::panic::resume_unwind(panic);
+        }
+    };
+    restore_terminal(&mut terminal)?;
+    result
+}
+
+fn draw_loading(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, message: &str, stage: &str, elapsed: Duration) -> Result<(), PrestoError> {
+    let spinner = SPINNER[(elapsed.as_millis() / LOADING_REFRESH.as_millis()) as usize % SPINNER.len()];
+    let mut lines = vec![Line::from(Span::styled(message, Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))), Line::from("")];
+    if !stage.is_empty() {
+        lines.push(Line::from(format!("{} {} ({:.1}s)", spinner, stage, elapsed.as_secs_f64())));
+    }
+    terminal.draw(|f| {
+        let area = centered_rect(60, 30, f.area());
+        let loading = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(Block::default()
+                .title("⚡ Presto")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .border_style(Style::default().fg(Color::Cyan)));
+        f.render_widget(loading, area);
+    }).map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
+    Ok(())
+}
+
+fn init_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>, PrestoError> {
     enable_raw_mode().map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen).map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
     let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend).map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
-    let mut tab_index = 0;
-    let mut table_state = TableState::default();
-    let mut table_h_scroll = 0usize;
-    let mut corr_state = TableState::default();
-    let mut corr_h_scroll = 0usize;
-    let mut details_v_scroll = 0u16;
-    let mut details_h_scroll = 0u16;
-    let mut advanced_v_scroll = 0u16;
-    let mut advanced_h_scroll = 0u16;
-    let mut plots_v_scroll = 0u16;
-    let mut plots_h_scroll = 0u16;
+    Terminal::new(backend).map_err(|e| PrestoError::InvalidNumeric(e.to_string()))
+}
+
+fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<(), PrestoError> {
+    disable_raw_mode().map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen).map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
+    terminal.show_cursor().map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
+    Ok(())
+}
+
+fn render_tui_with_reload(dataset: &Dataset, description: &Description, options: &TuiOptions, reload: Option<&mut Reload>) -> Result<(), PrestoError> {
+    let mut terminal = init_terminal()?;
+    let result = view_loop(&mut terminal, dataset, description, options, reload);
+    restore_terminal(&mut terminal)?;
+    result
+}
+
+fn view_loop(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, dataset: &Dataset, description: &Description, options: &TuiOptions, mut reload: Option<&mut Reload>) -> Result<(), PrestoError> {
+    let mut state = ViewState { export_format: options.export_format, ..ViewState::default() };
+    let mut reloaded: Option<(Dataset, Description)> = None;
+    loop {
+        let (dataset, description) = match &reloaded {
+            Some((dataset, description)) => (dataset, description),
+            None => (dataset, description),
+        };
+        match run_view(terminal, dataset, description, options, &mut state, reload.as_deref_mut())? {
+            Some(next) => reloaded = Some(next),
+            None => return Ok(()),
+        }
+    }
+}
+
+fn run_view(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    dataset: &Dataset,
+    description: &Description,
+    options: &TuiOptions,
+    state: &mut ViewState,
+    mut reload: Option<&mut Reload>,
+) -> Result<Option<(Dataset, Description)>, PrestoError> {
+    let max_cell = options.max_cell_width.max(1);
+    let full_headers: Vec<String> = dataset.headers.iter().zip(description.transforms.iter()).map(|(header, &transform)| column_label(header, transform)).collect();
+    let headers: Vec<String> = full_headers.iter().map(|header| clip(header, max_cell)).collect();
+    for table_state in [&mut state.table_state, &mut state.corr_state] {
+        clamp_selection(table_state, headers.len());
+    }
+    let total_rows = dataset.rows.len();
+    let (head_end, tail_start) = preview_bounds(total_rows, options);
+    let mut preview_rows: Vec<Option<usize>> = (0..head_end).map(Some).collect();
+    if tail_start > head_end {
+        preview_rows.push(None);
+    }
+    preview_rows.extend((tail_start..total_rows).map(Some));
+    if state.data_state.selected().is_some_and(|i| i >= preview_rows.len()) {
+        state.data_state.select(preview_rows.len().checked_sub(1));
+    }
+    let preview_header: Vec<String> = std::iter::once("#".to_string()).chain(dataset.headers.iter().map(|h| clip(h, max_cell))).collect();
+    let preview_cells: Vec<Vec<String>> = preview_rows.iter().map(|row| match row {
+        Some(idx) => std::iter::once((idx + 1).to_string()).chain(dataset.rows[*idx].iter().map(|v| clip(v, max_cell))).collect(),
+        None => vec!["…".to_string(); preview_header.len()],
+    }).collect();
+    let preview_preferred: Vec<usize> = (0..preview_header.len()).map(|col| {
+        preview_cells.iter().map(|cells| cells[col].chars().count())
+            .chain(std::iter::once(preview_header[col].chars().count()))
+            .max()
+            .unwrap_or(0)
+            .clamp(MIN_COLUMN_WIDTH, MAX_PREVIEW_WIDTH)
+    }).collect();
+    let data_title = if tail_start > head_end {
+        format!("Data (first {} and last {} of {} rows)", head_end, total_rows - tail_start, total_rows)
+    } else {
+        format!("Data (all {} rows)", total_rows)
+    };
+    let mut overlay: Option<(String, Vec<String>)> = None;
+    let mut palette: Option<String> = None;
+    let mut column_stats: Vec<ColumnStats> = description.stats.clone();
+    let mut column_types: Vec<TypeInference> = description.types.clone();
+    let thresholds = description.shape_thresholds();
+
+    // |z| of every value above MIN_OUTLIER_THRESHOLD per column, highest first; built on
+    // the first +/- so any threshold is a prefix of it.
+    let mut outlier_scores: Option<Vec<Vec<(usize, f64)>>> = None;
+    let mut outlier_counts: Vec<usize> = description.outlier_counts.clone();
+    // A threshold chosen before a --watch reload carries over to the new data.
+    if let Some(threshold) = state.outlier_threshold {
+        let scores = description.z_scores_above(dataset, MIN_OUTLIER_THRESHOLD);
+        outlier_counts = scores.iter().map(|col| col.partition_point(|&(_, z)| z > threshold)).collect();
+        outlier_scores = Some(scores);
+    }
 
+    let mut co_missing_pairs: Vec<(usize, usize, f64)> = Vec::new();
+    for (i, row) in description.co_missingness.iter().enumerate() {
+        for (j, &frac) in row.iter().enumerate().skip(i + 1) {
+            if frac > 0.0 && !description.empty_columns.contains(&i) && !description.empty_columns.contains(&j) {
+                co_missing_pairs.push((i, j, frac));
+            }
+        }
+    }
+    co_missing_pairs.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+    let co_missing_text = if co_missing_pairs.is_empty() {
+        "None".to_string()
+    } else {
+        co_missing_pairs.iter()
+            .take(5)
+            .map(|&(i, j, frac)| format!("{}<->{}:{:.2}", headers[i], headers[j], frac))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let drift_series_text = description.drift_series.iter().enumerate()
+        .filter(|(_, series)| !series.is_empty())
+        .map(|(i, series)| format!("{} {}", headers[i], sparkline(series)))
+        .collect::<Vec<_>>()
+        .join("  ");
+    let categorical_drift_text = description.categorical_drift.iter().enumerate()
+        .filter_map(|(i, js)| js.map(|js| format!("{}:{:.3}", headers[i], js)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let categorical_drift_text = if categorical_drift_text.is_empty() { "None".to_string() } else { categorical_drift_text };
+    let types_text = description.types.iter().enumerate().map(|(i, t)| {
+        let mut label = match description.integer_widths[i] {
+            Some(width) => format!("{:?}({})", t, width),
+            None => format!("{:?}", t),
+        };
+        if description.type_confidence[i] < 1.0 {
+            label.push_str(&format!(" ({:.1}% consistent)", description.type_confidence[i] * 100.0));
+        }
+        label
+    }).collect::<Vec<_>>().join(", ");
+    let dtypes_text = headers.iter().zip(description.recommended_dtype.iter()).map(|(h, d)| format!("{}: {}", h, d)).collect::<Vec<_>>().join(", ");
+    let gini_text = description.gini.iter().enumerate()
+        .filter(|&(i, _)| matches!(description.types[i], TypeInference::String | TypeInference::Categorical | TypeInference::Boolean | TypeInference::Mixed))
+        .map(|(i, g)| format!("{}:{:.2}", headers[i], g))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let whitespace_text = if description.whitespace_counts.iter().all(|&c| c == 0) {
+        "None".to_string()
+    } else {
+        description.whitespace_counts.iter().enumerate().filter(|&(_, &c)| c > 0).map(|(i, c)| format!("{}: {}", headers[i], c)).collect::<Vec<_>>().join(", ")
+    };
+    let non_finite_text = if description.non_finite_counts.iter().all(|&c| c == 0) {
+        "None".to_string()
+    } else {
+        description.non_finite_counts.iter().enumerate().filter(|&(_, &c)| c > 0).map(|(i, c)| format!("{}: {}", headers[i], c)).collect::<Vec<_>>().join(", ")
+    };
+    let sentinel_text = description.sentinels.iter().enumerate()
+        .filter_map(|(i, s)| s.map(|(value, count)| format!("{}: {} ({:.1}%)", headers[i], value, description.value_share(i, count))))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sentinel_text = if sentinel_text.is_empty() { "None".to_string() } else { sentinel_text };
+    let case_variant_text = description.case_variants.iter().enumerate()
+        .flat_map(|(i, groups)| { let header = &headers[i]; groups.iter().map(move |(canonical, variants)| format!("{}: {} -> {}", header, variants.iter().map(|(v, c)| format!("{:?} ({})", clip(v, max_cell), c)).collect::<Vec<_>>().join(" / "), clip(canonical, max_cell))) })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let case_variant_text = if case_variant_text.is_empty() { "None".to_string() } else { case_variant_text };
+    let granularity_text = description.granularity.iter().enumerate()
+        .filter_map(|(i, g)| g.map(|g| {
+            let mut parts = Vec::new();
+            if let Some(step) = g.step { parts.push(format!("all multiples of {}", step)); }
+            if let Some((round, share)) = g.heaping { parts.push(format!("{:.0}% on multiples of {}", share * 100.0, round)); }
+            format!("{}: {}", headers[i], parts.join(", "))
+        }))
+        .collect::<Vec<_>>()
+        .join("; ");
+    let granularity_text = if granularity_text.is_empty() { "None".to_string() } else { granularity_text };
+    let normality_text = description.normality.iter().enumerate()
+        .filter_map(|(i, n)| n.map(|(a2, p)| format!("{}: A² {:.3}, p {:.3}{}", headers[i], a2, p, if p < 0.05 { " (not normal)" } else { "" })))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let normality_text = if normality_text.is_empty() { "None".to_string() } else { normality_text };
+    let list_text = description.list_stats.iter().enumerate()
+        .filter_map(|(i, list)| list.as_ref().map(|list| format!("{} ({}): {}-{} items, mean {:.2}, {} distinct; {}", headers[i], list.delimiter, list.min_length, list.max_length, list.mean_length, list.distinct_elements,
+            list.top_elements.iter().take(5).map(|(element, count)| format!("{} {:.0}%", clip(element, max_cell), *count as f64 / list.lists as f64 * 100.0)).collect::<Vec<_>>().join(", "))))
+        .collect::<Vec<_>>()
+        .join(" | ");
+    let list_text = if list_text.is_empty() { "None".to_string() } else { list_text };
+    let semantic_text = if description.semantic_types.iter().all(Option::is_none) {
+        "None".to_string()
+    } else {
+        description.semantic_types.iter().enumerate()
+            .filter_map(|(i, s)| s.map(|s| if s.is_pii() { format!("{}: {} (PII)", headers[i], s) } else { format!("{}: {}", headers[i], s) }))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let (stats_title, corr_title) = match description.small_sample() {
+        Some(min) => (
+            format!("Statistics (only {} rows, below --min-rows {}: unreliable)", description.total_rows, min),
+            format!("Correlations (only {} rows, below --min-rows {}: unreliable)", description.total_rows, min),
+        ),
+        None => ("Statistics".to_string(), "Correlations".to_string()),
+    };
+    let corr_title = match description.correlation_method() {
+        CorrelationMethod::Pearson => corr_title,
+        method => format!("{} ({})", corr_title, method),
+    };
+    let five_number_text = (0..headers.len())
+        .filter_map(|i| description.five_number_summary(i).map(|(min, q1, median, q3, max)| format!("{}: {:.2} / {:.2} / {:.2} / {:.2} / {:.2}", headers[i], min, q1, median, q3, max)))
+        .collect::<Vec<_>>()
+        .join("; ");
+    let five_number_text = if five_number_text.is_empty() { "None".to_string() } else { five_number_text };
+    let top_values_text = description.top_values.iter().enumerate()
+        .map(|(i, (col, vals))| format!("{}: {}", clip(col, max_cell), vals.iter().map(|(v, c)| format!("{}({}, {:.0}%)", clip(v, max_cell), c, description.value_share(i, *c))).collect::<Vec<_>>().join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ");
+    let cols_text = match description.retained_columns() {
+        Some(kept) => format!("{} (limited from {} by --max-cols)", kept.len(), description.source_columns()),
+        None => headers.len().to_string(),
+    };
+    let duplicate_columns_text = if description.duplicate_columns.is_empty() {
+        "None".to_string()
+    } else {
+        description.duplicate_columns.iter().map(|&(i, j)| format!("{}=={}", headers[i], headers[j])).collect::<Vec<_>>().join(", ")
+    };
+    let empty_columns_text = if description.empty_columns.is_empty() {
+        "None".to_string()
+    } else {
+        description.empty_columns.iter().map(|&i| headers[i].clone()).collect::<Vec<_>>().join(", ")
+    };
+    let sequence_columns_text = if description.sequence_columns.is_empty() {
+        "None".to_string()
+    } else {
+        description.sequence_columns.iter().map(|&i| headers[i].clone()).collect::<Vec<_>>().join(", ")
+    };
+    let positive_rate_text = description.positive_rates.iter().enumerate()
+        .filter_map(|(i, rate)| rate.map(|r| format!("{}:{:.1}%", headers[i], r * 100.0)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let positive_rate_text = if positive_rate_text.is_empty() { "None".to_string() } else { positive_rate_text };
+    let parse_rate_text = description.parse_rate.iter().enumerate()
+        .filter_map(|(i, rate)| rate.map(|r| format!("{}:{:.1}%", headers[i], r * 100.0)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let parse_rate_text = if parse_rate_text.is_empty() { "None".to_string() } else { parse_rate_text };
+    let stability_text = if description.stability.is_empty() {
+        "Not computed (use --stability k)".to_string()
+    } else {
+        description.stability.iter().enumerate()
+            .filter_map(|(i, s)| s.map(|(mean_var, std_var)| format!("{}: mean var {:.3}, std var {:.3}", headers[i], mean_var, std_var)))
+            .collect::<Vec<_>>()
+            .join("; ")
+    };
+    let linear_text = match &description.linear_combinations {
+        None => "Not computed (use --linear-deps)".to_string(),
+        Some(found) if found.is_empty() => "None".to_string(),
+        Some(found) => found.iter()
+            .map(|(col, terms, r_squared)| {
+                let combination = terms.iter().map(|&(other, coefficient)| format!("{:+.3}*{}", coefficient, headers[other])).collect::<Vec<_>>().join(" ");
+                format!("{} ≈ {} (R² {:.4})", headers[*col], combination, r_squared)
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+    };
+    let cardinality_label = if description.cardinality_estimated { "Cardinality (est.): " } else { "Cardinality: " };
+    let anomalies_text = description.anomalies.iter().enumerate()
+        .map(|(i, (col, val, idx))| match description.anomaly_explanations.get(i) {
+            Some(why) => format!("{}:{} (idx {}, {})", headers[*col], val, idx, why),
+            None => format!("{}:{} (idx {})", headers[*col], val, idx),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let duplicates_label = match description.duplicate_key() {
+        [] => "Duplicates: ".to_string(),
+        key => format!("Duplicates (by {}): ", key.join(", ")),
+    };
+    let mut details_fields: Vec<(&str, Color, String)> = vec![
+        ("Rows: ", Color::Magenta, description.total_rows.to_string()),
+        ("Cols: ", Color::Magenta, cols_text),
+        ("Missing %: ", Color::Magenta, format!("{:.1}", description.missing_pct)),
+        ("Unique %: ", Color::Magenta, format!("{:.1}", description.unique_pct)),
+        ("Missing: ", Color::Magenta, description.missing.iter().map(|&m| m.to_string()).collect::<Vec<_>>().join(", ")),
+        (duplicates_label.as_str(), Color::Magenta, description.duplicates.to_string()),
+        ("Outliers: ", Color::Magenta, description.outlier_counts.iter().enumerate().map(|(i, c)| format!("{}: {}", headers[i], c)).collect::<Vec<_>>().join(", ")),
+        ("Types: ", Color::Magenta, types_text),
+        ("Recommended Dtypes: ", Color::Magenta, dtypes_text),
+        ("Semantic Types: ", Color::Magenta, semantic_text),
+        ("Positive Rate: ", Color::Magenta, positive_rate_text),
+        ("Parse Rate: ", Color::Magenta, parse_rate_text),
+        (cardinality_label, Color::Blue, description.cardinality.iter().map(|&c| c.to_string()).collect::<Vec<_>>().join(", ")),
+        ("Cardinality Ratio: ", Color::Blue, description.cardinality_ratio.iter().map(|&r| format!("{:.2}", r)).collect::<Vec<_>>().join(", ")),
+        ("Five-Number Summary: ", Color::Blue, five_number_text),
+        ("Distributions: ", Color::Blue, description.distributions.iter().map(|d| d.iter().map(|&(mid, cnt)| format!("{:.1}:{}", mid, cnt)).collect::<Vec<_>>().join("|")).collect::<Vec<_>>().join(", ")),
+        ("Top Values: ", Color::Blue, top_values_text),
+        ("Empty Columns: ", Color::Blue, empty_columns_text),
+        ("Sequence Columns: ", Color::Blue, sequence_columns_text),
+    ];
+    if !description.skipped_stages().is_empty() {
+        details_fields.insert(0, ("Not Computed (--timeout): ", Color::Red, description.skipped_stages().join(", ")));
+    }
+    let advanced_fields: Vec<(&str, Color, String)> = vec![
+        ("Dependency: ", Color::Green, description.dependency_scores.iter().map(|&s| format!("{:.2}", s)).collect::<Vec<_>>().join(", ")),
+        ("Drift: ", Color::Green, description.drift_scores.iter().map(|&s| format!("{:.2}", s)).collect::<Vec<_>>().join(", ")),
+        ("Drift Series: ", Color::Green, drift_series_text),
+        ("Categorical Drift (JS): ", Color::Green, categorical_drift_text),
+        ("Consistency Issues: ", Color::Red, description.consistency_issues.iter().map(|&i| i.to_string()).collect::<Vec<_>>().join(", ")),
+        ("Untrimmed Values: ", Color::Red, whitespace_text),
+        ("Non-finite (inf/NaN): ", Color::Red, non_finite_text),
+        ("Likely Sentinels: ", Color::Red, sentinel_text),
+        ("Case Variants: ", Color::Red, case_variant_text),
+        ("Rounded Values: ", Color::Red, granularity_text),
+        ("List Columns: ", Color::Yellow, list_text),
+        ("Temporal: ", Color::Red, description.temporal_patterns.join(", ")),
+        ("Normality (Anderson-Darling): ", Color::Red, normality_text),
+        ("Transforms: ", Color::Red, description.transform_suggestions.join(", ")),
+        ("Gini Impurity: ", Color::Yellow, gini_text),
+        ("Noise: ", Color::Yellow, description.noise_scores.iter().map(|&n| format!("{:.2}", n)).collect::<Vec<_>>().join(", ")),
+        ("Redundancy: ", Color::Yellow, if description.redundancy_pairs.is_empty() {
+            "None".to_string()
+        } else {
+            description.redundancy_pairs.iter()
+                .map(|&(i, j, s)| format!("{}<->{}:{:.2}", headers[i], headers[j], s))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }),
+        ("Correlation Ratio (η): ", Color::Green, if description.correlation_ratios.is_empty() {
+            "None".to_string()
+        } else {
+            description.correlation_ratios.iter()
+                .map(|&(cat, num, eta)| format!("{}->{}:{:.2}", headers[cat], headers[num], eta))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }),
+        ("Duplicate Columns: ", Color::Yellow, duplicate_columns_text),
+        ("Linear Combinations: ", Color::Yellow, linear_text),
+        ("Feature Importance: ", Color::Green, description.feature_importance.iter().map(|&(col, score)| format!("{}:{:.2}", headers[col], score)).collect::<Vec<_>>().join(", ")),
+        ("Anomalies: ", Color::Red, anomalies_text),
+        ("Co-Missing: ", Color::Yellow, co_missing_text),
+        ("Stability: ", Color::Yellow, stability_text),
+    ];
+    let details_strings: Vec<String> = details_fields.iter().map(|(label, _, value)| format!("{}{}", label, value)).collect();
+    let advanced_strings: Vec<String> = advanced_fields.iter().map(|(label, _, value)| format!("{}{}", label, value)).collect();
+    let details_width = details_strings.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+    let advanced_width = advanced_strings.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+    // The 'm' view of the Details tab: every column ranked by missing share, worst first.
+    let by_missing = columns_by_missing(&description.missing);
+    let missing_share = |i: usize| if description.total_rows > 0 { description.missing[i] as f64 / description.total_rows as f64 * 100.0 } else { 0.0 };
+    let missing_fields: Vec<(&str, Color, String)> = std::iter::once(("Columns by missingness: ", Color::Magenta, format!("{} of {} have missing values ('m' for all details)", description.missing.iter().filter(|&&m| m > 0).count(), headers.len())))
+        .chain(by_missing.iter().map(|&i| ("", if description.missing[i] > 0 { Color::Red } else { Color::White }, format!("{:>6.1}%  {:>8}  {}", missing_share(i), description.missing[i], headers[i]))))
+        .collect();
+    let missing_strings: Vec<String> = missing_fields.iter().map(|(label, _, value)| format!("{}{}", label, value)).collect();
+    let missing_width = missing_strings.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+
+    // Rebuilt only when the terminal height (and so the bar height) changes.
+    let mut histogram_cache: Option<(usize, Vec<Line<'static>>, usize)> = None;
     loop {
+        let (details_fields, details_strings, details_width) = if state.missing_sorted {
+            (&missing_fields, &missing_strings, missing_width)
+        } else {
+            (&details_fields, &details_strings, details_width)
+        };
         let size = terminal.size().map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
         let full_area = Rect::new(0, 0, size.width, size.height);
         let chunks = Layout::default()
@@ -43,539 +513,925 @@ pub fn render_tui(dataset: &Dataset, description: &Description) -> Result<(), Pr
                 Constraint::Length(3),  
                 Constraint::Length(3),  
                 Constraint::Min(10),    
+                Constraint::Length(1),  
                 Constraint::Length(3),  
             ])
             .split(full_area);
         let content_area = chunks[2];
         let content_height = content_area.height.saturating_sub(2) as usize;
         let content_width = content_area.width.saturating_sub(2) as usize;
+        let max_height = content_area.height.saturating_sub(4) as usize;
+        if histogram_cache.as_ref().is_none_or(|(height, _, _)| *height != max_height) {
+            let lines = histogram_lines(&headers, &description.distributions, max_height);
+            let width = lines.iter().map(Line::width).max().unwrap_or(0);
+            histogram_cache = Some((max_height, lines, width));
+        }
+        let Some((_, histogram, histogram_width)) = &histogram_cache else { unreachable!() };
+        let histogram_width = *histogram_width;
 
         let header_cells = vec![
-            "Column", "Mean", "Median", "StdDev", "Variance", "Min", "Max", "Skew", "Kurt",
+            "Column", "Dist", "Mean", "Median", "StdDev", "Variance", "MAD", "Min", "Max", "Skew", "Kurt", "Outliers",
         ];
-        let widths = [15usize, 10, 10, 10, 10, 10, 10, 10, 10];
-        let total_cols = header_cells.len();
-        let total_width: usize = widths.iter().sum();
+        let widths = fit_widths(&[15, 10, 10, 10, 10, 10, 10, 10, 10, 10, 10, 8], MIN_COLUMN_WIDTH, content_width);
+        let corr_widths = fit_widths(&vec![15; headers.len() + 1], MIN_COLUMN_WIDTH, content_width);
+        // The row-label column stays pinned; `corr_h_scroll` indexes the value columns beside it.
+        let corr_value_widths = &corr_widths[1..];
+        let corr_value_space = content_width.saturating_sub(corr_widths[0] + 1);
+        let data_widths = fit_widths(&preview_preferred, MIN_COLUMN_WIDTH, content_width);
 
-        terminal.draw(|f| {
+        let frame = terminal.draw(|f| {
             let title = Paragraph::new("⚡ Presto Presto accelerates preprocessing with precision ⚡")
                 .style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
                 .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)));
             f.render_widget(title, chunks[0]);
 
-            let tab_titles = vec!["📊 Stats", "📋 Details", "🔍 Advanced", "🔗 Correlations", "📈 Plots"];
+            let tab_titles = vec!["📊 Stats", "📋 Details", "🔍 Advanced", "🔗 Correlations", "📈 Plots", "🗂 Data"];
             let tabs = Tabs::new(tab_titles.into_iter().map(String::from).collect::<Vec<_>>())
-                .select(tab_index)
+                .select(state.tab_index)
                 .style(Style::default().fg(Color::White))
                 .highlight_style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
                 .divider("│");
             f.render_widget(tabs, chunks[1]);
 
-            match tab_index {
+            match state.tab_index {
                 0 => { 
-                    let mut visible_width = 0;
-                    let mut end_col = table_h_scroll;
-                    for i in table_h_scroll..total_cols {
-                        visible_width += widths[i];
-                        if visible_width > content_width {
-                            end_col = i;
-                            break;
-                        }
-                        end_col = i + 1;
-                    }
-                    let start_col = table_h_scroll;
+                    let start_col = state.table_h_scroll.min(widths.len() - 1);
+                    let end_col = visible_end(&widths, start_col, content_width);
                     let visible_headers = &header_cells[start_col..end_col];
                     let visible_widths = &widths[start_col..end_col];
 
-                    let all_rows: Vec<Row> = dataset.headers.iter().enumerate().map(|(i, header)| {
-                        let stats = &description.stats[i];
-                        let skew_desc = stats.skewness.map(|s| match s {
-                            s if s > 1.0 => "Highly +ve skewed",
-                            s if s > 0.5 => "Mod. +ve skewed",
-                            s if s < -1.0 => "Highly -ve skewed",
-                            s if s < -0.5 => "Mod. -ve skewed",
-                            _ => "Symmetric",
-                        }).unwrap_or("N/A");
-                        let kurt_desc = stats.kurtosis.map(|k| match k {
-                            k if k > 3.0 => "Leptokurtic",
-                            k if k < 3.0 => "Platykurtic",
-                            _ => "Mesokurtic",
-                        }).unwrap_or("N/A");
-                        Row::new(vec![
-                            header.clone(),
-                            stats.mean.map_or("N/A".to_string(), |v| format!("{:.2}", v)),
-                            stats.median.map_or("N/A".to_string(), |v| format!("{:.2}", v)),
-                            stats.std_dev.map_or("N/A".to_string(), |v| format!("{:.2}", v)),
-                            stats.variance.map_or("N/A".to_string(), |v| format!("{:.2}", v)),
-                            stats.min.map_or("N/A".to_string(), |v| format!("{:.2}", v)),
-                            stats.max.map_or("N/A".to_string(), |v| format!("{:.2}", v)),
-                            stats.skewness.map_or("N/A".to_string(), |v| format!("{:.2} ({})", v, skew_desc)),
-                            stats.kurtosis.map_or("N/A".to_string(), |v| format!("{:.2} ({})", v, kurt_desc)),
-                        ][start_col..end_col].to_vec())
+                    let all_rows: Vec<Row> = headers.iter().enumerate().map(|(i, header)| {
+                        let label = if column_types[i] != description.types[i] { format!("{} [{:?}]", header, column_types[i]) } else { header.clone() };
+                        Row::new(stats_row_cells(&label, &column_stats[i], &description.distributions[i], outlier_counts[i], &thresholds)[start_col..end_col].iter().zip(visible_widths).map(|(cell, &w)| clip(cell, w)).collect::<Vec<_>>())
                     }).collect();
 
                     let header = Row::new(visible_headers.to_vec()).style(Style::default().fg(Color::Green));
                     let stats_table = Table::new(all_rows, visible_widths.iter().map(|&w| Constraint::Length(w as u16)))
                         .header(header)
                         .block(Block::default()
-                            .title("Statistics")
+                            .title(match state.outlier_threshold {
+                                Some(threshold) => format!("{} · outliers |z| > {:.1}", stats_title, threshold),
+                                None => stats_title.clone(),
+                            })
                             .borders(Borders::ALL)
                             .border_type(BorderType::Thick)
                             .border_style(Style::default().fg(Color::Cyan)))
                         .column_spacing(1)
+                        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
                         .style(Style::default().fg(Color::White));
-                    if dataset.headers.len() > content_height {
-                        f.render_stateful_widget(stats_table, content_area, &mut table_state);
-                    } else {
-                        f.render_widget(stats_table, content_area);
-                    }
+                    f.render_stateful_widget(stats_table, content_area, &mut state.table_state);
                 }
                 1 => { 
-                    let info_text: Vec<Line> = vec![
-                        Line::from(vec![Span::styled("Rows: ", Style::default().fg(Color::Magenta)), Span::raw(description.total_rows.to_string())]),
-                        Line::from(vec![Span::styled("Cols: ", Style::default().fg(Color::Magenta)), Span::raw(dataset.headers.len().to_string())]),
-                        Line::from(vec![Span::styled("Missing %: ", Style::default().fg(Color::Magenta)), Span::raw(format!("{:.1}", description.missing_pct))]),
-                        Line::from(vec![Span::styled("Unique %: ", Style::default().fg(Color::Magenta)), Span::raw(format!("{:.1}", description.unique_pct))]),
-                        Line::from(vec![Span::styled("Missing: ", Style::default().fg(Color::Magenta)), Span::raw(description.missing.iter().map(|&m| m.to_string()).collect::<Vec<_>>().join(", "))]),
-                        Line::from(vec![Span::styled("Duplicates: ", Style::default().fg(Color::Magenta)), Span::raw(description.duplicates.to_string())]),
-                        Line::from(vec![Span::styled("Outliers: ", Style::default().fg(Color::Magenta)), Span::raw(description.outliers.iter().enumerate().map(|(i, o)| format!("{}: {:?}", dataset.headers[i], o)).collect::<Vec<_>>().join(", "))]),
-                        Line::from(vec![Span::styled("Types: ", Style::default().fg(Color::Magenta)), Span::raw(description.types.iter().map(|t| format!("{:?}", t)).collect::<Vec<_>>().join(", "))]),
-                        Line::from(vec![Span::styled("Cardinality: ", Style::default().fg(Color::Blue)), Span::raw(description.cardinality.iter().map(|&c| c.to_string()).collect::<Vec<_>>().join(", "))]),
-                        Line::from(vec![Span::styled("Distributions: ", Style::default().fg(Color::Blue)), Span::raw(description.distributions.iter().map(|d| d.iter().map(|&(mid, cnt)| format!("{:.1}:{}", mid, cnt)).collect::<Vec<_>>().join("|")).collect::<Vec<_>>().join(", "))]),
-                        Line::from(vec![Span::styled("Top Values: ", Style::default().fg(Color::Blue)), Span::raw(description.top_values.iter().map(|(col, vals)| format!("{}: {}", col, vals.iter().map(|(v, c)| format!("{}({})", v, c)).collect::<Vec<_>>().join(", "))).collect::<Vec<_>>().join("; "))]),
-                    ];
-                    let info_block = Paragraph::new(info_text.clone())
+                    let info_text: Vec<Line> = field_lines(details_fields);
+                    let info_block = Paragraph::new(info_text)
                         .block(Block::default()
-                            .title("Details")
+                            .title(if state.missing_sorted { "Details (columns by missingness)" } else { "Details" })
                             .borders(Borders::ALL)
                             .border_type(BorderType::Thick)
                             .border_style(Style::default().fg(Color::Cyan)))
                         .style(Style::default().fg(Color::White))
-                        .scroll((details_v_scroll, details_h_scroll));
+                        .scroll((state.details_v_scroll, if state.wrap_text { 0 } else { state.details_h_scroll }));
+                    let info_block = if state.wrap_text { info_block.wrap(Wrap { trim: false }) } else { info_block };
                     f.render_widget(info_block, content_area);
                 }
                 2 => { 
-                    let advanced_text: Vec<Line> = vec![
-                        Line::from(vec![Span::styled("Dependency: ", Style::default().fg(Color::Green)), Span::raw(description.dependency_scores.iter().map(|&s| format!("{:.2}", s)).collect::<Vec<_>>().join(", "))]),
-                        Line::from(vec![Span::styled("Drift: ", Style::default().fg(Color::Green)), Span::raw(description.drift_scores.iter().map(|&s| format!("{:.2}", s)).collect::<Vec<_>>().join(", "))]),
-                        Line::from(vec![Span::styled("Consistency Issues: ", Style::default().fg(Color::Red)), Span::raw(description.consistency_issues.iter().map(|&i| i.to_string()).collect::<Vec<_>>().join(", "))]),
-                        Line::from(vec![Span::styled("Temporal: ", Style::default().fg(Color::Red)), Span::raw(description.temporal_patterns.join(", "))]),
-                        Line::from(vec![Span::styled("Transforms: ", Style::default().fg(Color::Red)), Span::raw(description.transform_suggestions.join(", "))]),
-                        Line::from(vec![Span::styled("Noise: ", Style::default().fg(Color::Yellow)), Span::raw(description.noise_scores.iter().map(|&n| format!("{:.2}", n)).collect::<Vec<_>>().join(", "))]),
-                        Line::from(vec![Span::styled("Redundancy: ", Style::default().fg(Color::Yellow)), Span::raw(
-                            if description.redundancy_pairs.is_empty() {
-                                "None".to_string()
-                            } else {
-                                description.redundancy_pairs.iter()
-                                    .map(|&(i, j, s)| format!("{}<->{}:{:.2}", dataset.headers[i], dataset.headers[j], s))
-                                    .collect::<Vec<_>>()
-                                    .join(", ")
-                            }
-                        )]),
-                        Line::from(vec![Span::styled("Feature Importance: ", Style::default().fg(Color::Green)), Span::raw(description.feature_importance.iter().map(|&(col, score)| format!("{}:{:.2}", dataset.headers[col], score)).collect::<Vec<_>>().join(", "))]),
-                        Line::from(vec![Span::styled("Anomalies: ", Style::default().fg(Color::Red)), Span::raw(description.anomalies.iter().map(|(col, val, idx)| format!("{}:{} (idx {})", dataset.headers[*col], val, idx)).collect::<Vec<_>>().join(", "))]),
-                    ];
-                    let advanced_block = Paragraph::new(advanced_text.clone())
+                    let advanced_text: Vec<Line> = field_lines(&advanced_fields);
+                    let advanced_block = Paragraph::new(advanced_text)
                         .block(Block::default()
                             .title("Advanced")
                             .borders(Borders::ALL)
                             .border_type(BorderType::Thick)
                             .border_style(Style::default().fg(Color::Cyan)))
                         .style(Style::default().fg(Color::White))
-                        .scroll((advanced_v_scroll, advanced_h_scroll));
+                        .scroll((state.advanced_v_scroll, if state.wrap_text { 0 } else { state.advanced_h_scroll }));
+                    let advanced_block = if state.wrap_text { advanced_block.wrap(Wrap { trim: false }) } else { advanced_block };
                     f.render_widget(advanced_block, content_area);
                 }
                 3 => { 
-                    let corr_headers = dataset.headers.clone();
-                    let corr_widths = vec![15usize; corr_headers.len() + 1];
-                    let total_corr_cols = corr_headers.len() + 1;
-                    let _total_corr_width: usize = corr_widths.iter().sum();
-
-                    let mut visible_width = 0;
-                    let mut end_col = corr_h_scroll;
-                    for i in corr_h_scroll..total_corr_cols {
-                        visible_width += corr_widths[i];
-                        if visible_width > content_width {
-                            end_col = i;
-                            break;
-                        }
-                        end_col = i + 1;
-                    }
-                    let start_col = corr_h_scroll;
-                    let visible_headers = &corr_headers[start_col.saturating_sub(1)..end_col.saturating_sub(1)];
+                    let start_col = state.corr_h_scroll.min(corr_value_widths.len() - 1);
+                    let end_col = visible_end(corr_value_widths, start_col, corr_value_space);
+                    let visible_headers = &headers[start_col..end_col];
 
-                    let all_rows: Vec<Row> = dataset.headers.iter().enumerate().map(|(i, header)| {
-                        let mut row = vec![header.clone()];
-                        row.extend(description.correlations[i].iter().map(|&c| format!("{:.2}", c)));
-                        Row::new(row[start_col..end_col].to_vec())
+                    let cursor = state.corr_state.selected().zip(state.corr_column);
+                    let all_rows: Vec<Row> = headers.iter().enumerate().map(|(i, header)| {
+                        let mut row = vec![Cell::from(clip(header, corr_widths[0])).style(Style::default().fg(Color::Green))];
+                        row.extend(description.correlations[i][start_col..end_col].iter().enumerate().map(|(offset, &c)| {
+                            let cell = Cell::from(format!("{:.2}", c));
+                            if cursor == Some((i, start_col + offset)) { cell.style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD | Modifier::UNDERLINED)) } else { cell }
+                        }));
+                        Row::new(row)
                     }).collect();
 
-                    let header = Row::new(["".to_string()].iter().chain(visible_headers).cloned().collect::<Vec<_>>()).style(Style::default().fg(Color::Green));
-                    let corr_table = Table::new(all_rows, corr_widths[start_col..end_col].iter().map(|&w| Constraint::Length(w as u16)))
+                    let header = Row::new(std::iter::once(String::new()).chain(visible_headers.iter().zip(&corr_value_widths[start_col..end_col]).map(|(h, &w)| clip(h, w))).collect::<Vec<_>>()).style(Style::default().fg(Color::Green));
+                    let corr_table = Table::new(all_rows, std::iter::once(&corr_widths[0]).chain(&corr_value_widths[start_col..end_col]).map(|&w| Constraint::Length(w as u16)))
                         .header(header)
                         .block(Block::default()
-                            .title("Correlations")
+                            .title(corr_title.as_str())
                             .borders(Borders::ALL)
                             .border_type(BorderType::Thick)
                             .border_style(Style::default().fg(Color::Cyan)))
                         .column_spacing(1)
+                        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
                         .style(Style::default().fg(Color::White));
-                    if dataset.headers.len() > content_height {
-                        f.render_stateful_widget(corr_table, content_area, &mut corr_state);
-                    } else {
-                        f.render_widget(corr_table, content_area);
-                    }
+                    f.render_stateful_widget(corr_table, content_area, &mut state.corr_state);
                 }
-                4 => { 
+                4 if state.box_plots => {
                     let mut plot_text: Vec<Line> = Vec::new();
-                    let max_height = content_area.height.saturating_sub(4) as usize;
-                    for (i, header) in dataset.headers.iter().enumerate() {
+                    for (i, header) in headers.iter().enumerate() {
                         plot_text.push(Line::from(Span::styled(format!("{}:", header), Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD))));
-                        if let Some(dist) = description.distributions.get(i) {
-                            if dist.is_empty() {
-                                plot_text.push(Line::from(Span::raw("  (No numeric data)")));
-                                continue;
-                            }
-                            let max_val = dist.iter().map(|&(_, c)| c).max().unwrap_or(1) as f64;
-                            let bar_heights: Vec<usize> = dist.iter()
-                                .map(|&(_, cnt)| (cnt as f64 / max_val * max_height as f64).round() as usize)
-                                .collect();
-                            let max_label_width = dist.iter()
-                                .map(|&(mid, _)| format!("{:.1}", mid).len())
-                                .max()
-                                .unwrap_or(4);
-                            let step = max_val / max_height as f64;
-                            for h in (0..=max_height).rev() {
-                                let count = (h as f64 * step).round() as usize;
-                                let mut line = format!("{:4} | ", count);
-                                for (j, &height) in bar_heights.iter().enumerate() {
-                                    let mid_str = format!("{:.1}", dist[j].0);
-                                    let padding = max_label_width.saturating_sub(mid_str.len()) / 2;
-                                    if h == 0 {
-                                        line.push_str(&" ".repeat(padding));
-                                        line.push_str(&mid_str);
-                                        line.push_str(&" ".repeat(max_label_width.saturating_sub(mid_str.len() - padding)));
-                                    } else {
-                                        line.push_str(&" ".repeat(max_label_width / 2));
-                                        line.push(if height >= h { '█' } else { ' ' });
-                                        line.push_str(&" ".repeat(max_label_width / 2));
-                                    }
-                                    line.push(' ');
-                                }
-                                plot_text.push(Line::from(Span::raw(line)));
+                        match box_plot_lines(&column_stats[i], content_width.saturating_sub(2)) {
+                            Some((plot, labels)) => {
+                                plot_text.push(Line::from(Span::raw(format!("  {}", plot))));
+                                plot_text.push(Line::from(Span::styled(format!("  {}", labels), Style::default().fg(Color::DarkGray))));
                             }
+                            None => plot_text.push(Line::from(Span::raw("  (No numeric data)"))),
                         }
-                        plot_text.push(Line::from(Span::raw(""))); 
+                        plot_text.push(Line::from(Span::raw("")));
                     }
-                    let plot_block = Paragraph::new(plot_text.clone())
+                    let plot_block = Paragraph::new(plot_text)
+                        .block(Block::default()
+                            .title("Plots (box)")
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick)
+                            .border_style(Style::default().fg(Color::Cyan)))
+                        .style(Style::default().fg(Color::White))
+                        .scroll((state.plots_v_scroll, 0));
+                    f.render_widget(plot_block, content_area);
+                }
+                4 => { 
+                    let plot_block = Paragraph::new(histogram.clone())
                         .block(Block::default()
                             .title("Plots")
                             .borders(Borders::ALL)
                             .border_type(BorderType::Thick)
                             .border_style(Style::default().fg(Color::Cyan)))
                         .style(Style::default().fg(Color::White))
-                        .scroll((plots_v_scroll, plots_h_scroll));
+                        .scroll((state.plots_v_scroll, state.plots_h_scroll));
                     f.render_widget(plot_block, content_area);
                 }
+                5 => {
+                    let start_col = state.data_h_scroll.min(data_widths.len() - 1);
+                    let end_col = visible_end(&data_widths, start_col, content_width);
+                    let visible_widths = &data_widths[start_col..end_col];
+                    let header = Row::new(preview_header[start_col..end_col].iter().zip(visible_widths).map(|(cell, &w)| clip(cell, w)).collect::<Vec<_>>()).style(Style::default().fg(Color::Green));
+                    let all_rows: Vec<Row> = preview_cells.iter().map(|cells| Row::new(cells[start_col..end_col].iter().zip(visible_widths).map(|(cell, &w)| clip(cell, w)).collect::<Vec<_>>())).collect();
+                    let data_table = Table::new(all_rows, data_widths[start_col..end_col].iter().map(|&w| Constraint::Length(w as u16)))
+                        .header(header)
+                        .block(Block::default()
+                            .title(data_title.as_str())
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick)
+                            .border_style(Style::default().fg(Color::Cyan)))
+                        .column_spacing(1)
+                        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+                        .style(Style::default().fg(Color::White));
+                    f.render_stateful_widget(data_table, content_area, &mut state.data_state);
+                }
                 _ => unreachable!(),
             }
 
-            let footer = Paragraph::new("'q' to exit | 'e' to export | Tab/Shift+Tab to switch tabs")
+            if let Some(query) = &palette {
+                let hint = match best_command(query) {
+                    Some(command) => format!(":{}  → {}  (Enter to run, Esc to cancel)", query, command.name),
+                    None => format!(":{}  (no matching command)", query),
+                };
+                f.render_widget(Paragraph::new(hint).style(Style::default().fg(Color::Cyan)), chunks[3]);
+            } else if let Some((message, _)) = &state.status {
+                let status_line = Paragraph::new(message.as_str())
+                    .style(Style::default().fg(Color::Yellow));
+                f.render_widget(status_line, chunks[3]);
+            }
+
+            let footer = Paragraph::new("'q' to exit | 'e' to export | 'x' export tab | 'E' format | 'c' to copy row | 's' snapshot | Enter for full row or cell drill-down | 'w' to wrap | PgUp/PgDn/Home/End to jump | 'b' box plots | 'm' missingness | +/- outlier threshold | 't' retype | '?' legend | ':' commands | Tab/Shift+Tab to switch tabs")
                 .style(Style::default().fg(Color::Gray))
                 .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Cyan)));
-            f.render_widget(footer, chunks[3]);
+            f.render_widget(footer, chunks[4]);
+
+            if let Some((title, lines)) = &overlay {
+                let area = centered_rect(60, 60, full_area);
+                let popup = Paragraph::new(lines.iter().map(|l| Line::from(l.as_str())).collect::<Vec<_>>())
+                    .block(Block::default()
+                        .title(format!("{} (any key to close)", title))
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Thick)
+                        .border_style(Style::default().fg(Color::Yellow)))
+                    .style(Style::default().fg(Color::White))
+                    .wrap(Wrap { trim: false });
+                f.render_widget(Clear, area);
+                f.render_widget(popup, area);
+            }
         }).map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
 
+        // Captured from the frame just drawn, so the status line announcing it is not in the image.
+        if state.snapshot_pending {
+            state.snapshot_pending = false;
+            let message = match save_snapshot(frame.buffer) {
+                Ok(()) => format!("Saved snapshot to {}", SNAPSHOT_PATH),
+                Err(e) => format!("Snapshot failed: {}", e),
+            };
+            state.status = Some((message, Instant::now()));
+            continue;
+        }
+
+        let mut timeout = state.status.as_ref().map(|(_, shown_at)| status_time_left(*shown_at));
+        if reload.is_some() {
+            timeout = Some(timeout.map_or(WATCH_INTERVAL, |t| t.min(WATCH_INTERVAL)));
+        }
+        if let Some(timeout) = timeout && !event::poll(timeout).map_err(|e| PrestoError::InvalidNumeric(e.to_string()))? {
+            if state.status.as_ref().is_some_and(|(_, shown_at)| shown_at.elapsed() >= STATUS_TIMEOUT) {
+                state.status = None;
+            }
+            if let Some(reload) = reload.as_mut() {
+                match reload() {
+                    Ok(Some(next)) => {
+                        state.status = Some(("File changed; profile refreshed".to_string(), Instant::now()));
+                        return Ok(Some(next));
+                    }
+                    Ok(None) => {}
+                    Err(e) => state.status = Some((format!("Refresh failed: {}", e), Instant::now())),
+                }
+            }
+            continue;
+        }
+
         if let Event::Key(key) = event::read().map_err(|e| PrestoError::InvalidNumeric(e.to_string()))? {
-            match key.code {
+            state.status = None;
+            if overlay.take().is_some() {
+                continue;
+            }
+            let code = match palette.as_mut() {
+                Some(query) => match key.code {
+                    KeyCode::Char(c) => {
+                        query.push(c);
+                        continue;
+                    }
+                    KeyCode::Backspace => {
+                        query.pop();
+                        continue;
+                    }
+                    KeyCode::Enter => {
+                        let query = palette.take().unwrap_or_default();
+                        let Some(command) = best_command(&query) else {
+                            state.status = Some((format!("No command matches '{}'", query), Instant::now()));
+                            continue;
+                        };
+                        if let Some(tab) = command.tab {
+                            state.tab_index = tab;
+                        }
+                        match command.key {
+                            Some(c) => KeyCode::Char(c),
+                            None => continue,
+                        }
+                    }
+                    KeyCode::Esc => {
+                        palette = None;
+                        continue;
+                    }
+                    _ => continue,
+                },
+                None => key.code,
+            };
+            match code {
+                KeyCode::Char(':') => palette = Some(String::new()),
+                KeyCode::Enter => {
+                    overlay = match state.tab_index {
+                        0 => state.table_state.selected().map(|i| {
+                            let cells = stats_row_cells(&headers[i], &column_stats[i], &description.distributions[i], outlier_counts[i], &thresholds);
+                            let mut lines: Vec<String> = header_cells.iter().zip(cells.iter())
+                                .map(|(name, value)| format!("{}: {}", name, value))
+                                .collect();
+                            lines.push(format!("Type: {:?} ({:.1}% consistent)", column_types[i], description.type_confidence[i] * 100.0));
+                            if let Some((expected, samples)) = &description.type_mismatches[i] {
+                                lines.push(format!("Values not parsing as {:?}: {}", expected, samples.iter().map(|s| format!("{:?}", s)).collect::<Vec<_>>().join(", ")));
+                            }
+                            let flagged = match &outlier_scores {
+                                Some(scores) => &scores[i][..outlier_counts[i]],
+                                None => &description.outliers[i][..],
+                            };
+                            let threshold = state.outlier_threshold.unwrap_or(cleaning::OUTLIER_Z_THRESHOLD);
+                            if let Some((lower, upper)) = cleaning::outlier_bounds(&description.stats[i], threshold) {
+                                lines.push(format!("Outlier fences (|z| > {:.1}): {:.4} .. {:.4}", threshold, lower, upper));
+                            }
+                            if !flagged.is_empty() {
+                                lines.push(format!("Outlier rows: {}", flagged.iter().map(|(idx, z)| format!("{}(z {:.2})", idx, z)).collect::<Vec<_>>().join(", ")));
+                            }
+                            (full_headers[i].clone(), lines)
+                        }),
+                        3 => state.corr_state.selected().map(|i| match state.corr_column.filter(|&j| j != i && j < headers.len()) {
+                            Some(j) => {
+                                let contributions = description.correlation_contributions(dataset, i, j, CORR_DRILL_ROWS);
+                                let mut lines = vec![
+                                    format!("r = {:.4}; share of the covariance sum by row:", description.correlations[i][j]),
+                                ];
+                                lines.extend(contributions.iter().map(|&(row, x, y, percent)| {
+                                    format!("Row {}: {} = {}, {} = {} ({:+.1}%)", row + 1, headers[i], x, headers[j], y, percent)
+                                }));
+                                let top_share: f64 = contributions.iter().map(|&(_, _, _, percent)| percent).sum();
+                                lines.push(format!("These {} rows carry {:.1}% of the covariance", contributions.len(), top_share));
+                                (format!("Rows behind {} ~ {}", headers[i], headers[j]), lines)
+                            }
+                            None => {
+                                let lines = headers.iter().zip(description.correlations[i].iter())
+                                    .map(|(name, c)| format!("{}: {:.4}", name, c))
+                                    .collect();
+                                (format!("Correlations of {}", headers[i]), lines)
+                            }
+                        }),
+                        5 => state.data_state.selected().and_then(|i| preview_rows[i]).map(|idx| {
+                            let lines = dataset.headers.iter().zip(dataset.rows[idx].iter())
+                                .map(|(name, value)| format!("{}: {}", name, value))
+                                .collect();
+                            (format!("Row {}", idx + 1), lines)
+                        }),
+                        _ => None,
+                    };
+                    if overlay.is_none() && matches!(state.tab_index, 0 | 3 | 5) {
+                        state.status = Some(("Select a row with ↑/↓ first".to_string(), Instant::now()));
+                    }
+                }
+                KeyCode::Char('?') => overlay = Some(("Legend".to_string(), legend_lines(&thresholds, state.outlier_threshold.unwrap_or(cleaning::OUTLIER_Z_THRESHOLD)))),
                 KeyCode::Char('q') => break,
+                KeyCode::Char('c') => {
+                    let selected = match state.tab_index {
+                        0 => Some(state.table_state.selected().unwrap_or(0)),
+                        3 => Some(state.corr_state.selected().unwrap_or(0)),
+                        _ => None,
+                    };
+                    let text = selected.and_then(|i| headers.get(i).map(|header| match state.tab_index {
+                        0 => stats_row_cells(header, &column_stats[i], &description.distributions[i], outlier_counts[i], &thresholds).join("\t"),
+                        _ => std::iter::once(header.clone())
+                            .chain(description.correlations[i].iter().map(|c| format!("{:.2}", c)))
+                            .collect::<Vec<_>>()
+                            .join("\t"),
+                    }));
+                    let message = match text {
+                        Some(text) => match copy_to_clipboard(&text) {
+                            Ok(()) => format!("Copied row {} to clipboard", headers[selected.unwrap_or(0)]),
+                            Err(e) => format!("Clipboard unavailable: {}", e),
+                        },
+                        None => "Nothing to copy on this tab".to_string(),
+                    };
+                    state.status = Some((message, Instant::now()));
+                }
                 KeyCode::Char('e') => {
-                    let json = serde_json::to_string_pretty(&description)
-                        .map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
-                    std::fs::write("presto_insights.json", json)
-                        .map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
+                    let path = format!("{}.{}", EXPORT_STEM, state.export_format.extension());
+                    let result = description
+                        .to_report()
+                        .render(state.export_format)
+                        .map_err(|e| e.to_string())
+                        .and_then(|text| std::fs::write(&path, text).map_err(|e| e.to_string()));
+                    let message = match result {
+                        Ok(()) => format!("Exported to {}", path),
+                        Err(e) => format!("Export failed: {}", e),
+                    };
+                    state.status = Some((message, Instant::now()));
                 }
-                KeyCode::Tab => tab_index = (tab_index + 1) % 5,
-                KeyCode::BackTab => tab_index = (tab_index + 4) % 5,
-                KeyCode::Left => {
-                    match tab_index {
-                        0 => if total_width > content_width && table_h_scroll > 0 { table_h_scroll -= 1; }
-                        1 => {
-                            let info_text = vec![
-                                format!("Rows: {}", description.total_rows),
-                                format!("Cols: {}", dataset.headers.len()),
-                                format!("Missing %: {:.1}", description.missing_pct),
-                                format!("Unique %: {:.1}", description.unique_pct),
-                                format!("Missing: {}", description.missing.iter().map(|&m| m.to_string()).collect::<Vec<_>>().join(", ")),
-                                format!("Duplicates: {}", description.duplicates),
-                                format!("Outliers: {}", description.outliers.iter().enumerate().map(|(i, o)| format!("{}: {:?}", dataset.headers[i], o)).collect::<Vec<_>>().join(", ")),
-                                format!("Types: {}", description.types.iter().map(|t| format!("{:?}", t)).collect::<Vec<_>>().join(", ")),
-                                format!("Cardinality: {}", description.cardinality.iter().map(|&c| c.to_string()).collect::<Vec<_>>().join(", ")),
-                                format!("Distributions: {}", description.distributions.iter().map(|d| d.iter().map(|&(mid, cnt)| format!("{:.1}:{}", mid, cnt)).collect::<Vec<_>>().join("|")).collect::<Vec<_>>().join(", ")),
-                                format!("Top Values: {}", description.top_values.iter().map(|(col, vals)| format!("{}: {}", col, vals.iter().map(|(v, c)| format!("{}({})", v, c)).collect::<Vec<_>>().join(", "))).collect::<Vec<_>>().join("; ")),
-                            ];
-                            let max_line_width = info_text.iter().map(|s| s.len()).max().unwrap_or(0);
-                            if max_line_width > content_width && details_h_scroll > 0 { details_h_scroll -= 1; }
-                        }
-                        2 => {
-                            let advanced_text = vec![
-                                format!("Dependency: {}", description.dependency_scores.iter().map(|&s| format!("{:.2}", s)).collect::<Vec<_>>().join(", ")),
-                                format!("Drift: {}", description.drift_scores.iter().map(|&s| format!("{:.2}", s)).collect::<Vec<_>>().join(", ")),
-                                format!("Consistency Issues: {}", description.consistency_issues.iter().map(|&i| i.to_string()).collect::<Vec<_>>().join(", ")),
-                                format!("Temporal: {}", description.temporal_patterns.join(", ")),
-                                format!("Transforms: {}", description.transform_suggestions.join(", ")),
-                                format!("Noise: {}", description.noise_scores.iter().map(|&n| format!("{:.2}", n)).collect::<Vec<_>>().join(", ")),
-                                format!("Redundancy: {}", if description.redundancy_pairs.is_empty() {
-                                    "None".to_string()
-                                } else {
-                                    description.redundancy_pairs.iter()
-                                        .map(|&(i, j, s)| format!("{}<->{}:{:.2}", dataset.headers[i], dataset.headers[j], s))
-                                        .collect::<Vec<_>>()
-                                        .join(", ")
-                                }),
-                                format!("Feature Importance: {}", description.feature_importance.iter().map(|&(col, score)| format!("{}:{:.2}", dataset.headers[col], score)).collect::<Vec<_>>().join(", ")),
-                                format!("Anomalies: {}", description.anomalies.iter().map(|(col, val, idx)| format!("{}:{} (idx {})", dataset.headers[*col], val, idx)).collect::<Vec<_>>().join(", ")),
-                            ];
-                            let max_line_width = advanced_text.iter().map(|s| s.len()).max().unwrap_or(0);
-                            if max_line_width > content_width && advanced_h_scroll > 0 { advanced_h_scroll -= 1; }
-                        }
-                        3 => {
-                            let corr_widths = vec![15usize; dataset.headers.len() + 1];
-                            let total_corr_width: usize = corr_widths.iter().sum();
-                            if total_corr_width > content_width && corr_h_scroll > 0 { corr_h_scroll -= 1; }
-                        }
-                        4 => {
-                            let mut plot_text = Vec::new();
-                            let max_height = content_area.height.saturating_sub(4) as usize;
-                            let mut max_label_width = 4;
-                            for (i, header) in dataset.headers.iter().enumerate() {
-                                plot_text.push(format!("{}:", header));
-                                if let Some(dist) = description.distributions.get(i) {
-                                    if dist.is_empty() {
-                                        plot_text.push("  (No numeric data)".to_string());
-                                        continue;
-                                    }
-                                    max_label_width = dist.iter()
-                                        .map(|&(mid, _)| format!("{:.1}", mid).len())
-                                        .max()
-                                        .unwrap_or(4)
-                                        .max(max_label_width);
-                                    let max_val = dist.iter().map(|&(_, c)| c).max().unwrap_or(1) as f64;
-                                    let bar_heights: Vec<usize> = dist.iter()
-                                        .map(|&(_, cnt)| (cnt as f64 / max_val * max_height as f64).round() as usize)
-                                        .collect();
-                                    let step = max_val / max_height as f64;
-                                    for h in (0..=max_height).rev() {
-                                        let count = (h as f64 * step).round() as usize;
-                                        let mut line = format!("{:4} | ", count);
-                                        for (j, &height) in bar_heights.iter().enumerate() {
-                                            let mid_str = format!("{:.1}", dist[j].0);
-                                            let padding = max_label_width.saturating_sub(mid_str.len()) / 2;
-                                            if h == 0 {
-                                                line.push_str(&" ".repeat(padding));
-                                                line.push_str(&mid_str);
-                                                line.push_str(&" ".repeat(max_label_width.saturating_sub(mid_str.len() - padding)));
-                                            } else {
-                                                line.push_str(&" ".repeat(max_label_width / 2));
-                                                line.push(if height >= h { '█' } else { ' ' });
-                                                line.push_str(&" ".repeat(max_label_width / 2));
-                                            }
-                                            line.push(' ');
-                                        }
-                                        plot_text.push(line);
-                                    }
-                                }
-                                plot_text.push("".to_string());
+                KeyCode::Char('x') => {
+                    let (name, extension, text) = match state.tab_index {
+                        0 => ("stats", "csv", description.to_stats_csv()),
+                        1 => ("details", "txt", details_strings.join("\n") + "\n"),
+                        2 => ("advanced", "txt", advanced_strings.join("\n") + "\n"),
+                        3 => ("correlations", "csv", description.to_correlation_matrix_csv()),
+                        4 => ("plots", "csv", description.to_distribution_csv()),
+                        _ => ("data", "csv", dataset.to_csv_string()),
+                    };
+                    let path = format!("{}_{}.{}", EXPORT_STEM, name, extension);
+                    let message = match std::fs::write(&path, text) {
+                        Ok(()) => format!("Exported {} tab to {}", name, path),
+                        Err(e) => format!("Export failed: {}", e),
+                    };
+                    state.status = Some((message, Instant::now()));
+                }
+                KeyCode::Char('E') => {
+                    state.export_format = state.export_format.next();
+                    let message = format!("Export format: {}", state.export_format.extension());
+                    state.status = Some((message, Instant::now()));
+                }
+                KeyCode::Char('s') => state.snapshot_pending = true,
+                KeyCode::Char('w') => {
+                    state.wrap_text = !state.wrap_text;
+                    state.details_v_scroll = 0;
+                    state.advanced_v_scroll = 0;
+                    let message = if state.wrap_text { "Line wrapping on" } else { "Line wrapping off" };
+                    state.status = Some((message.to_string(), Instant::now()));
+                }
+                KeyCode::Char('b') if state.tab_index == 4 => {
+                    state.box_plots = !state.box_plots;
+                    state.plots_v_scroll = 0;
+                    state.plots_h_scroll = 0;
+                }
+                KeyCode::Char(c @ ('+' | '=' | '-')) if state.tab_index == 0 => {
+                    let current = state.outlier_threshold.unwrap_or(cleaning::OUTLIER_Z_THRESHOLD);
+                    let threshold = if c == '-' { (current - OUTLIER_THRESHOLD_STEP).max(MIN_OUTLIER_THRESHOLD) } else { current + OUTLIER_THRESHOLD_STEP };
+                    state.outlier_threshold = Some(threshold);
+                    let scores = outlier_scores.get_or_insert_with(|| description.z_scores_above(dataset, MIN_OUTLIER_THRESHOLD));
+                    outlier_counts = scores.iter().map(|col| col.partition_point(|&(_, z)| z > threshold)).collect();
+                    let message = format!("Outliers at |z| > {:.1}: {} values flagged", threshold, outlier_counts.iter().sum::<usize>());
+                    state.status = Some((message, Instant::now()));
+                }
+                KeyCode::Char('m') if state.tab_index == 1 => {
+                    state.missing_sorted = !state.missing_sorted;
+                    state.details_v_scroll = 0;
+                    state.details_h_scroll = 0;
+                }
+                KeyCode::Char('t') if state.tab_index == 0 => {
+                    if let Some(i) = state.table_state.selected() {
+                        let next = column_types[i].next_override();
+                        let message = match description.retyped_stats(dataset, i, &next) {
+                            Ok(recomputed) => {
+                                column_stats[i] = recomputed;
+                                let message = format!("Typed {} as {:?}; stats recomputed", headers[i], next);
+                                column_types[i] = next;
+                                message
                             }
-                            let max_line_width = plot_text.iter().map(|s| s.len()).max().unwrap_or(0);
-                            if max_line_width > content_width && plots_h_scroll > 0 { plots_h_scroll -= 1; }
+                            Err(e) => format!("Could not retype {}: {}", headers[i], e),
+                        };
+                        state.status = Some((message, Instant::now()));
+                    }
+                }
+                KeyCode::Tab => state.tab_index = (state.tab_index + 1) % 6,
+                KeyCode::BackTab => state.tab_index = (state.tab_index + 5) % 6,
+                KeyCode::Left => {
+                    match state.tab_index {
+                        0 if state.table_h_scroll > 0 => state.table_h_scroll -= 1,
+                        1 if !state.wrap_text && details_width > content_width && state.details_h_scroll > 0 => state.details_h_scroll -= 1,
+                        2 if !state.wrap_text && advanced_width > content_width && state.advanced_h_scroll > 0 => state.advanced_h_scroll -= 1,
+                        // With a row selected, ← / → move a cell cursor for the Enter drill-down.
+                        3 if state.corr_state.selected().is_some() && state.corr_column.is_some() => {
+                            state.corr_column = state.corr_column.and_then(|j| j.checked_sub(1));
+                            if let Some(j) = state.corr_column { state.corr_h_scroll = scroll_into_view(state.corr_h_scroll, j, corr_value_widths, corr_value_space); }
                         }
+                        3 if state.corr_h_scroll > 0 => state.corr_h_scroll -= 1,
+                        5 if state.data_h_scroll > 0 => state.data_h_scroll -= 1,
+                        4 if !state.box_plots && histogram_width > content_width && state.plots_h_scroll > 0 => state.plots_h_scroll -= 1,
                         _ => {}
                     }
                 }
                 KeyCode::Right => {
-                    match tab_index {
-                        0 => {
-                            let mut visible_width = 0;
-                            for &w in &widths[table_h_scroll..] {
-                                if visible_width + w > content_width { break; }
-                                visible_width += w;
-                            }
-                            let max_h_scroll = total_cols.saturating_sub((content_width / 10).max(1));
-                            if total_width > content_width && table_h_scroll < max_h_scroll { table_h_scroll += 1; }
-                        }
-                        1 => {
-                            let info_text = vec![
-                                format!("Rows: {}", description.total_rows),
-                                format!("Cols: {}", dataset.headers.len()),
-                                format!("Missing %: {:.1}", description.missing_pct),
-                                format!("Unique %: {:.1}", description.unique_pct),
-                                format!("Missing: {}", description.missing.iter().map(|&m| m.to_string()).collect::<Vec<_>>().join(", ")),
-                                format!("Duplicates: {}", description.duplicates),
-                                format!("Outliers: {}", description.outliers.iter().enumerate().map(|(i, o)| format!("{}: {:?}", dataset.headers[i], o)).collect::<Vec<_>>().join(", ")),
-                                format!("Types: {}", description.types.iter().map(|t| format!("{:?}", t)).collect::<Vec<_>>().join(", ")),
-                                format!("Cardinality: {}", description.cardinality.iter().map(|&c| c.to_string()).collect::<Vec<_>>().join(", ")),
-                                format!("Distributions: {}", description.distributions.iter().map(|d| d.iter().map(|&(mid, cnt)| format!("{:.1}:{}", mid, cnt)).collect::<Vec<_>>().join("|")).collect::<Vec<_>>().join(", ")),
-                                format!("Top Values: {}", description.top_values.iter().map(|(col, vals)| format!("{}: {}", col, vals.iter().map(|(v, c)| format!("{}({})", v, c)).collect::<Vec<_>>().join(", "))).collect::<Vec<_>>().join("; ")),
-                            ];
-                            let max_line_width = info_text.iter().map(|s| s.len()).max().unwrap_or(0);
-                            let max_h_scroll = max_line_width.saturating_sub(content_width) as u16;
-                            if max_line_width > content_width && details_h_scroll < max_h_scroll { details_h_scroll += 1; }
-                        }
-                        2 => {
-                            let advanced_text = vec![
-                                format!("Dependency: {}", description.dependency_scores.iter().map(|&s| format!("{:.2}", s)).collect::<Vec<_>>().join(", ")),
-                                format!("Drift: {}", description.drift_scores.iter().map(|&s| format!("{:.2}", s)).collect::<Vec<_>>().join(", ")),
-                                format!("Consistency Issues: {}", description.consistency_issues.iter().map(|&i| i.to_string()).collect::<Vec<_>>().join(", ")),
-                                format!("Temporal: {}", description.temporal_patterns.join(", ")),
-                                format!("Transforms: {}", description.transform_suggestions.join(", ")),
-                                format!("Noise: {}", description.noise_scores.iter().map(|&n| format!("{:.2}", n)).collect::<Vec<_>>().join(", ")),
-                                format!("Redundancy: {}", if description.redundancy_pairs.is_empty() {
-                                    "None".to_string()
-                                } else {
-                                    description.redundancy_pairs.iter()
-                                        .map(|&(i, j, s)| format!("{}<->{}:{:.2}", dataset.headers[i], dataset.headers[j], s))
-                                        .collect::<Vec<_>>()
-                                        .join(", ")
-                                }),
-                                format!("Feature Importance: {}", description.feature_importance.iter().map(|&(col, score)| format!("{}:{:.2}", dataset.headers[col], score)).collect::<Vec<_>>().join(", ")),
-                                format!("Anomalies: {}", description.anomalies.iter().map(|(col, val, idx)| format!("{}:{} (idx {})", dataset.headers[*col], val, idx)).collect::<Vec<_>>().join(", ")),
-                            ];
-                            let max_line_width = advanced_text.iter().map(|s| s.len()).max().unwrap_or(0);
-                            let max_h_scroll = max_line_width.saturating_sub(content_width) as u16;
-                            if max_line_width > content_width && advanced_h_scroll < max_h_scroll { advanced_h_scroll += 1; }
-                        }
-                        3 => {
-                            let corr_widths = vec![15usize; dataset.headers.len() + 1];
-                            let total_corr_width: usize = corr_widths.iter().sum();
-                            let max_h_scroll = (dataset.headers.len() + 1).saturating_sub((content_width / 15).max(1));
-                            if total_corr_width > content_width && corr_h_scroll < max_h_scroll { corr_h_scroll += 1; }
-                        }
-                        4 => {
-                            let mut plot_text = Vec::new();
-                            let max_height = content_area.height.saturating_sub(4) as usize;
-                            let mut max_label_width = 4;
-                            for (i, header) in dataset.headers.iter().enumerate() {
-                                plot_text.push(format!("{}:", header));
-                                if let Some(dist) = description.distributions.get(i) {
-                                    if dist.is_empty() {
-                                        plot_text.push("  (No numeric data)".to_string());
-                                        continue;
-                                    }
-                                    max_label_width = dist.iter()
-                                        .map(|&(mid, _)| format!("{:.1}", mid).len())
-                                        .max()
-                                        .unwrap_or(4)
-                                        .max(max_label_width);
-                                    let max_val = dist.iter().map(|&(_, c)| c).max().unwrap_or(1) as f64;
-                                    let bar_heights: Vec<usize> = dist.iter()
-                                        .map(|&(_, cnt)| (cnt as f64 / max_val * max_height as f64).round() as usize)
-                                        .collect();
-                                    let step = max_val / max_height as f64;
-                                    for h in (0..=max_height).rev() {
-                                        let count = (h as f64 * step).round() as usize;
-                                        let mut line = format!("{:4} | ", count);
-                                        for (j, &height) in bar_heights.iter().enumerate() {
-                                            let mid_str = format!("{:.1}", dist[j].0);
-                                            let padding = max_label_width.saturating_sub(mid_str.len()) / 2;
-                                            if h == 0 {
-                                                line.push_str(&" ".repeat(padding));
-                                                line.push_str(&mid_str);
-                                                line.push_str(&" ".repeat(max_label_width.saturating_sub(mid_str.len() - padding)));
-                                            } else {
-                                                line.push_str(&" ".repeat(max_label_width / 2));
-                                                line.push(if height >= h { '█' } else { ' ' });
-                                                line.push_str(&" ".repeat(max_label_width / 2));
-                                            }
-                                            line.push(' ');
-                                        }
-                                        plot_text.push(line);
-                                    }
-                                }
-                                plot_text.push("".to_string());
-                            }
-                            let max_line_width = plot_text.iter().map(|s| s.len()).max().unwrap_or(0);
-                            let max_h_scroll = max_line_width.saturating_sub(content_width) as u16;
-                            if max_line_width > content_width && plots_h_scroll < max_h_scroll { plots_h_scroll += 1; }
+                    match state.tab_index {
+                        0 if visible_end(&widths, state.table_h_scroll, content_width) < widths.len() => state.table_h_scroll += 1,
+                        1 if !state.wrap_text && (state.details_h_scroll as usize) < details_width.saturating_sub(content_width) => state.details_h_scroll += 1,
+                        2 if !state.wrap_text && (state.advanced_h_scroll as usize) < advanced_width.saturating_sub(content_width) => state.advanced_h_scroll += 1,
+                        3 if state.corr_state.selected().is_some() => {
+                            let j = state.corr_column.map_or(0, |j| (j + 1).min(headers.len() - 1));
+                            state.corr_column = Some(j);
+                            state.corr_h_scroll = scroll_into_view(state.corr_h_scroll, j, corr_value_widths, corr_value_space);
                         }
+                        3 if visible_end(corr_value_widths, state.corr_h_scroll, corr_value_space) < corr_value_widths.len() => state.corr_h_scroll += 1,
+                        5 if visible_end(&data_widths, state.data_h_scroll, content_width) < data_widths.len() => state.data_h_scroll += 1,
+                        4 if !state.box_plots && (state.plots_h_scroll as usize) < histogram_width.saturating_sub(content_width) => state.plots_h_scroll += 1,
                         _ => {}
                     }
                 }
                 KeyCode::Up => {
-                    match tab_index {
-                        0 => if dataset.headers.len() > content_height {
-                            if let Some(selected) = table_state.selected() {
-                                table_state.select(Some(selected.saturating_sub(1)));
+                    match state.tab_index {
+                        0 => {
+                            if let Some(selected) = state.table_state.selected() {
+                                state.table_state.select(Some(selected.saturating_sub(1)));
                             } else {
-                                table_state.select(Some(dataset.headers.len().saturating_sub(1)));
+                                state.table_state.select(Some(headers.len().saturating_sub(1)));
                             }
                         }
                         1 => {
-                            let info_lines = 12usize;
-                            if info_lines > content_height && details_v_scroll > 0 { details_v_scroll -= 1; }
+                            let info_lines = displayed_line_count(details_strings, content_width, state.wrap_text);
+                            if info_lines > content_height && state.details_v_scroll > 0 { state.details_v_scroll -= 1; }
                         }
                         2 => {
-                            let advanced_lines = 9usize;
-                            if advanced_lines > content_height && advanced_v_scroll > 0 { advanced_v_scroll -= 1; }
+                            let advanced_lines = displayed_line_count(&advanced_strings, content_width, state.wrap_text);
+                            if advanced_lines > content_height && state.advanced_v_scroll > 0 { state.advanced_v_scroll -= 1; }
                         }
-                        3 => if dataset.headers.len() > content_height {
-                            if let Some(selected) = corr_state.selected() {
-                                corr_state.select(Some(selected.saturating_sub(1)));
+                        3 => {
+                            if let Some(selected) = state.corr_state.selected() {
+                                state.corr_state.select(Some(selected.saturating_sub(1)));
                             } else {
-                                corr_state.select(Some(dataset.headers.len().saturating_sub(1)));
+                                state.corr_state.select(Some(headers.len().saturating_sub(1)));
                             }
                         }
                         4 => {
-                            let max_height = content_area.height.saturating_sub(4) as usize;
-                            let plot_lines = dataset.headers.len() * (max_height + 2);
-                            if plot_lines > content_height && plots_v_scroll > 0 { plots_v_scroll -= 1; }
+                            let plot_lines = if state.box_plots { headers.len() * 4 } else { histogram.len() };
+                            if plot_lines > content_height && state.plots_v_scroll > 0 { state.plots_v_scroll -= 1; }
+                        }
+                        5 => {
+                            if let Some(selected) = state.data_state.selected() {
+                                state.data_state.select(Some(selected.saturating_sub(1)));
+                            } else {
+                                state.data_state.select(preview_rows.len().checked_sub(1));
+                            }
                         }
                         _ => {}
                     }
                 }
                 KeyCode::Down => {
-                    match tab_index {
-                        0 => if dataset.headers.len() > content_height {
-                            if let Some(selected) = table_state.selected() {
-                                table_state.select(Some((selected + 1).min(dataset.headers.len() - 1)));
+                    match state.tab_index {
+                        0 => {
+                            if let Some(selected) = state.table_state.selected() {
+                                state.table_state.select(Some((selected + 1).min(headers.len() - 1)));
                             } else {
-                                table_state.select(Some(0));
+                                state.table_state.select(Some(0));
                             }
                         }
                         1 => {
-                            let info_lines = 12usize;
+                            let info_lines = displayed_line_count(details_strings, content_width, state.wrap_text);
                             let max_v_scroll = (info_lines.saturating_sub(content_height)) as u16;
-                            if info_lines > content_height && details_v_scroll < max_v_scroll { details_v_scroll += 1; }
+                            if info_lines > content_height && state.details_v_scroll < max_v_scroll { state.details_v_scroll += 1; }
                         }
                         2 => {
-                            let advanced_lines = 9usize;
+                            let advanced_lines = displayed_line_count(&advanced_strings, content_width, state.wrap_text);
                             let max_v_scroll = (advanced_lines.saturating_sub(content_height)) as u16;
-                            if advanced_lines > content_height && advanced_v_scroll < max_v_scroll { advanced_v_scroll += 1; }
+                            if advanced_lines > content_height && state.advanced_v_scroll < max_v_scroll { state.advanced_v_scroll += 1; }
                         }
-                        3 => if dataset.headers.len() > content_height {
-                            if let Some(selected) = corr_state.selected() {
-                                corr_state.select(Some((selected + 1).min(dataset.headers.len() - 1)));
+                        3 => {
+                            if let Some(selected) = state.corr_state.selected() {
+                                state.corr_state.select(Some((selected + 1).min(headers.len() - 1)));
                             } else {
-                                corr_state.select(Some(0));
+                                state.corr_state.select(Some(0));
                             }
                         }
                         4 => {
-                            let max_height = content_area.height.saturating_sub(4) as usize;
-                            let plot_lines = dataset.headers.len() * (max_height + 2);
+                            let plot_lines = if state.box_plots { headers.len() * 4 } else { histogram.len() };
                             let max_v_scroll = (plot_lines.saturating_sub(content_height)) as u16;
-                            if plot_lines > content_height && plots_v_scroll < max_v_scroll { plots_v_scroll += 1; }
+                            if plot_lines > content_height && state.plots_v_scroll < max_v_scroll { state.plots_v_scroll += 1; }
+                        }
+                        5 if !preview_rows.is_empty() => {
+                            if let Some(selected) = state.data_state.selected() {
+                                state.data_state.select(Some((selected + 1).min(preview_rows.len() - 1)));
+                            } else {
+                                state.data_state.select(Some(0));
+                            }
                         }
                         _ => {}
                     }
                 }
+                KeyCode::PageUp | KeyCode::PageDown | KeyCode::Home | KeyCode::End => {
+                    // Tables lose a line to their header row.
+                    let page = content_height.saturating_sub(1).max(1) as isize;
+                    let delta = match key.code {
+                        KeyCode::PageUp => -page,
+                        KeyCode::PageDown => page,
+                        KeyCode::Home => isize::MIN / 2,
+                        _ => isize::MAX / 2,
+                    };
+                    match state.tab_index {
+                        0 => state.table_state.select(Some(step(state.table_state.selected().unwrap_or(0), delta, headers.len() - 1))),
+                        1 => {
+                            let info_lines = displayed_line_count(details_strings, content_width, state.wrap_text);
+                            state.details_v_scroll = step(state.details_v_scroll as usize, delta, info_lines.saturating_sub(content_height)) as u16;
+                        }
+                        2 => {
+                            let advanced_lines = displayed_line_count(&advanced_strings, content_width, state.wrap_text);
+                            state.advanced_v_scroll = step(state.advanced_v_scroll as usize, delta, advanced_lines.saturating_sub(content_height)) as u16;
+                        }
+                        3 => state.corr_state.select(Some(step(state.corr_state.selected().unwrap_or(0), delta, headers.len() - 1))),
+                        4 => {
+                            let plot_lines = if state.box_plots { headers.len() * 4 } else { histogram.len() };
+                            state.plots_v_scroll = step(state.plots_v_scroll as usize, delta, plot_lines.saturating_sub(content_height)) as u16;
+                        }
+                        5 if !preview_rows.is_empty() => state.data_state.select(Some(step(state.data_state.selected().unwrap_or(0), delta, preview_rows.len() - 1))),
+                        _ => {}
+                    }
+                }
                 _ => {}
             }
         }
     }
 
-    disable_raw_mode().map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen).map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
-    terminal.show_cursor().map_err(|e| PrestoError::InvalidNumeric(e.to_string()))?;
+    Ok(None)
+}
 
-    Ok(())
+// Moves `current` by `delta`, clamped to `0..=max`.
+pub(crate) fn step(current: usize, delta: isize, max: usize) -> usize {
+    current.saturating_add_signed(delta).min(max)
+}
+
+// Rows shown by the Data tab: `0..head_end` then `tail_start..total_rows`, with
+// a gap between them when `tail_start > head_end`.
+pub(crate) fn preview_bounds(total_rows: usize, options: &TuiOptions) -> (usize, usize) {
+    let head_end = options.head_rows.min(total_rows);
+    let tail_start = total_rows.saturating_sub(options.tail_rows).max(head_end);
+    (head_end, tail_start)
+}
+
+// Shrinks preferred widths proportionally (down to `min` each) so the columns and the
+// one-cell gaps between them fit in `available`; below that the table scrolls sideways.
+pub(crate) fn fit_widths(preferred: &[usize], min: usize, available: usize) -> Vec<usize> {
+    let spacing = preferred.len().saturating_sub(1);
+    let total: usize = preferred.iter().sum();
+    if total + spacing <= available {
+        return preferred.to_vec();
+    }
+    let floor = min * preferred.len();
+    let budget = available.saturating_sub(spacing).saturating_sub(floor);
+    let excess = total.saturating_sub(floor).max(1);
+    preferred.iter().map(|&w| min + w.saturating_sub(min) * budget / excess).collect()
+}
+
+pub(crate) fn visible_end(widths: &[usize], start: usize, available: usize) -> usize {
+    let mut used = 0;
+    for (i, &w) in widths.iter().enumerate().skip(start) {
+        used += w + usize::from(i > start);
+        if used > available {
+            return i.max(start + 1);
+        }
+    }
+    widths.len()
+}
+
+// Smallest change to a horizontal `scroll` offset that puts `column` on screen.
+pub(crate) fn scroll_into_view(mut scroll: usize, column: usize, widths: &[usize], available: usize) -> usize {
+    if column < scroll {
+        return column;
+    }
+    while visible_end(widths, scroll, available) <= column && scroll < column {
+        scroll += 1;
+    }
+    scroll
+}
+
+// Column indices ordered by missing count, worst first; ties keep file order.
+pub(crate) fn columns_by_missing(missing: &[usize]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..missing.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(missing[i]));
+    order
+}
+
+pub(crate) fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+
+pub(crate) struct Command {
+    pub(crate) name: &'static str,
+    tab: Option<usize>,
+    key: Option<char>,
+}
+
+const COMMANDS: &[Command] = &[
+    Command { name: "stats", tab: Some(0), key: None },
+    Command { name: "details", tab: Some(1), key: None },
+    Command { name: "advanced", tab: Some(2), key: None },
+    Command { name: "correlations", tab: Some(3), key: None },
+    Command { name: "plots", tab: Some(4), key: None },
+    Command { name: "data", tab: Some(5), key: None },
+    Command { name: "box plots", tab: Some(4), key: Some('b') },
+    Command { name: "retype column", tab: Some(0), key: Some('t') },
+    Command { name: "missing by column", tab: Some(1), key: Some('m') },
+    Command { name: "raise outlier threshold", tab: Some(0), key: Some('+') },
+    Command { name: "lower outlier threshold", tab: Some(0), key: Some('-') },
+    Command { name: "export report", tab: None, key: Some('e') },
+    Command { name: "export tab", tab: None, key: Some('x') },
+    Command { name: "export format", tab: None, key: Some('E') },
+    Command { name: "copy row", tab: None, key: Some('c') },
+    Command { name: "snapshot", tab: None, key: Some('s') },
+    Command { name: "wrap lines", tab: None, key: Some('w') },
+    Command { name: "legend", tab: None, key: Some('?') },
+    Command { name: "quit", tab: None, key: Some('q') },
+];
+
+// Lower is better: prefix matches beat substring matches, which beat in-order
+// subsequence matches; among subsequences, fewer skipped characters wins.
+pub(crate) fn fuzzy_score(query: &str, name: &str) -> Option<usize> {
+    if name.starts_with(query) {
+        return Some(0);
+    }
+    if name.contains(query) {
+        return Some(1);
+    }
+    let mut chars = name.chars().enumerate();
+    let mut last = 0;
+    let mut gaps = 0;
+    for q in query.chars() {
+        let (pos, _) = chars.find(|&(_, c)| c == q)?;
+        gaps += pos - last;
+        last = pos + 1;
+    }
+    Some(2 + gaps)
+}
+
+pub(crate) fn best_command(query: &str) -> Option<&'static Command> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    COMMANDS.iter()
+        .filter_map(|command| fuzzy_score(&query, command.name).map(|score| (score, command)))
+        .min_by_key(|&(score, command)| (score, command.name.len()))
+        .map(|(_, command)| command)
+}
+
+pub(crate) fn legend_lines(thresholds: &ShapeThresholds, outlier_threshold: f64) -> Vec<String> {
+    vec![
+        "Skewness (asymmetry of the distribution):".to_string(),
+        format!("  Highly +ve skewed: skew > {:.1} (long right tail)", thresholds.high_skew),
+        format!("  Mod. +ve skewed: {:.1} < skew <= {:.1}", thresholds.moderate_skew, thresholds.high_skew),
+        format!("  Symmetric: {:.1} <= skew <= {:.1}", -thresholds.moderate_skew, thresholds.moderate_skew),
+        format!("  Mod. -ve skewed: {:.1} <= skew < {:.1}", -thresholds.high_skew, -thresholds.moderate_skew),
+        format!("  Highly -ve skewed: skew < {:.1} (long left tail)", -thresholds.high_skew),
+        String::new(),
+        "Kurtosis (excess over a normal distribution, which scores 0):".to_string(),
+        format!("  Leptokurtic: kurtosis > {:.1} (heavy tails, sharp peak)", thresholds.excess_kurtosis),
+        format!("  Mesokurtic: kurtosis = {:.1} (normal-like tails)", thresholds.excess_kurtosis),
+        format!("  Platykurtic: kurtosis < {:.1} (light tails, flat peak)", thresholds.excess_kurtosis),
+        String::new(),
+        "MAD: median absolute deviation from the median.".to_string(),
+        format!("Outliers: values more than {:.1} standard deviations from the mean (+/- to adjust).", outlier_threshold),
+    ]
+}
+
+// How long a status message shown at `shown_at` stays on screen.
+pub(crate) fn status_time_left(shown_at: Instant) -> Duration {
+    STATUS_TIMEOUT.saturating_sub(shown_at.elapsed())
+}
+
+// Keeps a table selection in range after a reload drops columns.
+pub(crate) fn clamp_selection(table_state: &mut TableState, len: usize) {
+    if table_state.selected().is_some_and(|i| i >= len) {
+        table_state.select(len.checked_sub(1));
+    }
+}
+
+pub(crate) fn stats_row_cells(header: &str, stats: &ColumnStats, distribution: &[(f64, usize)], outlier_count: usize, thresholds: &ShapeThresholds) -> Vec<String> {
+    let skew_desc = stats.skewness.map(|s| thresholds.skew_label(s)).unwrap_or("N/A");
+    let kurt_desc = stats.kurtosis.map(|k| thresholds.kurtosis_label(k)).unwrap_or("N/A");
+    vec![
+        header.to_string(),
+        distribution_sparkline(distribution),
+        stats.mean.map_or("N/A".to_string(), |v| format!("{:.2}", v)),
+        stats.median.map_or("N/A".to_string(), |v| format!("{:.2}", v)),
+        stats.std_dev.map_or("N/A".to_string(), |v| format!("{:.2}", v)),
+        stats.variance.map_or("N/A".to_string(), |v| format!("{:.2}", v)),
+        stats.mad.map_or("N/A".to_string(), |v| format!("{:.2}", v)),
+        stats.min.map_or("N/A".to_string(), |v| format!("{:.2}", v)),
+        stats.max.map_or("N/A".to_string(), |v| format!("{:.2}", v)),
+        stats.skewness.map_or("N/A".to_string(), |v| format!("{:.2} ({})", v, skew_desc)),
+        stats.kurtosis.map_or("N/A".to_string(), |v| format!("{:.2} ({})", v, kurt_desc)),
+        outlier_count.to_string(),
+    ]
+}
+
+#[cfg(feature = "clipboard")]
+fn copy_to_clipboard(text: &str) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text.to_string()))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn copy_to_clipboard(_text: &str) -> Result<(), String> {
+    Err("built without the `clipboard` feature".to_string())
+}
+
+#[cfg(feature = "snapshot")]
+fn save_snapshot(buffer: &ratatui::buffer::Buffer) -> Result<(), String> {
+    std::fs::write(SNAPSHOT_PATH, crate::snapshot::buffer_to_svg(buffer)).map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "snapshot"))]
+fn save_snapshot(_buffer: &ratatui::buffer::Buffer) -> Result<(), String> {
+    Err("built without the `snapshot` feature".to_string())
+}
+
+// One bar chart per column, `max_height` rows tall plus an axis row of bin midpoints.
+pub(crate) fn histogram_lines(headers: &[String], distributions: &[Vec<(f64, usize)>], max_height: usize) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, header) in headers.iter().enumerate() {
+        lines.push(Line::from(Span::styled(format!("{}:", header), Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD))));
+        if let Some(dist) = distributions.get(i) {
+            if dist.is_empty() {
+                lines.push(Line::from(Span::raw("  (No numeric data)")));
+                continue;
+            }
+            let max_val = dist.iter().map(|&(_, c)| c).max().unwrap_or(1) as f64;
+            let bar_heights: Vec<usize> = dist.iter()
+                .map(|&(_, cnt)| (cnt as f64 / max_val * max_height as f64).round() as usize)
+                .collect();
+            let max_label_width = dist.iter()
+                .map(|&(mid, _)| format!("{:.1}", mid).len())
+                .max()
+                .unwrap_or(4);
+            let step = max_val / max_height as f64;
+            for h in (0..=max_height).rev() {
+                let count = (h as f64 * step).round() as usize;
+                let mut line = format!("{:4} | ", count);
+                for (j, &height) in bar_heights.iter().enumerate() {
+                    let mid_str = format!("{:.1}", dist[j].0);
+                    let padding = max_label_width.saturating_sub(mid_str.len()) / 2;
+                    if h == 0 {
+                        line.push_str(&" ".repeat(padding));
+                        line.push_str(&mid_str);
+                        line.push_str(&" ".repeat(max_label_width.saturating_sub(mid_str.len() - padding)));
+                    } else {
+                        line.push_str(&" ".repeat(max_label_width / 2));
+                        line.push(if height >= h { '█' } else { ' ' });
+                        line.push_str(&" ".repeat(max_label_width / 2));
+                    }
+                    line.push(' ');
+                }
+                lines.push(Line::from(Span::raw(line)));
+            }
+        }
+        lines.push(Line::from(Span::raw("")));
+    }
+    lines
+}
+
+fn field_lines<'a>(fields: &'a [(&'a str, Color, String)]) -> Vec<Line<'a>> {
+    fields.iter()
+        .map(|(label, color, value)| Line::from(vec![Span::styled(*label, Style::default().fg(*color)), Span::raw(value.as_str())]))
+        .collect()
+}
+
+pub(crate) fn displayed_line_count(lines: &[String], width: usize, wrap: bool) -> usize {
+    if !wrap || width == 0 {
+        return lines.len();
+    }
+    lines
+        .iter()
+        .map(|line| line.chars().count().div_ceil(width).max(1))
+        .sum()
+}
+
+pub(crate) fn box_plot_lines(stats: &ColumnStats, width: usize) -> Option<(String, String)> {
+    let (min, q1, median, q3, max) = stats.five_number_summary()?;
+    let labels = format!(
+        "min {:.2}  q1 {:.2}  median {:.2}  q3 {:.2}  max {:.2}",
+        min, q1, median, q3, max
+    );
+    let width = width.max(10);
+    let range = max - min;
+    if range <= 0.0 {
+        return Some(("|".to_string(), labels));
+    }
+    let pos = |v: f64| (((v - min) / range) * (width - 1) as f64).round() as usize;
+    let iqr = q3 - q1;
+    let lower = (q1 - 1.5 * iqr).max(min);
+    let upper = (q3 + 1.5 * iqr).min(max);
+    let mut line = vec![' '; width];
+    for c in &mut line[pos(lower)..=pos(upper)] {
+        *c = '-';
+    }
+    for c in &mut line[pos(q1)..=pos(q3)] {
+        *c = '=';
+    }
+    line[pos(lower)] = '|';
+    line[pos(upper)] = '|';
+    line[pos(q1)] = '[';
+    line[pos(q3)] = ']';
+    line[pos(median)] = '|';
+    if min < lower {
+        line[pos(min)] = 'o';
+    }
+    if max > upper {
+        line[pos(max)] = 'o';
+    }
+    Some((line.into_iter().collect(), labels))
+}
+
+// Cuts text longer than `max` characters, ending it with `…` so the cut is visible.
+pub(crate) fn clip(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        text.chars().take(max.saturating_sub(1)).chain(std::iter::once('…')).collect()
+    }
+}
+
+// Histogram bin counts scaled from zero, so an empty bin is always the lowest tick.
+pub(crate) fn distribution_sparkline(distribution: &[(f64, usize)]) -> String {
+    const TICKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = distribution.iter().map(|&(_, count)| count).max().unwrap_or(0);
+    if max == 0 {
+        return "N/A".to_string();
+    }
+    distribution.iter().map(|&(_, count)| TICKS[count * 7 / max]).collect()
+}
+
+fn sparkline(values: &[f64]) -> String {
+    const TICKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+    values
+        .iter()
+        .map(|&v| {
+            if range > 0.0 {
+                TICKS[(((v - min) / range) * 7.0).round() as usize]
+            } else {
+                TICKS[3]
+            }
+        })
+        .collect()
 }
@@ -1,13 +1,238 @@
-#[derive(Debug, serde::Serialize)]
+use regex::Regex;
+use std::borrow::Cow;
+use std::sync::LazyLock;
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum TypeInference {
     Integer,
     Float,
     String,
     Boolean,
     Mixed,
+    Categorical,
+    Date,
+}
+
+impl TypeInference {
+    pub fn next_override(&self) -> TypeInference {
+        match self {
+            TypeInference::Integer => TypeInference::Float,
+            TypeInference::Float => TypeInference::String,
+            TypeInference::String => TypeInference::Categorical,
+            TypeInference::Categorical => TypeInference::Date,
+            _ => TypeInference::Integer,
+        }
+    }
+}
+
+pub fn parse_date(val: &str) -> Option<chrono::NaiveDateTime> {
+    chrono::NaiveDateTime::parse_from_str(val, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(val, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum IntegerWidth {
+    I32,
+    I64,
+    I128,
+}
+
+impl std::fmt::Display for IntegerWidth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            IntegerWidth::I32 => "i32",
+            IntegerWidth::I64 => "i64",
+            IntegerWidth::I128 => "i128",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SemanticType {
+    Email,
+    Url,
+    Uuid,
+    Ip,
+    Phone,
+}
+
+impl SemanticType {
+    pub fn is_pii(&self) -> bool {
+        matches!(
+            self,
+            SemanticType::Email | SemanticType::Phone | SemanticType::Ip
+        )
+    }
+
+    fn matches(&self, val: &str) -> bool {
+        static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}$")
+                .unwrap()
+        });
+        static URL: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"^(?i)(https?|ftp)://[^\s/?#]+[^\s]*$").unwrap());
+        static UUID: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(
+                r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
+            )
+            .unwrap()
+        });
+        static PHONE: LazyLock<Regex> =
+            LazyLock::new(|| Regex::new(r"^\+?[0-9(][0-9 ().-]{5,}[0-9]$").unwrap());
+        match self {
+            SemanticType::Email => EMAIL.is_match(val),
+            SemanticType::Url => URL.is_match(val),
+            SemanticType::Uuid => UUID.is_match(val),
+            SemanticType::Ip => val.parse::<std::net::IpAddr>().is_ok(),
+            SemanticType::Phone => {
+                let digits = val.chars().filter(char::is_ascii_digit).count();
+                PHONE.is_match(val) && (7..=15).contains(&digits) && parse_date(val).is_none()
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for SemanticType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SemanticType::Email => "email",
+            SemanticType::Url => "url",
+            SemanticType::Uuid => "uuid",
+            SemanticType::Ip => "ip",
+            SemanticType::Phone => "phone",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+const SEMANTIC_MIN_MATCH_RATIO: f64 = 0.9;
+
+pub fn semantic_type(
+    rows: &[Vec<String>],
+    col_idx: usize,
+    column_type: &TypeInference,
+) -> Option<SemanticType> {
+    if !matches!(
+        column_type,
+        TypeInference::String | TypeInference::Categorical | TypeInference::Mixed
+    ) {
+        return None;
+    }
+    let values: Vec<&str> = rows
+        .iter()
+        .map(|row| row[col_idx].trim())
+        .filter(|v| !v.is_empty() && *v != "NA")
+        .collect();
+    if values.is_empty() {
+        return None;
+    }
+    [
+        SemanticType::Uuid,
+        SemanticType::Email,
+        SemanticType::Url,
+        SemanticType::Ip,
+        SemanticType::Phone,
+    ]
+    .into_iter()
+    .find(|semantic| {
+        let matched = values.iter().filter(|v| semantic.matches(v)).count();
+        matched as f64 >= SEMANTIC_MIN_MATCH_RATIO * values.len() as f64
+    })
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BooleanEncoding {
+    pub pairs: Vec<(String, String)>,
+    /// A column of only `1`/`0` is ambiguous between Boolean and Integer; when
+    /// set (the default) it is typed Boolean, otherwise Integer.
+    pub binary_as_boolean: bool,
+}
+
+impl Default for BooleanEncoding {
+    fn default() -> Self {
+        BooleanEncoding {
+            pairs: [
+                ("true", "false"),
+                ("yes", "no"),
+                ("y", "n"),
+                ("t", "f"),
+                ("on", "off"),
+            ]
+            .iter()
+            .map(|&(t, f)| (t.to_string(), f.to_string()))
+            .collect(),
+            binary_as_boolean: true,
+        }
+    }
+}
+
+impl BooleanEncoding {
+    pub fn parse(&self, val: &str) -> Option<bool> {
+        if self.binary_as_boolean {
+            match val {
+                "1" => return Some(true),
+                "0" => return Some(false),
+                _ => {}
+            }
+        }
+        let lower = val.to_lowercase();
+        self.pairs.iter().find_map(|(t, f)| {
+            if lower == t.to_lowercase() {
+                Some(true)
+            } else if lower == f.to_lowercase() {
+                Some(false)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TypeSampling {
+    #[default]
+    Full,
+    Head(usize),
+    Random(usize),
+}
+
+pub fn sample_rows(
+    rows: &[Vec<String>],
+    sampling: TypeSampling,
+    seed: Option<u64>,
+) -> Cow<'_, [Vec<String>]> {
+    match sampling {
+        TypeSampling::Full => Cow::Borrowed(rows),
+        TypeSampling::Head(n) => Cow::Borrowed(&rows[..n.min(rows.len())]),
+        TypeSampling::Random(n) if n < rows.len() => {
+            let mut indices =
+                rand::seq::index::sample(&mut crate::seeded_rng(seed, 0), rows.len(), n).into_vec();
+            indices.sort_unstable();
+            Cow::Owned(indices.into_iter().map(|i| rows[i].clone()).collect())
+        }
+        TypeSampling::Random(_) => Cow::Borrowed(rows),
+    }
 }
 
 pub fn infer_type(rows: &[Vec<String>], col_idx: usize) -> TypeInference {
+    infer_type_with(rows, col_idx, &BooleanEncoding::default())
+}
+
+pub fn infer_type_with(
+    rows: &[Vec<String>],
+    col_idx: usize,
+    encoding: &BooleanEncoding,
+) -> TypeInference {
     let mut is_int = true;
     let mut is_float = true;
     let mut is_bool = true;
@@ -17,13 +242,13 @@ pub fn infer_type(rows: &[Vec<String>], col_idx: usize) -> TypeInference {
         if val.is_empty() || val == "NA" {
             continue;
         }
-        if is_int && val.parse::<i64>().is_err() {
+        if is_int && val.parse::<i128>().is_err() {
             is_int = false;
         }
         if is_float && val.parse::<f64>().is_err() {
             is_float = false;
         }
-        if is_bool && !matches!(val.to_lowercase().as_str(), "true" | "false" | "1" | "0") {
+        if is_bool && encoding.parse(val).is_none() {
             is_bool = false;
         }
         if !is_int && !is_float && !is_bool {
@@ -31,7 +256,7 @@ pub fn infer_type(rows: &[Vec<String>], col_idx: usize) -> TypeInference {
         }
     }
 
-    if is_int && !is_float && !is_bool {
+    if is_int && !is_bool {
         TypeInference::Integer
     } else if is_float && !is_bool {
         TypeInference::Float
@@ -41,3 +266,204 @@ pub fn infer_type(rows: &[Vec<String>], col_idx: usize) -> TypeInference {
         TypeInference::Mixed
     }
 }
+
+pub fn boolean_positive_rate(
+    rows: &[Vec<String>],
+    col_idx: usize,
+    encoding: &BooleanEncoding,
+) -> Option<f64> {
+    let parsed: Vec<bool> = rows
+        .iter()
+        .filter_map(|row| encoding.parse(&row[col_idx]))
+        .collect();
+    if parsed.is_empty() {
+        return None;
+    }
+    Some(parsed.iter().filter(|&&b| b).count() as f64 / parsed.len() as f64)
+}
+
+pub fn type_confidence(
+    rows: &[Vec<String>],
+    col_idx: usize,
+    inferred: &TypeInference,
+    encoding: &BooleanEncoding,
+) -> f64 {
+    let values: Vec<&str> = rows
+        .iter()
+        .map(|row| row[col_idx].as_str())
+        .filter(|&v| !v.is_empty() && v != "NA")
+        .collect();
+    if values.is_empty() {
+        return 1.0;
+    }
+    let consistent = values
+        .iter()
+        .filter(|&&v| match inferred {
+            TypeInference::Integer => v.parse::<i128>().is_ok(),
+            TypeInference::Float => v.parse::<f64>().is_ok(),
+            TypeInference::Boolean => encoding.parse(v).is_some(),
+            TypeInference::String => v.parse::<f64>().is_err() && encoding.parse(v).is_none(),
+            TypeInference::Mixed | TypeInference::Categorical => true,
+            TypeInference::Date => parse_date(v).is_some(),
+        })
+        .count();
+    consistent as f64 / values.len() as f64
+}
+
+const MISMATCH_SAMPLE_LIMIT: usize = 5;
+
+fn parses_as(val: &str, column_type: &TypeInference, encoding: &BooleanEncoding) -> bool {
+    match column_type {
+        TypeInference::Integer => val.parse::<i128>().is_ok(),
+        TypeInference::Float => val.parse::<f64>().is_ok(),
+        TypeInference::Boolean => encoding.parse(val).is_some(),
+        TypeInference::Date => parse_date(val).is_some(),
+        TypeInference::String | TypeInference::Mixed | TypeInference::Categorical => true,
+    }
+}
+
+// The type most values should parse as. For text and mixed columns this is the
+// best-fitting of integer, float, boolean and date, and only when it covers most
+// values.
+fn dominant_type(
+    values: &[&str],
+    inferred: &TypeInference,
+    encoding: &BooleanEncoding,
+) -> Option<TypeInference> {
+    match inferred {
+        TypeInference::String | TypeInference::Mixed | TypeInference::Categorical => {
+            let (best, matches) = [
+                TypeInference::Integer,
+                TypeInference::Float,
+                TypeInference::Boolean,
+                TypeInference::Date,
+            ]
+            .into_iter()
+            .map(|candidate| {
+                let matches = values
+                    .iter()
+                    .filter(|v| parses_as(v, &candidate, encoding))
+                    .count();
+                (candidate, matches)
+            })
+            .rev()
+            .max_by_key(|&(_, matches)| matches)?;
+            (matches * 2 > values.len()).then_some(best)
+        }
+        other => Some(other.clone()),
+    }
+}
+
+fn present_values(rows: &[Vec<String>], col_idx: usize) -> Vec<&str> {
+    rows.iter()
+        .map(|row| row[col_idx].as_str())
+        .filter(|&v| !v.is_empty() && v != "NA")
+        .collect()
+}
+
+// The dominant type and up to MISMATCH_SAMPLE_LIMIT distinct values that don't
+// parse as it; `None` when nothing breaks it.
+pub fn type_mismatch_samples(
+    rows: &[Vec<String>],
+    col_idx: usize,
+    inferred: &TypeInference,
+    encoding: &BooleanEncoding,
+) -> Option<(TypeInference, Vec<String>)> {
+    let values = present_values(rows, col_idx);
+    let expected = dominant_type(&values, inferred, encoding)?;
+    let mut samples: Vec<String> = Vec::new();
+    for val in values {
+        if samples.len() == MISMATCH_SAMPLE_LIMIT {
+            break;
+        }
+        if !parses_as(val, &expected, encoding) && !samples.iter().any(|s| s == val) {
+            samples.push(val.to_string());
+        }
+    }
+    (!samples.is_empty()).then_some((expected, samples))
+}
+
+/// Share of non-missing values that parse as the column's dominant type; `None`
+/// for free text with no dominant parseable type, or no values at all.
+pub fn parse_rate(
+    rows: &[Vec<String>],
+    col_idx: usize,
+    inferred: &TypeInference,
+    encoding: &BooleanEncoding,
+) -> Option<f64> {
+    let values = present_values(rows, col_idx);
+    if values.is_empty() {
+        return None;
+    }
+    let expected = dominant_type(&values, inferred, encoding)?;
+    let parsed = values
+        .iter()
+        .filter(|v| parses_as(v, &expected, encoding))
+        .count();
+    Some(parsed as f64 / values.len() as f64)
+}
+
+pub fn integer_width(rows: &[Vec<String>], col_idx: usize) -> Option<IntegerWidth> {
+    let mut widest = None;
+    for row in rows {
+        let val = &row[col_idx];
+        if val.is_empty() || val == "NA" {
+            continue;
+        }
+        let n = val.parse::<i128>().ok()?;
+        let width = if i32::try_from(n).is_ok() {
+            IntegerWidth::I32
+        } else if i64::try_from(n).is_ok() {
+            IntegerWidth::I64
+        } else {
+            IntegerWidth::I128
+        };
+        widest = widest.max(Some(width));
+    }
+    widest
+}
+
+pub fn has_leading_zeros(rows: &[Vec<String>], col_idx: usize) -> bool {
+    rows.iter().any(|row| {
+        let digits = row[col_idx].trim_start_matches(['-', '+']);
+        digits.len() > 1 && digits.starts_with('0') && digits.chars().all(|c| c.is_ascii_digit())
+    })
+}
+
+const CATEGORY_MAX_UNIQUE_RATIO: f64 = 0.5;
+
+pub fn recommended_dtype(
+    column_type: &TypeInference,
+    integer_width: Option<IntegerWidth>,
+    leading_zeros: bool,
+    date_like: bool,
+    cardinality: usize,
+    non_missing: usize,
+    missing: usize,
+) -> &'static str {
+    if non_missing == 0 {
+        return "string";
+    }
+    match column_type {
+        TypeInference::Boolean => "boolean",
+        TypeInference::Date => "datetime64[ns]",
+        TypeInference::Float => "float64",
+        TypeInference::Categorical => "category",
+        TypeInference::Integer if leading_zeros => "string",
+        TypeInference::Integer if integer_width == Some(IntegerWidth::I128) => "string",
+        // Plain numpy integers cannot hold NaN; pandas' nullable integer can.
+        TypeInference::Integer if missing > 0 => "Int64",
+        TypeInference::Integer => match integer_width {
+            Some(IntegerWidth::I32) => "int32",
+            Some(IntegerWidth::I128) => "string",
+            _ => "int64",
+        },
+        TypeInference::String if date_like => "datetime64[ns]",
+        TypeInference::String
+            if (cardinality as f64) < CATEGORY_MAX_UNIQUE_RATIO * non_missing as f64 =>
+        {
+            "category"
+        }
+        TypeInference::String | TypeInference::Mixed => "string",
+    }
+}